@@ -1,88 +1,767 @@
-use std::slice::Iter;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Bound, Not, Range, RangeBounds,
+    RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Shl, ShlAssign, Shr,
+    ShrAssign,
+};
+use std::sync::OnceLock;
 
-// TODO: see extern crate bitvector for better optimization
+const WORD_BITS: usize = u64::BITS as usize;
+
+// `Index` hands out references to these, the packed words have no addressable `bool`.
+static TRUE: bool = true;
+static FALSE: bool = false;
+
+/// Fixed-width bit vector, indexed most significant bit first: `bits[0]` is the MSB and
+/// `bits[bits.len() - 1]` is the LSB.
+///
+/// Binary bitwise operators (`&`, `|`, `^`) accept operands of different widths: the narrower
+/// one is zero-extended and the result has the width of the wider one. Unary operators and
+/// shifts always keep the width of their operand, bits shifted out are lost.
+#[derive(Clone)]
 pub struct Bits {
-    size: u8,
-    data: Vec<bool>,
+    size: usize,
+    // Packed least significant bit first: bit position `p` (`0` is the LSB) lives in
+    // `words[p / 64]`. Positions at or above `size` in the last word are always zero.
+    words: Vec<u64>,
+    // The bits as `bool`s MSB first, filled by the first `bits[a..b]` and cleared on writes.
+    unpacked: OnceLock<Vec<bool>>,
 }
 
 impl Bits {
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn zeros(size: usize) -> Self {
+        Bits {
+            size,
+            words: vec![0; size.div_ceil(WORD_BITS)],
+            unpacked: OnceLock::new(),
+        }
+    }
+
+    pub fn ones(size: usize) -> Self {
+        let mut bits = Bits {
+            size,
+            words: vec![u64::MAX; size.div_ceil(WORD_BITS)],
+            unpacked: OnceLock::new(),
+        };
+        bits.clear_unused();
+        bits
     }
 
-    pub fn from_vector_b(bools: Vec<bool>, size: Option<u8>) -> Self {
-        let size = size.unwrap_or(bools.len() as u8);
-        Bits { size, data: bools }
+    pub fn from_vector_b(bools: Vec<bool>, size: Option<usize>) -> Self {
+        Bits::from_slice_b(&bools, size)
     }
 
-    pub fn from_slice_b(bools: &[bool], size: Option<u8>) -> Self {
-        let size = size.unwrap_or(bools.len() as u8);
-        let padding = size as usize - bools.len().min(size as usize);
+    pub fn from_slice_b(bools: &[bool], size: Option<usize>) -> Self {
+        let size = size.unwrap_or(bools.len());
+        let taken = bools.len().min(size);
+        let padding = size - taken;
 
-        let data = std::iter::repeat(false)
-            .take(padding)
-            .chain(bools.iter().cloned().take(size as usize - padding))
-            .collect::<Vec<bool>>();
-        Bits::from_vector_b(data, Some(size))
+        let mut bits = Bits::zeros(size);
+        for (index, &bit) in bools[..taken].iter().enumerate() {
+            bits.set(padding + index, bit);
+        }
+        bits
     }
 
-    pub fn from_slice_i(ints: &[u8], size: Option<u8>) -> Self {
+    pub fn from_slice_i(ints: &[u8], size: Option<usize>) -> Self {
         let bools = ints.iter().map(|&n| n == 1).collect::<Vec<bool>>();
         Bits::from_slice_b(&bools, size)
     }
 
     pub fn from_bits(bits: &Bits) -> Self {
-        Bits::from_vector_b(bits.data.clone(), Some(bits.size))
+        bits.clone()
     }
 
-    pub fn from_int(value: u32, size: Option<u8>) -> Self {
-        let real_size = if let Some(size) = size {
-            size
-        } else {
-            let size_str = format!("{value:b}");
-            size_str.len() as u8
-        };
+    pub fn from_int(value: u32, size: Option<usize>) -> Self {
+        Bits::try_from_int(value, size).unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        if value >= u32::pow(2, real_size.into()) {
-            panic!(
-                "The given value is bigger than the value that can be represented with {} bits",
-                real_size
-            );
-        }
+    pub fn try_from_int(value: u32, size: Option<usize>) -> Result<Self, ConversionError> {
+        Bits::try_from_u128(value.into(), size)
+    }
 
-        let data = (0..real_size).rev().map(|n| ((value >> n) & 1) != 0);
+    pub fn to_int(&self) -> u32 {
+        u32::try_from(self).unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        Bits {
-            size: real_size,
-            data: data.collect(),
+    pub fn try_from_u64(value: u64, size: Option<usize>) -> Result<Self, ConversionError> {
+        Bits::try_from_u128(value.into(), size)
+    }
+
+    /// Encodes `value` on `size` bits, or on the minimal width (at least one bit) if `size` is
+    /// `None`.
+    pub fn try_from_u128(value: u128, size: Option<usize>) -> Result<Self, ConversionError> {
+        let required = ((u128::BITS - value.leading_zeros()) as usize).max(1);
+        let size = size.unwrap_or(required);
+        if value != 0 && required > size {
+            return Err(ConversionError::ValueTooLarge { required, size });
         }
+
+        let mut bits = Bits::zeros(size);
+        for (i, word) in bits.words.iter_mut().take(2).enumerate() {
+            *word = (value >> (i * WORD_BITS)) as u64;
+        }
+        bits.clear_unused();
+        Ok(bits)
     }
 
-    pub fn to_int(&self) -> u32 {
-        self.data
+    /// Big-endian bytes, `8 * bytes.len()` bits wide.
+    pub fn from_bytes_be(bytes: &[u8]) -> Self {
+        let size = bytes.len() * 8;
+        Bits::from_fn(size, |position| {
+            (bytes[bytes.len() - 1 - position / 8] >> (position % 8)) & 1 != 0
+        })
+    }
+
+    /// Little-endian bytes, `8 * bytes.len()` bits wide.
+    pub fn from_bytes_le(bytes: &[u8]) -> Self {
+        let size = bytes.len() * 8;
+        Bits::from_fn(size, |position| {
+            (bytes[position / 8] >> (position % 8)) & 1 != 0
+        })
+    }
+
+    /// Big-endian bytes, zero-extended to a whole number of bytes.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Little-endian bytes, zero-extended to a whole number of bytes.
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        (0..self.size.div_ceil(8))
+            .map(|byte| (self.word(byte * 8 / WORD_BITS) >> (byte * 8 % WORD_BITS)) as u8)
+            .collect()
+    }
+
+    /// Number of bits needed to hold the unsigned value, ignoring leading zeros.
+    pub fn significant_len(&self) -> usize {
+        self.words
             .iter()
-            .rev()
             .enumerate()
-            .map(|(place, bit)| u32::from(*bit) << place)
-            .sum()
+            .rev()
+            .find(|(_, &word)| word != 0)
+            .map_or(0, |(i, word)| {
+                (i + 1) * WORD_BITS - word.leading_zeros() as usize
+            })
+    }
+
+    fn to_u128_unchecked(&self) -> u128 {
+        u128::from(self.word(0)) | (u128::from(self.word(1)) << WORD_BITS)
     }
 
     // pub fn reverse(&mut self) {
     //     self._data.reverse();
     // }
     pub fn data(&self) -> Vec<bool> {
-        self.data.clone()
+        self.iter().collect()
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            bits: self,
+            front: 0,
+            back: self.size,
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < self.size).then(|| self.bit(self.size - 1 - index))
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        if index >= self.size {
+            panic!("Index {} is out of range for {} bits", index, self.size);
+        }
+        self.set_bit(self.size - 1 - index, value);
+    }
+
+    /// Most significant bit, `false` for an empty value.
+    pub fn msb(&self) -> bool {
+        self.get(0).unwrap_or(false)
+    }
+
+    /// Least significant bit, `false` for an empty value.
+    pub fn lsb(&self) -> bool {
+        self.size > 0 && self.bit(0)
+    }
+
+    /// Concatenates `self` (high part) with `low` (low part), like Verilog's `{self, low}`.
+    pub fn concat(&self, low: &Bits) -> Bits {
+        Bits::from_fn(self.size + low.size, |position| {
+            if position < low.size {
+                low.bit(position)
+            } else {
+                self.bit(position - low.size)
+            }
+        })
+    }
+
+    /// Copies the bits in `range` into a new value. Positions follow indexing, so `0` is the MSB.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Bits {
+        self.view(range).to_bits()
+    }
+
+    /// Borrows the bits in `range` without copying them, unlike `bits[a..b]` which unpacks the
+    /// value once. Positions follow indexing, so `0` is the MSB.
+    pub fn view<R: RangeBounds<usize>>(&self, range: R) -> BitsView<'_> {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end || end > self.size {
+            panic!(
+                "Range {}..{} is out of range for {} bits",
+                start, end, self.size
+            );
+        }
+        BitsView {
+            bits: self,
+            start,
+            end,
+        }
+    }
+
+    /// Splits into `(high, low)` where `high` holds the first `mid` bits (MSB side).
+    pub fn split_at(&self, mid: usize) -> (Bits, Bits) {
+        (self.slice(..mid), self.slice(mid..))
+    }
+
+    /// Widens to `size` bits by padding with zeros on the MSB side.
+    pub fn zero_extend(&self, size: usize) -> Bits {
+        if size < self.size {
+            panic!("Cannot extend {} bits to {} bits", self.size, size);
+        }
+        let mut words = self.words.clone();
+        words.resize(size.div_ceil(WORD_BITS), 0);
+        Bits {
+            size,
+            words,
+            unpacked: OnceLock::new(),
+        }
+    }
+
+    /// Widens to `size` bits by replicating the current MSB.
+    pub fn sign_extend(&self, size: usize) -> Bits {
+        let mut bits = self.zero_extend(size);
+        if self.msb() {
+            for position in self.size..size {
+                bits.set_bit(position, true);
+            }
+        }
+        bits
+    }
+
+    /// Narrows to `size` bits by dropping bits on the MSB side.
+    pub fn truncate(&self, size: usize) -> Bits {
+        if size > self.size {
+            panic!("Cannot truncate {} bits to {} bits", self.size, size);
+        }
+        let mut bits = Bits {
+            size,
+            words: self.words[..size.div_ceil(WORD_BITS)].to_vec(),
+            unpacked: OnceLock::new(),
+        };
+        bits.clear_unused();
+        bits
+    }
+
+    /// Zero-extends or truncates to exactly `size` bits.
+    pub fn resize(&self, size: usize) -> Bits {
+        if size >= self.len() {
+            self.zero_extend(size)
+        } else {
+            self.truncate(size)
+        }
+    }
+
+    fn from_fn(size: usize, f: impl Fn(usize) -> bool) -> Bits {
+        let mut bits = Bits::zeros(size);
+        for position in 0..size {
+            if f(position) {
+                bits.set_bit(position, true);
+            }
+        }
+        bits
+    }
+
+    fn bit(&self, position: usize) -> bool {
+        (self.words[position / WORD_BITS] >> (position % WORD_BITS)) & 1 != 0
+    }
+
+    fn set_bit(&mut self, position: usize, value: bool) {
+        self.unpacked.take();
+        let mask = 1 << (position % WORD_BITS);
+        if value {
+            self.words[position / WORD_BITS] |= mask;
+        } else {
+            self.words[position / WORD_BITS] &= !mask;
+        }
+    }
+
+    fn unpacked(&self) -> &[bool] {
+        self.unpacked.get_or_init(|| self.iter().collect())
+    }
+
+    fn word(&self, i: usize) -> u64 {
+        self.words.get(i).copied().unwrap_or(0)
+    }
+
+    fn clear_unused(&mut self) {
+        let used = self.size % WORD_BITS;
+        if used != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << used) - 1;
+            }
+        }
+    }
+
+    fn zip_with(&self, other: &Bits, f: impl Fn(u64, u64) -> u64) -> Bits {
+        let size = self.size.max(other.size);
+        let mut bits = Bits {
+            size,
+            words: (0..size.div_ceil(WORD_BITS))
+                .map(|i| f(self.word(i), other.word(i)))
+                .collect(),
+            unpacked: OnceLock::new(),
+        };
+        bits.clear_unused();
+        bits
+    }
+
+    fn shifted_left(&self, shift: usize) -> Bits {
+        if shift >= self.size {
+            return Bits::zeros(self.size);
+        }
+        let (word_shift, bit_shift) = (shift / WORD_BITS, shift % WORD_BITS);
+        let mut bits = Bits {
+            size: self.size,
+            words: (0..self.words.len())
+                .map(|i| {
+                    if i < word_shift {
+                        return 0;
+                    }
+                    let word = self.word(i - word_shift) << bit_shift;
+                    if bit_shift == 0 || i == word_shift {
+                        word
+                    } else {
+                        word | (self.word(i - word_shift - 1) >> (WORD_BITS - bit_shift))
+                    }
+                })
+                .collect(),
+            unpacked: OnceLock::new(),
+        };
+        bits.clear_unused();
+        bits
+    }
+
+    fn shifted_right(&self, shift: usize) -> Bits {
+        if shift >= self.size {
+            return Bits::zeros(self.size);
+        }
+        let (word_shift, bit_shift) = (shift / WORD_BITS, shift % WORD_BITS);
+        Bits {
+            size: self.size,
+            words: (0..self.words.len())
+                .map(|i| {
+                    let word = self.word(i + word_shift) >> bit_shift;
+                    if bit_shift == 0 {
+                        word
+                    } else {
+                        word | (self.word(i + word_shift + 1) << (WORD_BITS - bit_shift))
+                    }
+                })
+                .collect(),
+            unpacked: OnceLock::new(),
+        }
+    }
+}
+
+/// Iterator over the bits of a [`Bits`], MSB first.
+#[derive(Clone)]
+pub struct Iter<'a> {
+    bits: &'a Bits,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.bits.bit(self.bits.size - self.front))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.bits.bit(self.bits.size - 1 - self.back))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+/// Borrowed range of a [`Bits`], from [`Bits::view`]. Indexing and iteration are MSB first,
+/// relative to the start of the range.
+#[derive(Clone, Copy)]
+pub struct BitsView<'a> {
+    bits: &'a Bits,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> BitsView<'a> {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < self.len()).then(|| self.bits[self.start + index])
+    }
+
+    pub fn iter(&self) -> Iter<'a> {
+        Iter {
+            bits: self.bits,
+            front: self.start,
+            back: self.end,
+        }
+    }
+
+    /// Copies the viewed bits into a new value.
+    pub fn to_bits(&self) -> Bits {
+        let low = self.bits.size - self.end;
+        Bits::from_fn(self.len(), |position| self.bits.bit(low + position))
+    }
+}
+
+impl<'a> IntoIterator for BitsView<'a> {
+    type Item = bool;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::ops::Index<usize> for BitsView<'_> {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &bool {
+        if index >= self.len() {
+            panic!("Index {} is out of range for {} bits", index, self.len());
+        }
+        &self.bits[self.start + index]
+    }
+}
+
+impl PartialEq<Bits> for BitsView<'_> {
+    fn eq(&self, other: &Bits) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> PartialEq<T> for BitsView<'_>
+where
+    T: AsRef<[bool]> + ?Sized,
+{
+    fn eq(&self, other: &T) -> bool {
+        let other = other.as_ref();
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+impl fmt::Debug for BitsView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_bits(), f)
+    }
+}
+
+/// Owning iterator over the bits of a [`Bits`], MSB first.
+#[derive(Clone)]
+pub struct IntoIter {
+    bits: Bits,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for IntoIter {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.bits.bit(self.bits.size - self.front))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.bits.bit(self.bits.size - 1 - self.back))
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl FusedIterator for IntoIter {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The value needs `required` bits but only `size` are available.
+    ValueTooLarge { required: usize, size: usize },
+    /// The bits hold a value needing `significant` bits, more than the `target` type holds.
+    TargetTooNarrow { significant: usize, target: usize },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::ValueTooLarge { required, size } => write!(
+                f,
+                "The given value is bigger than the value that can be represented with {} bits (needs {})",
+                size, required
+            ),
+            ConversionError::TargetTooNarrow { significant, target } => write!(
+                f,
+                "The value needs {} bits and does not fit in a {} bits integer",
+                significant, target
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+macro_rules! impl_int_conversions {
+    ($($t:ty => $capacity:expr),*) => {
+        $(
+            impl TryFrom<&Bits> for $t {
+                type Error = ConversionError;
+
+                fn try_from(bits: &Bits) -> Result<Self, Self::Error> {
+                    let significant = bits.significant_len();
+                    if significant > $capacity {
+                        return Err(ConversionError::TargetTooNarrow {
+                            significant,
+                            target: $capacity,
+                        });
+                    }
+                    Ok(bits.to_u128_unchecked() as $t)
+                }
+            }
+
+            impl TryFrom<Bits> for $t {
+                type Error = ConversionError;
+
+                fn try_from(bits: Bits) -> Result<Self, Self::Error> {
+                    <$t>::try_from(&bits)
+                }
+            }
+        )*
+    };
+}
+
+// `i64` reads the bits as an unsigned value, so only 63 of its bits are usable.
+impl_int_conversions!(u8 => 8, u16 => 16, u32 => 32, u64 => 64, u128 => 128, i64 => 63);
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Bits {
+                /// Encodes the value on the full width of the integer type.
+                fn from(value: $t) -> Self {
+                    Bits::try_from_u128(value.into(), Some(<$t>::BITS as usize)).unwrap()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128);
+
+impl FromIterator<bool> for Bits {
+    /// Collects bits MSB first.
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let bools = iter.into_iter().collect::<Vec<bool>>();
+        Bits::from_slice_b(&bools, None)
     }
+}
 
-    pub fn iter(&self) -> Iter<'_, bool> {
-        self.data.iter()
+impl Extend<bool> for Bits {
+    /// Appends bits on the LSB side, widening the value.
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        let low = iter.into_iter().collect::<Bits>();
+        *self = self.concat(&low);
     }
 }
 
+impl IntoIterator for Bits {
+    type Item = bool;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let back = self.size;
+        IntoIter {
+            bits: self,
+            front: 0,
+            back,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Bits {
+    type Item = bool;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Not for &Bits {
+    type Output = Bits;
+
+    fn not(self) -> Bits {
+        let mut bits = Bits {
+            size: self.size,
+            words: self.words.iter().map(|word| !word).collect(),
+            unpacked: OnceLock::new(),
+        };
+        bits.clear_unused();
+        bits
+    }
+}
+
+impl Not for Bits {
+    type Output = Bits;
+
+    fn not(self) -> Bits {
+        !&self
+    }
+}
+
+macro_rules! impl_bitwise_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $f:expr) => {
+        impl $op<&Bits> for &Bits {
+            type Output = Bits;
+
+            fn $method(self, rhs: &Bits) -> Bits {
+                self.zip_with(rhs, $f)
+            }
+        }
+
+        impl $op<Bits> for Bits {
+            type Output = Bits;
+
+            fn $method(self, rhs: Bits) -> Bits {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $op<&Bits> for Bits {
+            type Output = Bits;
+
+            fn $method(self, rhs: &Bits) -> Bits {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $op_assign<&Bits> for Bits {
+            fn $method_assign(&mut self, rhs: &Bits) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+
+        impl $op_assign<Bits> for Bits {
+            fn $method_assign(&mut self, rhs: Bits) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+    };
+}
+
+impl_bitwise_op!(BitAnd, bitand, BitAndAssign, bitand_assign, |a, b| a & b);
+impl_bitwise_op!(BitOr, bitor, BitOrAssign, bitor_assign, |a, b| a | b);
+impl_bitwise_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, |a, b| a ^ b);
+
+macro_rules! impl_shift_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $f:ident) => {
+        impl $op<usize> for &Bits {
+            type Output = Bits;
+
+            fn $method(self, shift: usize) -> Bits {
+                self.$f(shift)
+            }
+        }
+
+        impl $op<usize> for Bits {
+            type Output = Bits;
+
+            fn $method(self, shift: usize) -> Bits {
+                self.$f(shift)
+            }
+        }
+
+        impl $op_assign<usize> for Bits {
+            fn $method_assign(&mut self, shift: usize) {
+                *self = self.$f(shift);
+            }
+        }
+    };
+}
+
+impl_shift_op!(Shl, shl, ShlAssign, shl_assign, shifted_left);
+impl_shift_op!(Shr, shr, ShrAssign, shr_assign, shifted_right);
+
 impl PartialEq<Bits> for Bits {
     fn eq(&self, other: &Bits) -> bool {
-        self.to_int() == other.to_int()
+        let words = self.words.len().max(other.words.len());
+        (0..words).all(|i| self.word(i) == other.word(i))
     }
 }
 
@@ -91,19 +770,27 @@ where
     T: AsRef<[bool]> + ?Sized,
 {
     fn eq(&self, other: &T) -> bool {
-        self.to_int() == Bits::from_slice_b(other.as_ref(), None).to_int()
+        let other = other.as_ref();
+        let leading_zeros = other.iter().take_while(|&&bit| !bit).count();
+        let significant = &other[leading_zeros..];
+        self.significant_len() == significant.len()
+            && significant
+                .iter()
+                .rev()
+                .enumerate()
+                .all(|(position, &bit)| self.bit(position) == bit)
     }
 }
 
 impl PartialEq<Bits> for &[bool] {
     fn eq(&self, other: &Bits) -> bool {
-        other.data == *self
+        self.len() == other.len() && self.iter().copied().eq(other.iter())
     }
 }
 
 impl PartialEq<Bits> for [bool] {
     fn eq(&self, other: &Bits) -> bool {
-        other.data == *self
+        self.len() == other.len() && self.iter().copied().eq(other.iter())
     }
 }
 
@@ -113,17 +800,43 @@ impl PartialEq<Bits> for [bool] {
 //     }
 // }
 
-impl<Idx> std::ops::Index<Idx> for Bits
-where
-    Idx: std::slice::SliceIndex<[bool]>,
-{
-    type Output = Idx::Output;
+impl std::ops::Index<usize> for Bits {
+    type Output = bool;
 
-    fn index(&self, index: Idx) -> &Self::Output {
-        &self.data[index]
+    fn index(&self, index: usize) -> &bool {
+        match self.get(index) {
+            Some(true) => &TRUE,
+            Some(false) => &FALSE,
+            None => panic!("Index {} is out of range for {} bits", index, self.size),
+        }
     }
 }
 
+macro_rules! impl_range_index {
+    ($($range:ty),*) => {
+        $(
+            /// Unpacks the value once and borrows the range from that copy, which is cached
+            /// until the next write. [`Bits::view`] borrows a range without unpacking.
+            impl std::ops::Index<$range> for Bits {
+                type Output = [bool];
+
+                fn index(&self, range: $range) -> &[bool] {
+                    &self.unpacked()[range]
+                }
+            }
+        )*
+    };
+}
+
+impl_range_index!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeTo<usize>,
+    RangeInclusive<usize>,
+    RangeToInclusive<usize>,
+    RangeFull
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,6 +882,121 @@ mod tests {
         Bits::from_int(253, Some(4));
     }
 
+    #[test]
+    fn bits_wide() {
+        let bits = Bits::try_from_u128(u128::MAX, Some(200)).unwrap();
+        assert!(bits.len() == 200);
+        assert!(bits.significant_len() == 128);
+        assert_eq!(u128::try_from(&bits), Ok(u128::MAX));
+        assert!(bits == Bits::from(u128::MAX));
+        assert!((&bits << 72).len() == 200);
+        assert!((&bits << 72).msb());
+    }
+
+    #[test]
+    fn bits_u64_roundtrip() {
+        for value in [0, 1, 0xDEAD_BEEF, u64::MAX] {
+            let bits = Bits::from(value);
+            assert!(bits.len() == 64);
+            assert_eq!(u64::try_from(&bits), Ok(value));
+        }
+        let bits = Bits::try_from_u64(5, None).unwrap();
+        assert!(bits.len() == 3);
+    }
+
+    #[test]
+    fn bits_checked_conversions() {
+        assert_eq!(
+            Bits::try_from_u64(256, Some(8)),
+            Err(ConversionError::ValueTooLarge {
+                required: 9,
+                size: 8
+            })
+        );
+        let bits = Bits::from(u64::MAX);
+        assert_eq!(
+            u32::try_from(&bits),
+            Err(ConversionError::TargetTooNarrow {
+                significant: 64,
+                target: 32
+            })
+        );
+        assert_eq!(
+            i64::try_from(&bits),
+            Err(ConversionError::TargetTooNarrow {
+                significant: 64,
+                target: 63
+            })
+        );
+        assert_eq!(i64::try_from(&Bits::from(i64::MAX as u64)), Ok(i64::MAX));
+        assert_eq!(u8::try_from(Bits::from_int(255, Some(40))), Ok(255));
+        assert_eq!(
+            Bits::try_from_int(16, Some(4)),
+            Err(ConversionError::ValueTooLarge {
+                required: 5,
+                size: 4
+            })
+        );
+    }
+
+    #[test]
+    fn bits_bytes() {
+        let bits = Bits::from_bytes_be(&[0x12, 0x34, 0x56]);
+        assert!(bits.len() == 24);
+        assert!(bits.to_int() == 0x123456);
+        assert!(Bits::from_bytes_le(&[0x56, 0x34, 0x12]) == bits);
+        assert!(bits.to_bytes_be() == vec![0x12, 0x34, 0x56]);
+        assert!(bits.to_bytes_le() == vec![0x56, 0x34, 0x12]);
+        assert!(Bits::from_int(0x1FF, Some(9)).to_bytes_be() == vec![0x01, 0xFF]);
+    }
+
+    #[test]
+    fn bits_index_across_words() {
+        let mut bits = Bits::zeros(130);
+        bits.set(0, true);
+        bits.set(65, true);
+        bits.set(129, true);
+        assert!(bits[0] && bits[65] && bits[129]);
+        assert!(!bits[1] && !bits[64] && !bits[128]);
+        assert!(bits.get(130).is_none());
+        assert!(bits.significant_len() == 130);
+        assert!(bits.iter().filter(|&b| b).count() == 3);
+        assert!(bits.iter().rev().position(|b| b) == Some(0));
+        assert!(bits.iter().len() == 130);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bits_index_out_of_range() {
+        let bits = Bits::zeros(8);
+        let _ = bits[8];
+    }
+
+    #[test]
+    fn bits_shifts_across_words() {
+        let bits = (0..150).map(|n| n % 3 == 0 || n % 7 == 0).collect::<Bits>();
+        let bools = bits.data();
+        for shift in [0, 1, 63, 64, 65, 127, 128, 149, 150, 200] {
+            let shift_left = (&bits << shift).data();
+            let shift_right = (&bits >> shift).data();
+            for index in 0..bools.len() {
+                let expected_left = index + shift < bools.len() && bools[index + shift];
+                let expected_right = index >= shift && bools[index - shift];
+                assert!(shift_left[index] == expected_left);
+                assert!(shift_right[index] == expected_right);
+            }
+        }
+    }
+
+    #[test]
+    fn bits_not_keeps_unused_bits_clear() {
+        let bits = !Bits::zeros(70);
+        assert!(bits == Bits::ones(70));
+        assert!(bits.significant_len() == 70);
+        assert!(!bits.clone() == Bits::zeros(3));
+        assert!((bits >> 69).to_int() == 1);
+    }
+
     #[test]
     fn bits_equal() {
         let a = Bits::from_int(253, Some(8));
@@ -180,6 +1008,134 @@ mod tests {
         assert!(a == b);
     }
 
+    #[test]
+    fn bits_bitwise_ops() {
+        let a = Bits::from_int(0b1100, Some(4));
+        let b = Bits::from_int(0b1010, Some(4));
+        assert!((&a & &b) == [true, false, false, false]);
+        assert!((&a | &b) == [true, true, true, false]);
+        assert!((&a ^ &b) == [false, true, true, false]);
+        assert!(!&a == [false, false, true, true]);
+        assert!((!a).len() == 4);
+    }
+
+    #[test]
+    fn bits_bitwise_ops_width_mismatch() {
+        let a = Bits::from_int(0b1111_0000, Some(8));
+        let b = Bits::from_int(0b1010, Some(4));
+        let result = &a | &b;
+        assert!(result.len() == 8);
+        assert!(result.to_int() == 0b1111_1010);
+
+        let mut c = Bits::from_int(0b11, Some(2));
+        c ^= &a;
+        assert!(c.len() == 8);
+        assert!(c.to_int() == 0b1111_0011);
+    }
+
+    #[test]
+    fn bits_shifts() {
+        let a = Bits::from_int(0b1011, Some(4));
+        assert!((&a << 1).to_int() == 0b0110);
+        assert!((&a >> 1).to_int() == 0b0101);
+        assert!((&a << 1).len() == 4);
+        assert!((&a << 8).to_int() == 0);
+        assert!((&a >> 8).len() == 4);
+
+        let mut b = a.clone();
+        b <<= 2;
+        assert!(b.to_int() == 0b1100);
+        b >>= 3;
+        assert!(b.to_int() == 0b0001);
+    }
+
+    #[test]
+    fn bits_concat_split() {
+        let high = Bits::from_int(0b10, Some(2));
+        let low = Bits::from_int(0b011, Some(3));
+        let bits = high.concat(&low);
+        assert!(bits.len() == 5);
+        assert!(bits.to_int() == 0b10011);
+
+        let (h, l) = bits.split_at(2);
+        assert!(h.len() == 2 && h.to_int() == 0b10);
+        assert!(l.len() == 3 && l.to_int() == 0b011);
+    }
+
+    #[test]
+    fn bits_slice() {
+        let bits = Bits::from_int(0b110100, Some(6));
+        assert!(bits.slice(1..4) == [true, false, true]);
+        assert!(bits.slice(1..4).len() == 3);
+        assert!(bits.slice(..=1).to_int() == 0b11);
+        assert!(bits.slice(4..).to_int() == 0);
+        assert!(bits.slice(4..).len() == 2);
+    }
+
+    #[test]
+    fn bits_view() {
+        let bits = Bits::from_int(0b110100, Some(6));
+        let view = bits.view(1..4);
+        assert!(view == [true, false, true]);
+        assert!(view.len() == 3);
+        assert!(view[0] && !view[1] && view.get(3).is_none());
+        assert!(view.iter().rev().collect::<Vec<bool>>() == vec![true, false, true]);
+        assert!(view.to_bits() == bits.slice(1..4));
+        assert!(bits.view(..=1) == Bits::from_int(0b11, Some(2)));
+        assert!(bits.view(6..).is_empty());
+
+        let wide = Bits::ones(64).concat(&Bits::zeros(64));
+        assert!(wide.view(60..68).to_bits().to_int() == 0b11110000);
+    }
+
+    #[test]
+    fn bits_range_index() {
+        let mut bits = Bits::from_int(0b110100, Some(6));
+        assert!(bits[1..4] == [true, false, true]);
+        assert!(bits[..=1] == [true, true] && bits[4..].len() == 2 && bits[..].len() == 6);
+        bits.set(2, true);
+        assert!(bits[1..4] == [true, true, true]);
+        assert!(bits.clone()[..] == bits.iter().collect::<Vec<bool>>()[..]);
+    }
+
+    #[test]
+    fn bits_extend_truncate() {
+        let bits = Bits::from_int(0b101, Some(3));
+        let zero_extended = bits.zero_extend(6);
+        assert!(zero_extended.len() == 6 && zero_extended.to_int() == 0b000101);
+        let sign_extended = bits.sign_extend(6);
+        assert!(sign_extended.len() == 6 && sign_extended.to_int() == 0b111101);
+        let truncated = sign_extended.truncate(2);
+        assert!(truncated.len() == 2 && truncated.to_int() == 0b01);
+        assert!(bits.resize(5).len() == 5);
+        assert!(bits.resize(1).to_int() == 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bits_zero_extend_narrower() {
+        Bits::from_int(0b101, Some(3)).zero_extend(2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bits_truncate_wider() {
+        Bits::from_int(0b101, Some(3)).truncate(4);
+    }
+
+    #[test]
+    fn bits_iterators() {
+        let bits = [true, false, true].into_iter().collect::<Bits>();
+        assert!(bits.len() == 3 && bits.to_int() == 5);
+
+        let mut extended = bits.clone();
+        extended.extend([true, true]);
+        assert!(extended.len() == 5 && extended.to_int() == 0b10111);
+
+        assert!((&bits).into_iter().filter(|&b| b).count() == 2);
+        assert!(bits.into_iter().collect::<Vec<bool>>() == vec![true, false, true]);
+    }
+
     #[test]
     fn bits_not_equal() {
         let a = Bits::from_int(253, Some(8));
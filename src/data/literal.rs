@@ -0,0 +1,538 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::data::bits::Bits;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+impl Radix {
+    pub fn base(&self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+        }
+    }
+
+    /// Base character used in sized literals (`8'hA5`).
+    pub fn verilog_char(&self) -> char {
+        match self {
+            Radix::Binary => 'b',
+            Radix::Octal => 'o',
+            Radix::Decimal => 'd',
+            Radix::Hexadecimal => 'h',
+        }
+    }
+
+    fn bits_per_digit(&self) -> Option<usize> {
+        match self {
+            Radix::Binary => Some(1),
+            Radix::Octal => Some(3),
+            Radix::Decimal => None,
+            Radix::Hexadecimal => Some(4),
+        }
+    }
+
+    fn from_verilog_char(c: char) -> Option<Radix> {
+        match c.to_ascii_lowercase() {
+            'b' => Some(Radix::Binary),
+            'o' => Some(Radix::Octal),
+            'd' => Some(Radix::Decimal),
+            'h' => Some(Radix::Hexadecimal),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseBitsError {
+    Empty,
+    InvalidDigit(char),
+    InvalidWidth(String),
+    /// The literal needs more bits than the requested width.
+    DoesNotFit {
+        size: usize,
+    },
+    /// A negative literal was given without a width to encode it in.
+    NegativeWithoutWidth,
+    /// A sized literal was parsed with an explicit width that differs from its own.
+    WidthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for ParseBitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBitsError::Empty => write!(f, "Cannot parse bits from an empty literal"),
+            ParseBitsError::InvalidDigit(c) => write!(f, "Invalid digit '{}' in literal", c),
+            ParseBitsError::InvalidWidth(width) => {
+                write!(f, "Invalid width '{}' in literal", width)
+            }
+            ParseBitsError::DoesNotFit { size } => {
+                write!(f, "The literal cannot be represented with {} bits", size)
+            }
+            ParseBitsError::NegativeWithoutWidth => {
+                write!(f, "A negative literal needs an explicit width")
+            }
+            ParseBitsError::WidthMismatch { expected, actual } => write!(
+                f,
+                "Expected a literal of {} bits, got a sized literal of {} bits",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseBitsError {}
+
+struct Literal<'a> {
+    negative: bool,
+    size: Option<usize>,
+    radix: Radix,
+    digits: &'a str,
+}
+
+impl<'a> Literal<'a> {
+    fn split(s: &'a str) -> Result<Self, ParseBitsError> {
+        let s = s.trim();
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        let (size, radix, digits) = if let Some((width, rest)) = s.split_once('\'') {
+            let size = if width.is_empty() {
+                None
+            } else {
+                match width.parse::<usize>() {
+                    Ok(size) => Some(size),
+                    Err(_) => return Err(ParseBitsError::InvalidWidth(width.to_string())),
+                }
+            };
+            let mut chars = rest.chars();
+            let radix = match chars.next() {
+                Some(c) => Radix::from_verilog_char(c).ok_or(ParseBitsError::InvalidDigit(c))?,
+                None => return Err(ParseBitsError::Empty),
+            };
+            (size, radix, chars.as_str())
+        } else if let Some(rest) = s.strip_prefix("0b").or_else(|| s.strip_prefix("0B")) {
+            (None, Radix::Binary, rest)
+        } else if let Some(rest) = s.strip_prefix("0o").or_else(|| s.strip_prefix("0O")) {
+            (None, Radix::Octal, rest)
+        } else if let Some(rest) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            (None, Radix::Hexadecimal, rest)
+        } else {
+            (None, Radix::Decimal, s)
+        };
+
+        Ok(Literal {
+            negative,
+            size,
+            radix,
+            digits,
+        })
+    }
+
+    /// Magnitude of the literal at its natural width: one group of bits per digit for power of
+    /// two radices, the minimal width for decimal.
+    fn magnitude(&self) -> Result<Bits, ParseBitsError> {
+        let digits = self
+            .digits
+            .chars()
+            .filter(|&c| c != '_')
+            .map(|c| {
+                c.to_digit(self.radix.base())
+                    .ok_or(ParseBitsError::InvalidDigit(c))
+            })
+            .collect::<Result<Vec<u32>, ParseBitsError>>()?;
+        if digits.is_empty() {
+            return Err(ParseBitsError::Empty);
+        }
+
+        match self.radix.bits_per_digit() {
+            Some(bits_per_digit) => Ok(digits
+                .iter()
+                .flat_map(|&digit| {
+                    (0..bits_per_digit)
+                        .rev()
+                        .map(move |n| (digit >> n) & 1 != 0)
+                })
+                .collect()),
+            None => {
+                // Little-endian base 2^32 limbs, multiplied by 10 for each digit.
+                let mut limbs = vec![0_u32];
+                for digit in digits {
+                    let mut carry = digit as u64;
+                    for limb in limbs.iter_mut() {
+                        let value = *limb as u64 * 10 + carry;
+                        *limb = value as u32;
+                        carry = value >> 32;
+                    }
+                    if carry != 0 {
+                        limbs.push(carry as u32);
+                    }
+                }
+                let bits = limbs
+                    .iter()
+                    .rev()
+                    .flat_map(|&limb| (0..32).rev().map(move |n| (limb >> n) & 1 != 0))
+                    .collect::<Bits>();
+                let leading_zeros = bits.iter().take_while(|&b| !b).count();
+                Ok(bits.truncate((bits.len() - leading_zeros).max(1)))
+            }
+        }
+    }
+
+    fn to_bits(&self, size: Option<usize>) -> Result<Bits, ParseBitsError> {
+        let size = match (self.size, size) {
+            (Some(actual), Some(expected)) if actual != expected => {
+                return Err(ParseBitsError::WidthMismatch { expected, actual })
+            }
+            (Some(size), _) | (None, Some(size)) => Some(size),
+            (None, None) => None,
+        };
+        // `0'b`, as a zero-width value is formatted, has no digit to give.
+        if size == Some(0) && self.digits.is_empty() {
+            return Ok(Bits::zeros(0));
+        }
+
+        let magnitude = self.magnitude()?;
+        let significant = magnitude.len() - magnitude.iter().take_while(|&b| !b).count();
+
+        if !self.negative {
+            return match size {
+                Some(size) if significant > size => Err(ParseBitsError::DoesNotFit { size }),
+                Some(size) => Ok(magnitude.resize(size)),
+                None => Ok(magnitude),
+            };
+        }
+
+        let size = size.ok_or(ParseBitsError::NegativeWithoutWidth)?;
+        if significant == 0 {
+            // -0 is 0, which fits even in 0 bits where there is no `size - 1`.
+            return Ok(Bits::zeros(size));
+        }
+        // -2^(size - 1) is the most negative value representable on `size` bits.
+        let is_min = significant == size && magnitude.iter().rev().take(size - 1).all(|b| !b);
+        if significant >= size && !is_min {
+            return Err(ParseBitsError::DoesNotFit { size });
+        }
+        Ok(increment(!magnitude.resize(size)))
+    }
+}
+
+fn increment(bits: Bits) -> Bits {
+    let mut carry = true;
+    let mut output = bits
+        .iter()
+        .rev()
+        .map(|bit| {
+            let sum = bit ^ carry;
+            carry &= bit;
+            sum
+        })
+        .collect::<Vec<bool>>();
+    output.reverse();
+    Bits::from_vector_b(output, None)
+}
+
+fn group_digits(digits: String, group: Option<usize>) -> String {
+    match group {
+        Some(group) if group > 0 => {
+            let chars = digits.chars().collect::<Vec<char>>();
+            let mut output = String::with_capacity(chars.len() + chars.len() / group);
+            for (i, c) in chars.iter().enumerate() {
+                if i > 0 && (chars.len() - i) % group == 0 {
+                    output.push('_');
+                }
+                output.push(*c);
+            }
+            output
+        }
+        _ => digits,
+    }
+}
+
+impl Bits {
+    /// Parses a literal and fits it into exactly `size` bits.
+    ///
+    /// Accepts the same forms as [`FromStr`]; unsized literals are zero-extended, negative ones
+    /// are encoded in two's complement. Sized literals must already be `size` bits wide.
+    pub fn parse_sized(s: &str, size: usize) -> Result<Bits, ParseBitsError> {
+        Literal::split(s)?.to_bits(Some(size))
+    }
+
+    /// Digits of the value in `radix`, zero-padded to the width for power of two radices, with
+    /// an `_` every `group` digits counted from the right.
+    pub fn to_string_radix(&self, radix: Radix, group: Option<usize>) -> String {
+        let digits = match radix.bits_per_digit() {
+            Some(bits_per_digit) => {
+                let padded = self.zero_extend(self.len().div_ceil(bits_per_digit) * bits_per_digit);
+                padded
+                    .data()
+                    .chunks(bits_per_digit)
+                    .map(|chunk| {
+                        let digit = chunk.iter().fold(0, |acc, &b| (acc << 1) | b as u32);
+                        std::char::from_digit(digit, radix.base()).unwrap()
+                    })
+                    .collect::<String>()
+            }
+            None => {
+                // Repeated division by 10 over the MSB-first bits.
+                let mut remaining = self.data();
+                let mut digits = Vec::new();
+                loop {
+                    let mut remainder = 0_u32;
+                    for bit in remaining.iter_mut() {
+                        remainder = (remainder << 1) | *bit as u32;
+                        *bit = remainder >= 10;
+                        remainder %= 10;
+                    }
+                    digits.push(std::char::from_digit(remainder, 10).unwrap());
+                    if remaining.iter().all(|&b| !b) {
+                        break;
+                    }
+                }
+                digits.iter().rev().collect::<String>()
+            }
+        };
+        group_digits(digits, group)
+    }
+
+    /// Verilog sized literal such as `8'hA5`.
+    pub fn to_literal(&self, radix: Radix) -> String {
+        let digits = self.to_string_radix(radix, None);
+        let digits = if radix == Radix::Hexadecimal {
+            digits.to_uppercase()
+        } else {
+            digits
+        };
+        format!("{}'{}{}", self.len(), radix.verilog_char(), digits)
+    }
+}
+
+impl FromStr for Bits {
+    type Err = ParseBitsError;
+
+    /// Parses `0b1011`, `0o17`, `0x1F`, decimal `13` and Verilog sized literals like `8'hA5`.
+    ///
+    /// Prefixed literals take one group of bits per digit, decimal ones the minimal width.
+    /// `_` separators are ignored. Negative values need a sized literal (`-4'd3`), see
+    /// [`Bits::parse_sized`] to give the width separately.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Literal::split(s)?.to_bits(None)
+    }
+}
+
+impl fmt::Display for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_literal(Radix::Binary))
+    }
+}
+
+impl fmt::Debug for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bits({})", self.to_literal(Radix::Binary))
+    }
+}
+
+impl fmt::Binary for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0b", &self.to_string_radix(Radix::Binary, None))
+    }
+}
+
+impl fmt::Octal for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0o", &self.to_string_radix(Radix::Octal, None))
+    }
+}
+
+impl fmt::LowerHex for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_string_radix(Radix::Hexadecimal, None))
+    }
+}
+
+impl fmt::UpperHex for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self
+            .to_string_radix(Radix::Hexadecimal, None)
+            .to_uppercase();
+        f.pad_integral(true, "0x", &digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_prefixed() {
+        let bits = "0b1011".parse::<Bits>().unwrap();
+        assert!(bits.len() == 4 && bits.to_int() == 0b1011);
+
+        let bits = "0x1F".parse::<Bits>().unwrap();
+        assert!(bits.len() == 8 && bits.to_int() == 0x1F);
+
+        let bits = "0o17".parse::<Bits>().unwrap();
+        assert!(bits.len() == 6 && bits.to_int() == 0o17);
+
+        let bits = "0b1010_0101".parse::<Bits>().unwrap();
+        assert!(bits.len() == 8 && bits.to_int() == 0xA5);
+    }
+
+    #[test]
+    fn parse_decimal() {
+        let bits = "13".parse::<Bits>().unwrap();
+        assert!(bits.len() == 4 && bits.to_int() == 13);
+
+        let bits = "0".parse::<Bits>().unwrap();
+        assert!(bits.len() == 1 && bits.to_int() == 0);
+
+        let bits = "18446744073709551616".parse::<Bits>().unwrap();
+        assert!(bits.len() == 65);
+        assert!(bits.msb() && bits.iter().filter(|&b| b).count() == 1);
+    }
+
+    #[test]
+    fn parse_verilog_sized() {
+        let bits = "8'hA5".parse::<Bits>().unwrap();
+        assert!(bits.len() == 8 && bits.to_int() == 0xA5);
+
+        let bits = "12'hA5".parse::<Bits>().unwrap();
+        assert!(bits.len() == 12 && bits.to_int() == 0xA5);
+
+        let bits = "3'd5".parse::<Bits>().unwrap();
+        assert!(bits.len() == 3 && bits.to_int() == 5);
+
+        let bits = "-4'd3".parse::<Bits>().unwrap();
+        assert!(bits.len() == 4 && bits.to_int() == 0b1101);
+
+        let bits = "'b101".parse::<Bits>().unwrap();
+        assert!(bits.len() == 3 && bits.to_int() == 5);
+    }
+
+    #[test]
+    fn parse_sized() {
+        let bits = Bits::parse_sized("-3", 4).unwrap();
+        assert!(bits.len() == 4 && bits.to_int() == 0b1101);
+
+        let bits = Bits::parse_sized("-8", 4).unwrap();
+        assert!(bits.to_int() == 0b1000);
+
+        let bits = Bits::parse_sized("0x1F", 16).unwrap();
+        assert!(bits.len() == 16 && bits.to_int() == 0x1F);
+
+        let bits = Bits::parse_sized("0x0F", 4).unwrap();
+        assert!(bits.len() == 4 && bits.to_int() == 0xF);
+
+        assert!(Bits::parse_sized("-0", 4).unwrap() == Bits::zeros(4));
+        assert!(Bits::parse_sized("-0", 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<Bits>(), Err(ParseBitsError::Empty));
+        assert_eq!("0x".parse::<Bits>(), Err(ParseBitsError::Empty));
+        assert_eq!(
+            "0b102".parse::<Bits>(),
+            Err(ParseBitsError::InvalidDigit('2'))
+        );
+        assert_eq!(
+            "8'zA5".parse::<Bits>(),
+            Err(ParseBitsError::InvalidDigit('z'))
+        );
+        assert_eq!(
+            "x'hA5".parse::<Bits>(),
+            Err(ParseBitsError::InvalidWidth("x".to_string()))
+        );
+        assert_eq!(
+            "-3".parse::<Bits>(),
+            Err(ParseBitsError::NegativeWithoutWidth)
+        );
+        assert_eq!(
+            "4'hA5".parse::<Bits>(),
+            Err(ParseBitsError::DoesNotFit { size: 4 })
+        );
+        assert_eq!(
+            Bits::parse_sized("-9", 4),
+            Err(ParseBitsError::DoesNotFit { size: 4 })
+        );
+        assert_eq!(
+            Bits::parse_sized("8'hA5", 4),
+            Err(ParseBitsError::WidthMismatch {
+                expected: 4,
+                actual: 8
+            })
+        );
+    }
+
+    #[test]
+    fn format_radix() {
+        let bits = Bits::from_int(0xA5, Some(10));
+        assert_eq!(bits.to_string_radix(Radix::Binary, None), "0010100101");
+        assert_eq!(bits.to_string_radix(Radix::Binary, Some(4)), "00_1010_0101");
+        assert_eq!(bits.to_string_radix(Radix::Octal, None), "0245");
+        assert_eq!(bits.to_string_radix(Radix::Hexadecimal, None), "0a5");
+        assert_eq!(bits.to_string_radix(Radix::Decimal, None), "165");
+        assert_eq!(
+            Bits::from_int(1234567, None).to_string_radix(Radix::Decimal, Some(3)),
+            "1_234_567"
+        );
+    }
+
+    #[test]
+    fn format_traits() {
+        let bits = Bits::from_int(0xA5, Some(8));
+        assert_eq!(format!("{}", bits), "8'b10100101");
+        assert_eq!(format!("{:?}", bits), "Bits(8'b10100101)");
+        assert_eq!(format!("{:b}", bits), "10100101");
+        assert_eq!(format!("{:#x}", bits), "0xa5");
+        assert_eq!(format!("{:X}", bits), "A5");
+        assert_eq!(format!("{:#o}", bits), "0o245");
+        assert_eq!(format!("{:>6x}", bits), "    a5");
+        assert_eq!(bits.to_literal(Radix::Hexadecimal), "8'hA5");
+        assert_eq!(bits.to_literal(Radix::Decimal), "8'd165");
+    }
+
+    #[test]
+    fn format_parse_roundtrip() {
+        for value in [0, 1, 5, 0xA5, 0x1234] {
+            let bits = Bits::from_int(value, Some(16));
+            for radix in [
+                Radix::Binary,
+                Radix::Octal,
+                Radix::Decimal,
+                Radix::Hexadecimal,
+            ] {
+                let parsed = bits.to_literal(radix).parse::<Bits>().unwrap();
+                assert!(parsed.len() == 16 && parsed.to_int() == value);
+            }
+        }
+        // A zero-width value is written without digits.
+        let empty = Bits::zeros(0);
+        assert_eq!(empty.to_string(), "0'b");
+        assert!(empty.to_string().parse::<Bits>().unwrap().is_empty());
+        assert!(empty
+            .to_literal(Radix::Hexadecimal)
+            .parse::<Bits>()
+            .unwrap()
+            .is_empty());
+        assert!("0'b0".parse::<Bits>().unwrap().is_empty());
+        assert_eq!(
+            "0'b1".parse::<Bits>(),
+            Err(ParseBitsError::DoesNotFit { size: 0 })
+        );
+        assert_eq!("4'b".parse::<Bits>(), Err(ParseBitsError::Empty));
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+use std::str::FromStr;
+
+use crate::data::bits::Bits;
+use crate::data::literal::ParseBitsError;
+
+/// Four-valued signal: driven low, driven high, high impedance (`Z`) or unknown (`X`).
+///
+/// Gate operators follow Verilog: a `Z` input reads as `X`, and a controlling value wins over
+/// an unknown one (`0 & X == 0`, `1 | X == 1`). Wired connections use [`Logic::resolve`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Logic {
+    #[default]
+    Zero,
+    One,
+    Z,
+    X,
+}
+
+impl Logic {
+    pub fn to_bool(self) -> Option<bool> {
+        match self {
+            Logic::Zero => Some(false),
+            Logic::One => Some(true),
+            Logic::Z | Logic::X => None,
+        }
+    }
+
+    pub fn is_known(self) -> bool {
+        self.to_bool().is_some()
+    }
+
+    /// Value of a node driven by both `self` and `other`: `Z` yields to the other driver and
+    /// two different driven values conflict into `X`.
+    pub fn resolve(self, other: Logic) -> Logic {
+        match (self, other) {
+            (Logic::Z, value) | (value, Logic::Z) => value,
+            (a, b) if a == b => a,
+            _ => Logic::X,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Logic::Zero => '0',
+            Logic::One => '1',
+            Logic::Z => 'z',
+            Logic::X => 'x',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Logic> {
+        match c.to_ascii_lowercase() {
+            '0' => Some(Logic::Zero),
+            '1' => Some(Logic::One),
+            'z' | '?' => Some(Logic::Z),
+            'x' => Some(Logic::X),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Logic {
+    fn from(value: bool) -> Self {
+        if value {
+            Logic::One
+        } else {
+            Logic::Zero
+        }
+    }
+}
+
+impl fmt::Display for Logic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl Not for Logic {
+    type Output = Logic;
+
+    fn not(self) -> Logic {
+        match self.to_bool() {
+            Some(value) => Logic::from(!value),
+            None => Logic::X,
+        }
+    }
+}
+
+impl BitAnd for Logic {
+    type Output = Logic;
+
+    fn bitand(self, rhs: Logic) -> Logic {
+        match (self.to_bool(), rhs.to_bool()) {
+            (Some(false), _) | (_, Some(false)) => Logic::Zero,
+            (Some(true), Some(true)) => Logic::One,
+            _ => Logic::X,
+        }
+    }
+}
+
+impl BitOr for Logic {
+    type Output = Logic;
+
+    fn bitor(self, rhs: Logic) -> Logic {
+        match (self.to_bool(), rhs.to_bool()) {
+            (Some(true), _) | (_, Some(true)) => Logic::One,
+            (Some(false), Some(false)) => Logic::Zero,
+            _ => Logic::X,
+        }
+    }
+}
+
+impl BitXor for Logic {
+    type Output = Logic;
+
+    fn bitxor(self, rhs: Logic) -> Logic {
+        match (self.to_bool(), rhs.to_bool()) {
+            (Some(a), Some(b)) => Logic::from(a ^ b),
+            _ => Logic::X,
+        }
+    }
+}
+
+/// Vector of [`Logic`] values, the four-valued counterpart of [`Bits`]. Indexed MSB first.
+///
+/// Binary operators follow the same width rules as [`Bits`]: the narrower operand is
+/// zero-extended.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct LBits {
+    data: Vec<Logic>,
+}
+
+impl LBits {
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn filled(value: Logic, size: usize) -> Self {
+        LBits {
+            data: vec![value; size],
+        }
+    }
+
+    pub fn from_vector(data: Vec<Logic>) -> Self {
+        LBits { data }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Logic> {
+        self.data.iter()
+    }
+
+    pub fn set(&mut self, index: usize, value: Logic) {
+        self.data[index] = value;
+    }
+
+    /// Whether every bit is driven to `0` or `1`.
+    pub fn is_known(&self) -> bool {
+        self.data.iter().all(|value| value.is_known())
+    }
+
+    /// The two-valued bits, or `None` if any bit is `Z` or `X`.
+    pub fn to_bits(&self) -> Option<Bits> {
+        self.data.iter().map(|value| value.to_bool()).collect()
+    }
+
+    /// Value of a bus driven by both `self` and `other`, bit by bit.
+    pub fn resolve(&self, other: &LBits) -> LBits {
+        self.zip_with(other, Logic::resolve)
+    }
+
+    /// Zero-extends to `size` bits.
+    pub fn zero_extend(&self, size: usize) -> LBits {
+        if size < self.len() {
+            panic!("Cannot extend {} bits to {} bits", self.len(), size);
+        }
+        let mut data = vec![Logic::Zero; size - self.len()];
+        data.extend_from_slice(&self.data);
+        LBits { data }
+    }
+
+    fn zip_with(&self, other: &LBits, f: impl Fn(Logic, Logic) -> Logic) -> LBits {
+        let size = self.len().max(other.len());
+        let a = self.zero_extend(size);
+        let b = other.zero_extend(size);
+        a.iter().zip(b.iter()).map(|(&x, &y)| f(x, y)).collect()
+    }
+}
+
+impl From<&Bits> for LBits {
+    fn from(bits: &Bits) -> Self {
+        bits.iter().map(Logic::from).collect()
+    }
+}
+
+impl From<Bits> for LBits {
+    fn from(bits: Bits) -> Self {
+        LBits::from(&bits)
+    }
+}
+
+impl FromIterator<Logic> for LBits {
+    fn from_iter<I: IntoIterator<Item = Logic>>(iter: I) -> Self {
+        LBits {
+            data: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a LBits {
+    type Item = &'a Logic;
+    type IntoIter = std::slice::Iter<'a, Logic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl std::ops::Index<usize> for LBits {
+    type Output = Logic;
+
+    fn index(&self, index: usize) -> &Logic {
+        &self.data[index]
+    }
+}
+
+impl Not for &LBits {
+    type Output = LBits;
+
+    fn not(self) -> LBits {
+        self.iter().map(|&value| !value).collect()
+    }
+}
+
+impl BitAnd for &LBits {
+    type Output = LBits;
+
+    fn bitand(self, rhs: &LBits) -> LBits {
+        self.zip_with(rhs, Logic::bitand)
+    }
+}
+
+impl BitOr for &LBits {
+    type Output = LBits;
+
+    fn bitor(self, rhs: &LBits) -> LBits {
+        self.zip_with(rhs, Logic::bitor)
+    }
+}
+
+impl BitXor for &LBits {
+    type Output = LBits;
+
+    fn bitxor(self, rhs: &LBits) -> LBits {
+        self.zip_with(rhs, Logic::bitxor)
+    }
+}
+
+impl FromStr for LBits {
+    type Err = ParseBitsError;
+
+    /// Parses binary digits with `z` and `x`, optionally prefixed with `0b` or a sized `4'b`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (size, digits) = match s.split_once('\'') {
+            Some((width, rest)) => {
+                let size = width
+                    .parse::<usize>()
+                    .map_err(|_| ParseBitsError::InvalidWidth(width.to_string()))?;
+                let digits = rest
+                    .strip_prefix('b')
+                    .or_else(|| rest.strip_prefix('B'))
+                    .ok_or_else(|| {
+                        ParseBitsError::InvalidDigit(rest.chars().next().unwrap_or('\''))
+                    })?;
+                (Some(size), digits)
+            }
+            None => (None, s.strip_prefix("0b").unwrap_or(s)),
+        };
+
+        let data = digits
+            .chars()
+            .filter(|&c| c != '_')
+            .map(|c| Logic::from_char(c).ok_or(ParseBitsError::InvalidDigit(c)))
+            .collect::<Result<Vec<Logic>, ParseBitsError>>()?;
+        if data.is_empty() {
+            return Err(ParseBitsError::Empty);
+        }
+
+        let value = LBits { data };
+        match size {
+            Some(size) if size < value.len() => Err(ParseBitsError::DoesNotFit { size }),
+            Some(size) => Ok(value.zero_extend(size)),
+            None => Ok(value),
+        }
+    }
+}
+
+impl fmt::Display for LBits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}'b", self.len())?;
+        for value in self.iter() {
+            write!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for LBits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LBits({})", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Logic; 4] = [Logic::Zero, Logic::One, Logic::Z, Logic::X];
+
+    #[test]
+    fn logic_not() {
+        assert_eq!(!Logic::Zero, Logic::One);
+        assert_eq!(!Logic::One, Logic::Zero);
+        assert_eq!(!Logic::Z, Logic::X);
+        assert_eq!(!Logic::X, Logic::X);
+    }
+
+    #[test]
+    fn logic_and_or_controlling_values() {
+        for value in ALL {
+            assert_eq!(Logic::Zero & value, Logic::Zero);
+            assert_eq!(value & Logic::Zero, Logic::Zero);
+            assert_eq!(Logic::One | value, Logic::One);
+            assert_eq!(value | Logic::One, Logic::One);
+        }
+        assert_eq!(Logic::One & Logic::Z, Logic::X);
+        assert_eq!(Logic::Zero | Logic::X, Logic::X);
+        assert_eq!(Logic::One & Logic::One, Logic::One);
+    }
+
+    #[test]
+    fn logic_xor() {
+        assert_eq!(Logic::One ^ Logic::Zero, Logic::One);
+        assert_eq!(Logic::One ^ Logic::One, Logic::Zero);
+        for value in ALL {
+            assert_eq!(value ^ Logic::X, Logic::X);
+            assert_eq!(Logic::Z ^ value, Logic::X);
+        }
+    }
+
+    #[test]
+    fn logic_resolve() {
+        for value in ALL {
+            assert_eq!(Logic::Z.resolve(value), value);
+            assert_eq!(value.resolve(Logic::Z), value);
+            assert_eq!(Logic::X.resolve(value), Logic::X);
+        }
+        assert_eq!(Logic::Zero.resolve(Logic::One), Logic::X);
+        assert_eq!(Logic::One.resolve(Logic::One), Logic::One);
+    }
+
+    #[test]
+    fn lbits_conversions() {
+        let bits = Bits::from_int(0b1010, Some(4));
+        let lbits = LBits::from(&bits);
+        assert!(lbits.is_known());
+        assert!(lbits.to_bits().unwrap() == bits);
+
+        let lbits = "4'b10zx".parse::<LBits>().unwrap();
+        assert!(!lbits.is_known());
+        assert!(lbits.to_bits().is_none());
+        assert_eq!(lbits[2], Logic::Z);
+        assert_eq!(format!("{}", lbits), "4'b10zx");
+        assert_eq!("6'b1x".parse::<LBits>().unwrap().to_string(), "6'b00001x");
+    }
+
+    #[test]
+    fn lbits_bus_resolution() {
+        let driver_a = "4'b10zz".parse::<LBits>().unwrap();
+        let driver_b = "4'bzz01".parse::<LBits>().unwrap();
+        assert_eq!(driver_a.resolve(&driver_b).to_string(), "4'b1001");
+
+        let conflicting = "4'b0zzz".parse::<LBits>().unwrap();
+        assert_eq!(driver_a.resolve(&conflicting).to_string(), "4'bx0zz");
+    }
+
+    #[test]
+    fn lbits_ops() {
+        let a = "4'b01xz".parse::<LBits>().unwrap();
+        let b = "4'b0011".parse::<LBits>().unwrap();
+        assert_eq!((&a & &b).to_string(), "4'b00xx");
+        assert_eq!((&a | &b).to_string(), "4'b0111");
+        assert_eq!((&a ^ &b).to_string(), "4'b01xx");
+        assert_eq!((!&a).to_string(), "4'b10xx");
+    }
+}
@@ -1 +1,4 @@
 pub mod bits;
+pub mod literal;
+pub mod logic;
+pub mod signed;
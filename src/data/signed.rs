@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+
+use crate::data::bits::{Bits, ConversionError};
+
+/// How the bits of a value are read: plain binary or two's complement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signedness {
+    Unsigned,
+    Signed,
+}
+
+impl Bits {
+    /// Encodes `value` in two's complement on `size` bits, or on the minimal width (sign bit
+    /// included) if `size` is `None`.
+    pub fn try_from_i128(value: i128, size: Option<usize>) -> Result<Self, ConversionError> {
+        let required = signed_len_i128(value);
+        let size = size.unwrap_or(required);
+        if required > size {
+            return Err(ConversionError::ValueTooLarge { required, size });
+        }
+        let bits = Bits::try_from_u128(value as u128, Some(i128::BITS as usize))?;
+        Ok(bits.resize_signed(size))
+    }
+
+    pub fn try_from_i64(value: i64, size: Option<usize>) -> Result<Self, ConversionError> {
+        Bits::try_from_i128(value.into(), size)
+    }
+
+    pub fn from_i64(value: i64, size: Option<usize>) -> Self {
+        Bits::try_from_i64(value, size).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Reads the bits as a two's complement value.
+    pub fn try_to_i128(&self) -> Result<i128, ConversionError> {
+        let significant = self.signed_significant_len();
+        if significant > i128::BITS as usize {
+            return Err(ConversionError::TargetTooNarrow {
+                significant,
+                target: i128::BITS as usize,
+            });
+        }
+        let bits = self.resize_signed(i128::BITS as usize);
+        Ok(u128::try_from(&bits)? as i128)
+    }
+
+    /// Reads the bits as a two's complement value.
+    pub fn try_to_i64(&self) -> Result<i64, ConversionError> {
+        let significant = self.signed_significant_len();
+        if significant > i64::BITS as usize {
+            return Err(ConversionError::TargetTooNarrow {
+                significant,
+                target: i64::BITS as usize,
+            });
+        }
+        Ok(self.try_to_i128()? as i64)
+    }
+
+    pub fn to_i64(&self) -> i64 {
+        self.try_to_i64()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Sign bit of the two's complement reading, i.e. the MSB.
+    pub fn sign_bit(&self) -> bool {
+        self.msb()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.sign_bit()
+    }
+
+    /// Number of bits needed to hold the two's complement value, sign bit included.
+    pub fn signed_significant_len(&self) -> usize {
+        if self.is_negative() {
+            self.len() - self.iter().take_while(|&b| b).count() + 1
+        } else {
+            self.significant_len() + 1
+        }
+    }
+
+    /// Sign-extends or truncates to exactly `size` bits.
+    pub fn resize_signed(&self, size: usize) -> Bits {
+        if size >= self.len() {
+            self.sign_extend(size)
+        } else {
+            self.truncate(size)
+        }
+    }
+
+    /// Whether the value survives being resized to `size` bits.
+    pub fn fits(&self, size: usize, signedness: Signedness) -> bool {
+        match signedness {
+            Signedness::Unsigned => self.significant_len() <= size,
+            Signedness::Signed => self.signed_significant_len() <= size,
+        }
+    }
+
+    /// Compares the values, reading both operands with the same `signedness`.
+    pub fn cmp_as(&self, other: &Bits, signedness: Signedness) -> Ordering {
+        let (a, b) = match signedness {
+            Signedness::Unsigned => {
+                let size = self.len().max(other.len());
+                (self.zero_extend(size), other.zero_extend(size))
+            }
+            Signedness::Signed => {
+                if self.is_negative() != other.is_negative() {
+                    return if self.is_negative() {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    };
+                }
+                // Same sign: two's complement orders like unsigned once widths match.
+                let size = self.len().max(other.len());
+                (self.sign_extend(size), other.sign_extend(size))
+            }
+        };
+        a.iter().cmp(b.iter())
+    }
+
+    pub fn eq_as(&self, other: &Bits, signedness: Signedness) -> bool {
+        self.cmp_as(other, signedness) == Ordering::Equal
+    }
+
+    /// Whether `a + b` overflowed in two's complement, given the `sum` of an adder of the same
+    /// width. Unsigned overflow is the adder's carry out.
+    pub fn add_overflowed(a: &Bits, b: &Bits, sum: &Bits) -> bool {
+        a.sign_bit() == b.sign_bit() && sum.sign_bit() != a.sign_bit()
+    }
+
+    /// Whether `a - b` overflowed in two's complement, given the `difference` of a subtractor of
+    /// the same width. Unsigned overflow is the subtractor's borrow out.
+    pub fn sub_overflowed(a: &Bits, b: &Bits, difference: &Bits) -> bool {
+        a.sign_bit() != b.sign_bit() && difference.sign_bit() != a.sign_bit()
+    }
+}
+
+fn signed_len_i128(value: i128) -> usize {
+    let magnitude_bits = if value < 0 {
+        i128::BITS - value.leading_ones()
+    } else {
+        i128::BITS - value.leading_zeros()
+    };
+    magnitude_bits as usize + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_i64() {
+        let bits = Bits::from_i64(-3, Some(4));
+        assert!(bits.len() == 4 && bits.to_int() == 0b1101);
+
+        let bits = Bits::from_i64(-3, None);
+        assert!(bits.len() == 3 && bits.to_int() == 0b101);
+
+        let bits = Bits::from_i64(3, None);
+        assert!(bits.len() == 3 && bits.to_int() == 0b011);
+
+        let bits = Bits::from_i64(0, None);
+        assert!(bits.len() == 1);
+
+        let bits = Bits::from_i64(-1, Some(100));
+        assert!(bits == Bits::ones(100));
+    }
+
+    #[test]
+    fn from_i64_too_large() {
+        assert_eq!(
+            Bits::try_from_i64(8, Some(4)),
+            Err(ConversionError::ValueTooLarge {
+                required: 5,
+                size: 4
+            })
+        );
+        assert_eq!(
+            Bits::try_from_i64(-9, Some(4)),
+            Err(ConversionError::ValueTooLarge {
+                required: 5,
+                size: 4
+            })
+        );
+        assert!(Bits::try_from_i64(-8, Some(4)).is_ok());
+    }
+
+    #[test]
+    fn to_i64_roundtrip() {
+        for size in [4, 8, 64, 100] {
+            for value in [-8, -5, -1, 0, 1, 7] {
+                assert_eq!(Bits::from_i64(value, Some(size)).to_i64(), value);
+            }
+        }
+        assert_eq!(Bits::from_i64(i64::MIN, None).to_i64(), i64::MIN);
+        assert_eq!(Bits::from_i64(i64::MAX, None).to_i64(), i64::MAX);
+    }
+
+    #[test]
+    fn to_i64_too_narrow() {
+        let bits = Bits::from(u64::MAX).zero_extend(65);
+        assert_eq!(
+            bits.try_to_i64(),
+            Err(ConversionError::TargetTooNarrow {
+                significant: 65,
+                target: 64
+            })
+        );
+        assert_eq!(bits.try_to_i128(), Ok(u64::MAX as i128));
+    }
+
+    #[test]
+    fn sign_helpers() {
+        let bits = Bits::from_i64(-2, Some(8));
+        assert!(bits.sign_bit() && bits.is_negative());
+        assert!(bits.signed_significant_len() == 2);
+        assert!(bits.fits(2, Signedness::Signed));
+        assert!(!bits.fits(7, Signedness::Unsigned));
+        assert!(bits.resize_signed(16).to_i64() == -2);
+        assert!(bits.resize_signed(3).to_i64() == -2);
+    }
+
+    #[test]
+    fn compare_signedness() {
+        let minus_one = Bits::from_i64(-1, Some(4));
+        let one = Bits::from_i64(1, Some(8));
+        assert!(minus_one.cmp_as(&one, Signedness::Signed) == Ordering::Less);
+        assert!(minus_one.cmp_as(&one, Signedness::Unsigned) == Ordering::Greater);
+
+        let minus_one_wide = Bits::from_i64(-1, Some(8));
+        assert!(minus_one.eq_as(&minus_one_wide, Signedness::Signed));
+        assert!(!minus_one.eq_as(&minus_one_wide, Signedness::Unsigned));
+
+        for a in -8..8 {
+            for b in -8..8 {
+                let bits_a = Bits::from_i64(a, Some(4));
+                let bits_b = Bits::from_i64(b, Some(6));
+                assert!(bits_a.cmp_as(&bits_b, Signedness::Signed) == a.cmp(&b));
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::data::logic::Logic;
+use crate::electronic::circuits::component::Component;
+use crate::electronic::stats::{indented_name, rows};
+
+/// Counts the changes of a signal.
+#[derive(Clone, Debug, Default)]
+pub struct ToggleCounter {
+    last: Option<Logic>,
+    count: u64,
+}
+
+impl ToggleCounter {
+    pub fn new() -> Self {
+        ToggleCounter::default()
+    }
+
+    /// Records the current value of the signal. The first value is not a toggle.
+    pub fn record(&mut self, value: Logic) {
+        if self.last.is_some_and(|last| last != value) {
+            self.count += 1;
+        }
+        self.last = Some(value);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The last value recorded.
+    pub fn last(&self) -> Option<Logic> {
+        self.last
+    }
+
+    /// Clears the count, the last value is kept so the next change is still counted.
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+}
+
+/// Clears the toggle counts of `component` and all its descendants.
+pub fn reset_toggles(component: &mut dyn Component) {
+    component.reset_own_toggles();
+    for (_, sub_component) in component.sub_components_mut() {
+        reset_toggles(sub_component);
+    }
+}
+
+/// Electrical parameters for the dynamic energy `½·C·V²` dissipated by each toggle, and the
+/// power `E·f` when one evaluation happens per clock cycle.
+#[derive(Clone, Debug)]
+pub struct PowerModel {
+    /// Supply voltage, in volts.
+    pub vdd: f64,
+    /// Clock frequency, in hertz.
+    pub frequency: f64,
+    /// Switched capacitance per toggle, in femtofarads, by component type name.
+    pub capacitances: HashMap<&'static str, f64>,
+}
+
+impl PowerModel {
+    pub fn new(vdd: f64, frequency: f64) -> Self {
+        PowerModel {
+            vdd,
+            frequency,
+            capacitances: HashMap::new(),
+        }
+    }
+
+    pub fn with_capacitance(mut self, type_name: &'static str, femtofarads: f64) -> Self {
+        self.capacitances.insert(type_name, femtofarads);
+        self
+    }
+
+    pub fn capacitance(&self, type_name: &str) -> f64 {
+        self.capacitances.get(type_name).copied().unwrap_or(0.0)
+    }
+
+    /// Energy of `toggles` changes of a `type_name` node, in femtojoules.
+    pub fn energy(&self, type_name: &str, toggles: u64) -> f64 {
+        0.5 * self.capacitance(type_name) * self.vdd * self.vdd * toggles as f64
+    }
+}
+
+impl Default for PowerModel {
+    /// 1 V at 1 GHz, with gate capacitances of a few femtofarads and a larger load on gate
+    /// outputs.
+    fn default() -> Self {
+        PowerModel::new(1.0, 1e9)
+            .with_capacitance("NMOSTransistor", 0.5)
+            .with_capacitance("PMOSTransistor", 1.0)
+            .with_capacitance("Not", 2.0)
+            .with_capacitance("Nand", 3.0)
+            .with_capacitance("Nor", 3.0)
+            .with_capacitance("Xor", 4.0)
+            .with_capacitance("Xnor", 4.0)
+    }
+}
+
+/// Switching activity of one component, including all its descendants.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivityRow {
+    pub path: String,
+    pub type_name: &'static str,
+    pub depth: usize,
+    pub transistor_toggles: u64,
+    pub gate_toggles: u64,
+    /// In femtojoules.
+    pub energy: f64,
+}
+
+/// Hierarchical switching activity, one row per component, parents before their children.
+#[derive(Clone, Debug)]
+pub struct ActivityReport {
+    pub rows: Vec<ActivityRow>,
+    /// Number of evaluations the counts were accumulated over.
+    pub cycles: u64,
+    frequency: f64,
+}
+
+impl ActivityReport {
+    /// The whole component.
+    pub fn total(&self) -> &ActivityRow {
+        &self.rows[0]
+    }
+
+    /// Average dynamic power over the `cycles`, in microwatts.
+    pub fn power(&self) -> f64 {
+        if self.cycles == 0 {
+            return 0.0;
+        }
+        // fJ per cycle times cycles per second gives fW.
+        self.total().energy / self.cycles as f64 * self.frequency * 1e-9
+    }
+
+    pub fn find(&self, path: &str) -> Option<&ActivityRow> {
+        self.rows.iter().find(|row| row.path == path)
+    }
+}
+
+/// Collects the toggles counted in `component` since the last [`reset_toggles`], rows down
+/// to `max_depth` levels below it.
+pub fn report(
+    component: &dyn Component,
+    model: &PowerModel,
+    cycles: u64,
+    max_depth: usize,
+) -> ActivityReport {
+    let rows = rows(
+        component,
+        max_depth,
+        &mut |path, depth, component| {
+            let own_toggles = component.own_toggles();
+            let is_transistor = component.sub_components().is_empty();
+            ActivityRow {
+                path: path.to_string(),
+                type_name: component.type_name(),
+                depth,
+                transistor_toggles: if is_transistor { own_toggles } else { 0 },
+                gate_toggles: if is_transistor { 0 } else { own_toggles },
+                energy: model.energy(component.type_name(), own_toggles),
+            }
+        },
+        &|_, row, child| {
+            row.transistor_toggles += child.transistor_toggles;
+            row.gate_toggles += child.gate_toggles;
+            row.energy += child.energy;
+        },
+    );
+    ActivityReport {
+        rows,
+        cycles,
+        frequency: model.frequency,
+    }
+}
+
+impl fmt::Display for ActivityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<40} {:<16} {:>12} {:>12} {:>14}",
+            "path", "type", "transistor", "gate", "energy (fJ)"
+        )?;
+        for row in &self.rows {
+            writeln!(
+                f,
+                "{:<40} {:<16} {:>12} {:>12} {:>14.3}",
+                indented_name(&row.path, row.depth),
+                row.type_name,
+                row.transistor_toggles,
+                row.gate_toggles,
+                row.energy
+            )?;
+        }
+        write!(f, "{} cycles, {:.3} uW", self.cycles, self.power())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::bits::Bits;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::bitwise::mult::BitwiseMult;
+    use crate::electronic::circuits::logic_gates::and::And;
+    use crate::electronic::circuits::logic_gates::not::Not;
+
+    #[test]
+    fn toggle_counter() {
+        let mut counter = ToggleCounter::new();
+        counter.record(Logic::Zero);
+        counter.record(Logic::Zero);
+        counter.record(Logic::One);
+        counter.record(Logic::X);
+        assert_eq!(counter.count(), 2);
+        counter.reset();
+        counter.record(Logic::Zero);
+        assert_eq!(counter.count(), 1);
+    }
+
+    #[test]
+    fn activity_not() {
+        let mut not = Not::new();
+        for i in 0..5 {
+            not.evaluate(i % 2 == 1);
+        }
+        let report = report(&not, &PowerModel::default(), 5, usize::MAX);
+        assert_eq!(report.total().gate_toggles, 4);
+        // The gate signal starts low: 4 changes, on both transistors.
+        assert_eq!(report.total().transistor_toggles, 8);
+        assert_eq!(report.find("nmos").unwrap().transistor_toggles, 4);
+
+        let model = PowerModel::default();
+        let expected = model.energy("Not", 4)
+            + model.energy("NMOSTransistor", 4)
+            + model.energy("PMOSTransistor", 4);
+        assert!((report.total().energy - expected).abs() < 1e-9);
+        assert!(report.power() > 0.0);
+
+        reset_toggles(&mut not);
+        let report = super::report(&not, &PowerModel::default(), 0, usize::MAX);
+        assert_eq!(report.total().transistor_toggles, 0);
+        assert_eq!(report.total().gate_toggles, 0);
+    }
+
+    #[test]
+    fn activity_hierarchy() {
+        let mut and = And::new();
+        and.evaluate(true, true);
+        and.evaluate(false, true);
+        let report = report(&and, &PowerModel::default(), 2, 1);
+        assert_eq!(
+            report
+                .rows
+                .iter()
+                .map(|row| row.path.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["", "nand", "not"]
+        );
+        let children = &report.rows[1..];
+        assert_eq!(
+            report.total().gate_toggles,
+            children.iter().map(|row| row.gate_toggles).sum::<u64>()
+        );
+        assert!(report.to_string().contains("nand"));
+    }
+
+    #[test]
+    fn activity_adder_vs_multiplier() {
+        let energy = |component: &mut dyn Component, size: usize| {
+            for i in 0..16_u32 {
+                let a = Bits::from_int(i.wrapping_mul(7) % 16, Some(size));
+                let b = Bits::from_int(i.wrapping_mul(11) % 16, Some(size));
+                let inputs = a.concat(&b);
+                let inputs = if component.input_count() > inputs.len() {
+                    inputs.concat(&Bits::zeros(1))
+                } else {
+                    inputs
+                };
+                component.evaluate_bits(&inputs);
+            }
+            report(component, &PowerModel::default(), 16, 0)
+                .total()
+                .energy
+        };
+        let adder = energy(&mut BitwiseAdd::new(4), 4);
+        let multiplier = energy(&mut BitwiseMult::new(4), 4);
+        assert!(adder > 0.0);
+        assert!(multiplier > adder);
+    }
+}
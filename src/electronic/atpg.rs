@@ -0,0 +1,261 @@
+use std::fmt;
+
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{walk, Component};
+use crate::electronic::fault::{clear, clear_all, inject, Fault};
+use crate::electronic::truth_table::row_count;
+use crate::error::Error;
+
+/// A fault and the path of the component carrying it.
+pub type FaultSite = (String, Fault);
+
+/// Test vectors for the stuck-at faults of a combinational circuit.
+#[derive(Clone, Debug)]
+pub struct TestSet {
+    /// Input vectors, each detecting at least one fault no other vector catches.
+    pub vectors: Vec<Bits>,
+    pub detected: Vec<FaultSite>,
+    /// Faults no input vector can reveal at the outputs.
+    pub redundant: Vec<FaultSite>,
+}
+
+impl TestSet {
+    /// Share of the faults detected by the vectors, in `[0, 1]`.
+    pub fn coverage(&self) -> f64 {
+        let total = self.detected.len() + self.redundant.len();
+        if total == 0 {
+            1.0
+        } else {
+            self.detected.len() as f64 / total as f64
+        }
+    }
+}
+
+impl fmt::Display for TestSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} vectors", self.vectors.len())?;
+        for vector in &self.vectors {
+            writeln!(f, "  {}", vector)?;
+        }
+        writeln!(
+            f,
+            "{} of {} faults detected, {:.1}% coverage",
+            self.detected.len(),
+            self.detected.len() + self.redundant.len(),
+            100.0 * self.coverage()
+        )?;
+        for (path, fault) in &self.redundant {
+            writeln!(f, "  redundant: {} {}", path, fault)?;
+        }
+        Ok(())
+    }
+}
+
+/// Stuck-at faults on the output of every gate of `component`.
+pub fn stuck_at_faults(component: &dyn Component) -> Vec<FaultSite> {
+    let mut faults = Vec::new();
+    walk(component, &mut |path, sub_component| {
+        if sub_component.own_transistors() > 0 {
+            return;
+        }
+        for fault in sub_component.supported_faults() {
+            if Fault::STUCK_AT.contains(fault) {
+                faults.push((path.to_string(), *fault));
+            }
+        }
+    });
+    faults
+}
+
+/// Generates a compact set of vectors detecting every detectable stuck-at fault on the gate
+/// outputs of the combinational `component`.
+///
+/// Each fault still undetected gets a branch-and-bound enumeration of the primary inputs: they
+/// are assigned one at a time in order, the good and faulty circuits are simulated on
+/// three-valued logic with `X` for the unassigned inputs, and a branch is abandoned as soon as
+/// no output can differ any more. Unlike PODEM there is no objective traced back to an input,
+/// so a hard fault can take every input value: circuits with more than
+/// [`MAX_TABLE_INPUTS`](crate::electronic::truth_table::MAX_TABLE_INPUTS) inputs are an
+/// [`Error::TooManyInputs`]. A vector found is simulated
+/// against the remaining faults to drop those it also detects, and vectors made useless by
+/// later ones are removed at the end.
+pub fn generate(component: &mut dyn Component) -> Result<TestSet, Error> {
+    row_count(component.input_count())?;
+    clear_all(component);
+    let mut remaining = stuck_at_faults(component);
+    let mut vectors = Vec::new();
+    let mut detected = Vec::new();
+    let mut redundant = Vec::new();
+
+    while let Some(site) = remaining.first().cloned() {
+        let mut inputs = LBits::filled(Logic::X, component.input_count());
+        if search(component, &site, &mut inputs, 0)? {
+            let vector = inputs.to_bits().expect("all inputs are assigned");
+            let caught = simulate(component, &vector, &remaining)?;
+            remaining.retain(|site| !caught.contains(site));
+            detected.extend(caught);
+            vectors.push(vector);
+        } else {
+            remaining.remove(0);
+            redundant.push(site);
+        }
+    }
+
+    let vectors = compact(component, vectors, &detected)?;
+    Ok(TestSet {
+        vectors,
+        detected,
+        redundant,
+    })
+}
+
+/// Faults among `faults` whose effect `vector` brings to an output.
+pub fn simulate(
+    component: &mut dyn Component,
+    vector: &Bits,
+    faults: &[FaultSite],
+) -> Result<Vec<FaultSite>, Error> {
+    let good = component.try_evaluate_bits(vector)?;
+    let mut caught = Vec::new();
+    for site in faults {
+        inject(component, &site.0, site.1)?;
+        let faulty = component.try_evaluate_bits(vector);
+        clear(component, &site.0)?;
+        if faulty? != good {
+            caught.push(site.clone());
+        }
+    }
+    Ok(caught)
+}
+
+/// Assigns `inputs` from `next` on, true once a vector detecting `site` is found.
+fn search(
+    component: &mut dyn Component,
+    site: &FaultSite,
+    inputs: &mut LBits,
+    next: usize,
+) -> Result<bool, Error> {
+    let good = component.try_evaluate_logic_bits(inputs)?;
+    inject(component, &site.0, site.1)?;
+    let faulty = component.try_evaluate_logic_bits(inputs);
+    clear(component, &site.0)?;
+    let faulty = faulty?;
+
+    let pairs = good.iter().zip(faulty.iter());
+    let detected = pairs
+        .clone()
+        .any(|(good, faulty)| good.is_known() && faulty.is_known() && good != faulty);
+    if detected {
+        // The unassigned inputs do not matter, any value keeps the difference.
+        for i in next..inputs.len() {
+            inputs.set(i, Logic::Zero);
+        }
+        return Ok(true);
+    }
+    let possible = pairs
+        .clone()
+        .any(|(good, faulty)| !good.is_known() || !faulty.is_known());
+    if !possible || next == inputs.len() {
+        return Ok(false);
+    }
+
+    for value in [Logic::Zero, Logic::One] {
+        inputs.set(next, value);
+        if search(component, site, inputs, next + 1)? {
+            return Ok(true);
+        }
+    }
+    inputs.set(next, Logic::X);
+    Ok(false)
+}
+
+/// Drops the vectors whose faults are all detected by the others, the last vectors first.
+fn compact(
+    component: &mut dyn Component,
+    vectors: Vec<Bits>,
+    faults: &[FaultSite],
+) -> Result<Vec<Bits>, Error> {
+    let mut remaining = faults.to_vec();
+    let mut kept = Vec::new();
+    for vector in vectors.into_iter().rev() {
+        let caught = simulate(component, &vector, &remaining)?;
+        if !caught.is_empty() {
+            remaining.retain(|site| !caught.contains(site));
+            kept.push(vector);
+        }
+    }
+    kept.reverse();
+    Ok(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::decoder::Decoder;
+    use crate::electronic::circuits::mux::Mux2To1;
+    use crate::electronic::truth_table::MAX_TABLE_INPUTS;
+
+    fn check(component: &mut dyn Component, test_set: &TestSet) {
+        let mut caught = Vec::new();
+        for vector in &test_set.vectors {
+            caught.extend(simulate(component, vector, &test_set.detected).unwrap());
+        }
+        for site in &test_set.detected {
+            assert!(caught.contains(site), "{:?} is not detected", site);
+        }
+    }
+
+    #[test]
+    fn atpg_mux() {
+        let mut mux = Mux2To1::new();
+        let test_set = generate(&mut mux).unwrap();
+        assert_eq!(test_set.detected.len(), stuck_at_faults(&mux).len());
+        assert!(test_set.redundant.is_empty());
+        assert_eq!(test_set.coverage(), 1.0);
+        assert!(test_set.vectors.len() < 8);
+        check(&mut mux, &test_set);
+    }
+
+    #[test]
+    fn atpg_bitwise_add() {
+        let mut bitwise_add = BitwiseAdd::new(4);
+        let test_set = generate(&mut bitwise_add).unwrap();
+        assert_eq!(test_set.coverage(), 1.0);
+        assert!(test_set.vectors.len() <= 16);
+        check(&mut bitwise_add, &test_set);
+
+        // 33 inputs would take up to 2^33 branches per fault.
+        assert_eq!(
+            generate(&mut BitwiseAdd::new(16)).err(),
+            Some(Error::TooManyInputs {
+                inputs: 33,
+                limit: MAX_TABLE_INPUTS
+            })
+        );
+    }
+
+    #[test]
+    fn atpg_redundant() {
+        // A 2-bit decoder is a Decoder2To4, its own inverter is left unconnected.
+        let mut decoder = Decoder::new(2);
+        let test_set = generate(&mut decoder).unwrap();
+        assert_eq!(
+            test_set.redundant,
+            vec![
+                ("not".to_string(), Fault::StuckAt0),
+                ("not".to_string(), Fault::StuckAt1)
+            ]
+        );
+        assert!(test_set.coverage() < 1.0);
+        check(&mut decoder, &test_set);
+        assert!(test_set.to_string().contains("redundant: not stuck-at-0"));
+
+        // The same inverter in both half decoders.
+        let mut decoder = Decoder::new(3);
+        let test_set = generate(&mut decoder).unwrap();
+        assert_eq!(test_set.redundant.len(), 2 * 2);
+        check(&mut decoder, &test_set);
+    }
+}
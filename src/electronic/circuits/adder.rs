@@ -1,6 +1,11 @@
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{names, pin, port, Component, Pin};
 use crate::electronic::circuits::logic_gates::and::And;
 use crate::electronic::circuits::logic_gates::or::Or;
 use crate::electronic::circuits::logic_gates::xor::Xor;
+use crate::electronic::timing::Waveform;
+use crate::error::Error;
 
 pub struct AdderResult {
     pub sum: bool,
@@ -27,6 +32,30 @@ impl HalfAdder {
             carry_out: _carry,
         }
     }
+
+    /// Four-valued evaluation, returns the sum and carry out.
+    pub fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic) -> (Logic, Logic) {
+        let _sum_result = self.xor.evaluate_logic(signal_a, signal_b);
+        let _carry = self.and.evaluate_logic(signal_a, signal_b);
+        (_sum_result, _carry)
+    }
+
+    /// Timed evaluation, returns the sum and carry out waveforms.
+    pub fn evaluate_timed(
+        &mut self,
+        signal_a: &Waveform,
+        signal_b: &Waveform,
+    ) -> (Waveform, Waveform) {
+        let _sum_result = self.xor.evaluate_timed(signal_a, signal_b);
+        let _carry = self.and.evaluate_timed(signal_a, signal_b);
+        (_sum_result, _carry)
+    }
+}
+
+impl Default for HalfAdder {
+    fn default() -> Self {
+        HalfAdder::new()
+    }
 }
 
 pub struct FullAdder {
@@ -59,6 +88,185 @@ impl FullAdder {
             carry_out: _carry,
         }
     }
+
+    /// Four-valued evaluation, returns the sum and carry out.
+    pub fn evaluate_logic(
+        &mut self,
+        _signal_a: Logic,
+        _signal_b: Logic,
+        _carry_in: Logic,
+    ) -> (Logic, Logic) {
+        let _xor0_result = self.xor0.evaluate_logic(_signal_a, _signal_b);
+        let _sum_result = self.xor1.evaluate_logic(_xor0_result, _carry_in);
+        let _and0_result = self.and0.evaluate_logic(_carry_in, _xor0_result);
+        let _and1_result = self.and1.evaluate_logic(_signal_a, _signal_b);
+        let _carry = self.or.evaluate_logic(_and0_result, _and1_result);
+        (_sum_result, _carry)
+    }
+
+    /// Gate levels the sum and carry out settle at, for `a` and `b` settled after `a_b` levels
+    /// and the carry in after `carry_in`. Only the carry in to carry out path adds up along a
+    /// ripple adder.
+    pub(crate) fn arrivals(&self, a_b: usize, carry_in: usize) -> (usize, usize) {
+        let xor0 = a_b + self.xor0.logic_depth();
+        let sum = xor0.max(carry_in) + self.xor1.logic_depth();
+        let carry = (xor0.max(carry_in) + self.and0.logic_depth())
+            .max(a_b + self.and1.logic_depth())
+            + self.or.logic_depth();
+        (sum, carry)
+    }
+
+    /// Timed evaluation, returns the sum and carry out waveforms.
+    pub fn evaluate_timed(
+        &mut self,
+        _signal_a: &Waveform,
+        _signal_b: &Waveform,
+        _carry_in: &Waveform,
+    ) -> (Waveform, Waveform) {
+        let _xor0_result = self.xor0.evaluate_timed(_signal_a, _signal_b);
+        let _sum_result = self.xor1.evaluate_timed(&_xor0_result, _carry_in);
+        let _and0_result = self.and0.evaluate_timed(_carry_in, &_xor0_result);
+        let _and1_result = self.and1.evaluate_timed(_signal_a, _signal_b);
+        let _carry = self.or.evaluate_timed(&_and0_result, &_and1_result);
+        (_sum_result, _carry)
+    }
+}
+
+impl Default for FullAdder {
+    fn default() -> Self {
+        FullAdder::new()
+    }
+}
+
+impl Component for HalfAdder {
+    fn type_name(&self) -> &'static str {
+        "HalfAdder"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["sum", "carry_out"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let result = self.evaluate(inputs[0], inputs[1]);
+        Ok(Bits::from_slice_b(&[result.sum, result.carry_out], None))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let (sum, carry_out) = self.evaluate_logic(inputs[0], inputs[1]);
+        Ok(LBits::from_vector(vec![sum, carry_out]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        let (sum, carry_out) = self.evaluate_timed(&inputs[0], &inputs[1]);
+        Ok(vec![sum, carry_out])
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("xor".to_string(), &self.xor as &dyn Component),
+            ("and".to_string(), &self.and as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("xor".to_string(), &mut self.xor as &mut dyn Component),
+            ("and".to_string(), &mut self.and as &mut dyn Component),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("a"), pin("xor", "a")),
+            (port("b"), pin("xor", "b")),
+            (port("a"), pin("and", "a")),
+            (port("b"), pin("and", "b")),
+            (pin("xor", "y"), port("sum")),
+            (pin("and", "y"), port("carry_out")),
+        ]
+    }
+}
+
+impl Component for FullAdder {
+    fn type_name(&self) -> &'static str {
+        "FullAdder"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b", "carry_in"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["sum", "carry_out"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let result = self.evaluate(inputs[0], inputs[1], inputs[2]);
+        Ok(Bits::from_slice_b(&[result.sum, result.carry_out], None))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let (sum, carry_out) = self.evaluate_logic(inputs[0], inputs[1], inputs[2]);
+        Ok(LBits::from_vector(vec![sum, carry_out]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        let (sum, carry_out) = self.evaluate_timed(&inputs[0], &inputs[1], &inputs[2]);
+        Ok(vec![sum, carry_out])
+    }
+
+    fn logic_depth(&self) -> usize {
+        let (sum, carry) = self.arrivals(0, 0);
+        sum.max(carry)
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("xor0".to_string(), &self.xor0 as &dyn Component),
+            ("xor1".to_string(), &self.xor1 as &dyn Component),
+            ("and0".to_string(), &self.and0 as &dyn Component),
+            ("and1".to_string(), &self.and1 as &dyn Component),
+            ("or".to_string(), &self.or as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("xor0".to_string(), &mut self.xor0 as &mut dyn Component),
+            ("xor1".to_string(), &mut self.xor1 as &mut dyn Component),
+            ("and0".to_string(), &mut self.and0 as &mut dyn Component),
+            ("and1".to_string(), &mut self.and1 as &mut dyn Component),
+            ("or".to_string(), &mut self.or as &mut dyn Component),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("a"), pin("xor0", "a")),
+            (port("b"), pin("xor0", "b")),
+            (pin("xor0", "y"), pin("xor1", "a")),
+            (port("carry_in"), pin("xor1", "b")),
+            (port("carry_in"), pin("and0", "a")),
+            (pin("xor0", "y"), pin("and0", "b")),
+            (port("a"), pin("and1", "a")),
+            (port("b"), pin("and1", "b")),
+            (pin("and0", "y"), pin("or", "a")),
+            (pin("and1", "y"), pin("or", "b")),
+            (pin("xor1", "y"), port("sum")),
+            (pin("or", "y"), port("carry_out")),
+        ]
+    }
 }
 
 #[cfg(test)]
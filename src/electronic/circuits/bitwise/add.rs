@@ -1,10 +1,14 @@
 use std::iter::zip;
 
 use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
 use crate::electronic::circuits::adder::FullAdder;
 use crate::electronic::circuits::bitwise::Bitwise;
 
 use super::BitwiseCheck;
+use crate::electronic::circuits::component::{bus_names, indexed, pin, port, Component, Pin};
+use crate::electronic::timing::Waveform;
+use crate::error::Error;
 
 pub struct BitwiseAdd {
     bitwise: Bitwise,
@@ -12,13 +16,13 @@ pub struct BitwiseAdd {
 }
 
 impl BitwiseCheck for BitwiseAdd {
-    fn size(&self) -> u8 {
+    fn size(&self) -> usize {
         self.bitwise.size
     }
 }
 
 impl BitwiseAdd {
-    pub fn new(size: u8) -> Self {
+    pub fn new(size: usize) -> Self {
         let adders = (0..size)
             .map(|_| FullAdder::new())
             .collect::<Vec<FullAdder>>();
@@ -29,8 +33,18 @@ impl BitwiseAdd {
     }
 
     pub fn evaluate(&mut self, d1: &Bits, d2: &Bits, carry: bool) -> (Bits, bool) {
-        self.check_input(d1);
-        self.check_input(d2);
+        self.try_evaluate(d1, d2, carry)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_evaluate(
+        &mut self,
+        d1: &Bits,
+        d2: &Bits,
+        carry: bool,
+    ) -> Result<(Bits, bool), Error> {
+        self.try_check_input("d1", d1)?;
+        self.try_check_input("d2", d2)?;
 
         let mut carry_in = carry;
         let mut output = Vec::<bool>::new();
@@ -39,14 +53,161 @@ impl BitwiseAdd {
             self.adders.iter_mut(),
             zip(d1.iter().rev(), d2.iter().rev()),
         ) {
-            let adder_result = adder.evaluate(*bit1, *bit2, carry_in);
+            let adder_result = adder.evaluate(bit1, bit2, carry_in);
             carry_in = adder_result.carry_out;
             output.push(adder_result.sum);
             carry_out = adder_result.carry_out
         }
         output.reverse();
 
-        (Bits::from_vector_b(output, None), carry_out)
+        Ok((Bits::from_vector_b(output, None), carry_out))
+    }
+
+    /// Four-valued evaluation on buses given MSB first, returns the sum bus and the carry out.
+    pub fn try_evaluate_logic(
+        &mut self,
+        d1: &[Logic],
+        d2: &[Logic],
+        carry: Logic,
+    ) -> Result<(LBits, Logic), Error> {
+        Error::check_width("d1", self.size(), d1.len())?;
+        Error::check_width("d2", self.size(), d2.len())?;
+
+        let mut carry_in = carry;
+        let mut output = Vec::<Logic>::new();
+        for (adder, (bit1, bit2)) in zip(
+            self.adders.iter_mut(),
+            zip(d1.iter().rev(), d2.iter().rev()),
+        ) {
+            let (sum, carry_out) = adder.evaluate_logic(*bit1, *bit2, carry_in);
+            carry_in = carry_out;
+            output.push(sum);
+        }
+        output.reverse();
+
+        Ok((LBits::from_vector(output), carry_in))
+    }
+
+    /// Timed evaluation on buses given MSB first, returns the sum bus and the carry out.
+    pub fn evaluate_timed(
+        &mut self,
+        d1: &[Waveform],
+        d2: &[Waveform],
+        carry: &Waveform,
+    ) -> Result<(Vec<Waveform>, Waveform), Error> {
+        Error::check_width("d1", self.size(), d1.len())?;
+        Error::check_width("d2", self.size(), d2.len())?;
+
+        let mut carry_in = carry.clone();
+        let mut output = Vec::<Waveform>::new();
+        for (adder, (bit1, bit2)) in zip(
+            self.adders.iter_mut(),
+            zip(d1.iter().rev(), d2.iter().rev()),
+        ) {
+            let (sum, carry_out) = adder.evaluate_timed(bit1, bit2, &carry_in);
+            carry_in = carry_out;
+            output.push(sum);
+        }
+        output.reverse();
+
+        Ok((output, carry_in))
+    }
+
+    /// Gate levels each sum bit (MSB first) and the carry out settle at, for input bits
+    /// settled after the given levels.
+    pub(crate) fn arrivals(&self, d1: &[usize], d2: &[usize], carry: usize) -> (Vec<usize>, usize) {
+        let mut carry_in = carry;
+        let mut output = Vec::<usize>::new();
+        for (adder, (bit1, bit2)) in zip(&self.adders, zip(d1.iter().rev(), d2.iter().rev())) {
+            let (sum, carry_out) = adder.arrivals(*bit1.max(bit2), carry_in);
+            carry_in = carry_out;
+            output.push(sum);
+        }
+        output.reverse();
+        (output, carry_in)
+    }
+}
+
+impl Component for BitwiseAdd {
+    fn type_name(&self) -> &'static str {
+        "BitwiseAdd"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        let mut inputs = bus_names("d1", self.size());
+        inputs.extend(bus_names("d2", self.size()));
+        inputs.push("carry".to_string());
+        inputs
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        let mut outputs = bus_names("sum", self.size());
+        outputs.push("carry_out".to_string());
+        outputs
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let (d1, rest) = inputs.split_at(self.size());
+        let (d2, carry) = rest.split_at(self.size());
+        let (sum, carry_out) = self.try_evaluate(&d1, &d2, carry[0])?;
+        Ok(sum.concat(&Bits::from_slice_b(&[carry_out], None)))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let inputs = inputs.iter().copied().collect::<Vec<Logic>>();
+        let (d1, rest) = inputs.split_at(self.size());
+        let (d2, carry) = rest.split_at(self.size());
+        let (sum, carry_out) = self.try_evaluate_logic(d1, d2, carry[0])?;
+        Ok(sum.iter().copied().chain([carry_out]).collect())
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        let (d1, rest) = inputs.split_at(self.size());
+        let (d2, carry) = rest.split_at(self.size());
+        let (mut sum, carry_out) = self.evaluate_timed(d1, d2, &carry[0])?;
+        sum.push(carry_out);
+        Ok(sum)
+    }
+
+    fn logic_depth(&self) -> usize {
+        let inputs = vec![0; self.size()];
+        let (sum, carry_out) = self.arrivals(&inputs, &inputs, 0);
+        sum.into_iter().fold(carry_out, usize::max)
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> = Vec::new();
+        for (i, component) in self.adders.iter().enumerate() {
+            sub_components.push((indexed("adders", i), component));
+        }
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> = Vec::new();
+        for (i, component) in self.adders.iter_mut().enumerate() {
+            sub_components.push((indexed("adders", i), component));
+        }
+        sub_components
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        // `adders[j]` handles bit j, from the LSB.
+        let mut wires = Vec::new();
+        let mut carry = port("carry");
+        for j in 0..self.size() {
+            let adder = indexed("adders", j);
+            wires.push((port(&indexed("d1", j)), pin(&adder, "a")));
+            wires.push((port(&indexed("d2", j)), pin(&adder, "b")));
+            wires.push((carry, pin(&adder, "carry_in")));
+            wires.push((pin(&adder, "sum"), port(&indexed("sum", j))));
+            carry = pin(&adder, "carry_out");
+        }
+        wires.push((carry, port("carry_out")));
+        wires
     }
 }
 
@@ -72,6 +233,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bitwise_add_evaluate_carry_out() {
+        let mut bitwise_add = BitwiseAdd::new(4);
+        for d1 in 0..16 {
+            for d2 in 0..16 {
+                let data1 = Bits::from_int(d1, Some(4));
+                let data2 = Bits::from_int(d2, Some(4));
+
+                let (result, carry_out) = bitwise_add.evaluate(&data1, &data2, false);
+
+                let with_carry = Bits::from_slice_b(&[carry_out], None).concat(&result);
+                assert_eq!(with_carry.to_int(), d1 + d2);
+                assert!(with_carry.len() == 5);
+            }
+        }
+    }
+
+    #[test]
+    fn bitwise_add_evaluate_signed() {
+        let mut bitwise_add = BitwiseAdd::new(4);
+        for d1 in -8..8 {
+            for d2 in -8..8 {
+                let data1 = Bits::from_i64(d1, Some(4));
+                let data2 = Bits::from_i64(d2, Some(4));
+                let (result, _) = bitwise_add.evaluate(&data1, &data2, false);
+
+                let expected = d1 + d2;
+                let overflow = !(-8..8).contains(&expected);
+                assert_eq!(Bits::add_overflowed(&data1, &data2, &result), overflow);
+                if !overflow {
+                    assert_eq!(result.to_i64(), expected);
+                }
+            }
+        }
+    }
+
     #[test]
     #[should_panic]
     fn bitwise_add_wrong_size_d1() {
@@ -91,4 +288,18 @@ mod tests {
 
         bitwise_add.evaluate(&data1, &data2, true);
     }
+
+    #[test]
+    fn bitwise_add_try_evaluate() {
+        let mut bitwise_add = BitwiseAdd::new(4);
+        let result = bitwise_add.try_evaluate(&Bits::zeros(4), &Bits::zeros(3), false);
+        assert_eq!(
+            result,
+            Err(Error::WidthMismatch {
+                input: "d2",
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
 }
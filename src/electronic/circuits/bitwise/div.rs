@@ -1,12 +1,15 @@
 use std::iter::zip;
 
 use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
 use crate::electronic::circuits::bitwise::Bitwise;
 use crate::electronic::circuits::logic_gates::not::Not;
 use crate::electronic::circuits::logic_gates::or::Or;
 use crate::electronic::circuits::subtractor::FullSubtractorRestore;
 
 use super::BitwiseCheck;
+use crate::electronic::circuits::component::{bus_names, indexed, pin, port, Component, Pin};
+use crate::error::Error;
 
 pub struct BitwiseDiv {
     bitwise: Bitwise,
@@ -16,13 +19,13 @@ pub struct BitwiseDiv {
 }
 
 impl BitwiseCheck for BitwiseDiv {
-    fn size(&self) -> u8 {
+    fn size(&self) -> usize {
         self.bitwise.size
     }
 }
 
 impl BitwiseDiv {
-    pub fn new(size: u8) -> Self {
+    pub fn new(size: usize) -> Self {
         let mut ors = Vec::new();
         let mut nots = Vec::new();
         let mut subrestores = Vec::new();
@@ -45,24 +48,26 @@ impl BitwiseDiv {
     }
 
     pub fn evaluate(&mut self, a: &Bits, d: &Bits) -> (Bits, Bits) {
-        self.check_input(a);
-        self.check_input(d);
+        self.try_evaluate(a, d)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_evaluate(&mut self, a: &Bits, d: &Bits) -> Result<(Bits, Bits), Error> {
+        self.try_check_input("a", a)?;
+        self.try_check_input("d", d)?;
 
-        if d.iter().all(|&b| !b) {
-            panic!("Divider cannot be 0")
+        if d.iter().all(|b| !b) {
+            return Err(Error::DivisionByZero);
         }
 
         let mut filled_a = (0..a.len()).map(|_| false).collect::<Vec<bool>>();
-        filled_a.extend(a.data());
+        filled_a.extend(a.iter());
 
         let mut quotient = Vec::<bool>::new();
         let mut last_remainder_row = Vec::<bool>::new();
-        for i in 0..self.size() as usize {
+        for i in 0..self.size() {
             let (bit_or, row_a) = if i == 0 {
-                (
-                    filled_a[0],
-                    filled_a[i + 1..i + 1 + self.size() as usize].to_vec(),
-                )
+                (filled_a[0], filled_a[i + 1..i + 1 + self.size()].to_vec())
             } else {
                 let r = &last_remainder_row[1..last_remainder_row.len()];
                 let row_a: Vec<bool> = [r, &[a[i]]].concat();
@@ -73,14 +78,14 @@ impl BitwiseDiv {
             last_remainder_row = remainder_row;
             quotient.push(quotient_row);
         }
-        (
+        Ok((
             Bits::from_vector_b(quotient, None),
             Bits::from_vector_b(last_remainder_row, None),
-        )
+        ))
     }
 
     fn _row(&mut self, row: usize, a: Vec<bool>, d: &Bits, bit_or: bool) -> (bool, Vec<bool>) {
-        if a.len() != self.size() as usize {
+        if a.len() != self.size() {
             panic!(
                 "Length of input a should be {} but is {}",
                 self.size(),
@@ -96,7 +101,7 @@ impl BitwiseDiv {
             self.subrestores[row].iter_mut(),
             zip(a.iter().rev(), d.iter().rev()),
         ) {
-            let (_, borrow_out) = subrestore.evaluate(*bit_a, *bit_d, borrow_in, carry_in);
+            let (_, borrow_out) = subrestore.evaluate(*bit_a, bit_d, borrow_in, carry_in);
             borrow_in = borrow_out;
             last_borrow_out = borrow_out;
         }
@@ -112,7 +117,7 @@ impl BitwiseDiv {
             zip(a.iter().rev(), d.iter().rev()),
         ) {
             let (subrestore_result, borrow_out) =
-                subrestore.evaluate(*bit_a, *bit_d, borrow_in, carry_in);
+                subrestore.evaluate(*bit_a, bit_d, borrow_in, carry_in);
             remainder.push(subrestore_result);
             borrow_in = borrow_out;
         }
@@ -120,6 +125,211 @@ impl BitwiseDiv {
         remainder.reverse();
         (quotient, remainder)
     }
+
+    /// Four-valued evaluation on buses given MSB first, returns the quotient and remainder
+    /// buses. Division by zero is only reported when `d` is known to be zero.
+    pub fn try_evaluate_logic(
+        &mut self,
+        a: &[Logic],
+        d: &[Logic],
+    ) -> Result<(LBits, LBits), Error> {
+        Error::check_width("a", self.size(), a.len())?;
+        Error::check_width("d", self.size(), d.len())?;
+
+        if d.iter().all(|&b| b == Logic::Zero) {
+            return Err(Error::DivisionByZero);
+        }
+
+        let mut filled_a = vec![Logic::Zero; a.len()];
+        filled_a.extend(a);
+
+        let mut quotient = Vec::<Logic>::new();
+        let mut last_remainder_row = Vec::<Logic>::new();
+        for i in 0..self.size() {
+            let (bit_or, row_a) = if i == 0 {
+                (filled_a[0], filled_a[i + 1..i + 1 + self.size()].to_vec())
+            } else {
+                let r = &last_remainder_row[1..last_remainder_row.len()];
+                let row_a: Vec<Logic> = [r, &[a[i]]].concat();
+                (last_remainder_row[0], row_a)
+            };
+
+            let (quotient_row, remainder_row) = self.row_logic(i, &row_a, d, bit_or);
+            last_remainder_row = remainder_row;
+            quotient.push(quotient_row);
+        }
+        Ok((
+            LBits::from_vector(quotient),
+            LBits::from_vector(last_remainder_row),
+        ))
+    }
+
+    fn row_logic(
+        &mut self,
+        row: usize,
+        a: &[Logic],
+        d: &[Logic],
+        bit_or: Logic,
+    ) -> (Logic, Vec<Logic>) {
+        let mut borrow_in = Logic::Zero;
+        let mut carry_in = Logic::One;
+        let mut last_borrow_out = Logic::Zero;
+
+        for (subrestore, (bit_a, bit_d)) in zip(
+            self.subrestores[row].iter_mut(),
+            zip(a.iter().rev(), d.iter().rev()),
+        ) {
+            let (_, borrow_out) = subrestore.evaluate_logic(*bit_a, *bit_d, borrow_in, carry_in);
+            borrow_in = borrow_out;
+            last_borrow_out = borrow_out;
+        }
+
+        let not_borrow_out = self.nots[row].evaluate_logic(last_borrow_out);
+        let quotient = self.ors[row].evaluate_logic(bit_or, not_borrow_out);
+
+        let mut remainder = Vec::<Logic>::new();
+        carry_in = quotient;
+
+        for (subrestore, (bit_a, bit_d)) in zip(
+            self.subrestores[row].iter_mut(),
+            zip(a.iter().rev(), d.iter().rev()),
+        ) {
+            let (subrestore_result, borrow_out) =
+                subrestore.evaluate_logic(*bit_a, *bit_d, borrow_in, carry_in);
+            remainder.push(subrestore_result);
+            borrow_in = borrow_out;
+        }
+
+        remainder.reverse();
+        (quotient, remainder)
+    }
+}
+
+impl Component for BitwiseDiv {
+    fn type_name(&self) -> &'static str {
+        "BitwiseDiv"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        let mut inputs = bus_names("a", self.size());
+        inputs.extend(bus_names("d", self.size()));
+        inputs
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        let mut outputs = bus_names("quotient", self.size());
+        outputs.extend(bus_names("remainder", self.size()));
+        outputs
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let (a, d) = inputs.split_at(self.size());
+        let (quotient, remainder) = self.try_evaluate(&a, &d)?;
+        Ok(quotient.concat(&remainder))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let inputs = inputs.iter().copied().collect::<Vec<Logic>>();
+        let (a, d) = inputs.split_at(self.size());
+        let (quotient, remainder) = self.try_evaluate_logic(a, d)?;
+        Ok(quotient.iter().chain(remainder.iter()).copied().collect())
+    }
+
+    fn logic_depth(&self) -> usize {
+        // Each row ripples the borrow, derives the quotient bit, then restores through the muxes.
+        // Bits are followed LSB first, along the same paths as `wires`.
+        let size = self.size();
+        let mut remainder = vec![0; size];
+        let mut bit_or = 0;
+        let mut depth = 0;
+        for (row, (not, or)) in zip(&self.subrestores, zip(&self.nots, &self.ors)) {
+            let mut row_a = vec![0];
+            row_a.extend(&remainder[..size - 1]);
+            let mut borrow = 0;
+            let mut borrows = Vec::new();
+            for (subrestore, a) in zip(row, &row_a) {
+                borrows.push(borrow);
+                borrow = subrestore.arrivals(*a, borrow, 0).1;
+            }
+            let quotient = bit_or.max(borrow + not.logic_depth()) + or.logic_depth();
+            remainder = zip(row, zip(&row_a, borrows))
+                .map(|(subrestore, (a, borrow))| subrestore.arrivals(*a, borrow, quotient).0)
+                .collect();
+            bit_or = remainder[size - 1];
+            depth = depth.max(quotient);
+        }
+        remainder.into_iter().fold(depth, usize::max)
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> = Vec::new();
+        for (i, component) in self.ors.iter().enumerate() {
+            sub_components.push((indexed("ors", i), component));
+        }
+        for (i, component) in self.nots.iter().enumerate() {
+            sub_components.push((indexed("nots", i), component));
+        }
+        for (i, row) in self.subrestores.iter().enumerate() {
+            for (j, component) in row.iter().enumerate() {
+                sub_components.push((format!("subrestores[{}][{}]", i, j), component));
+            }
+        }
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> = Vec::new();
+        for (i, component) in self.ors.iter_mut().enumerate() {
+            sub_components.push((indexed("ors", i), component));
+        }
+        for (i, component) in self.nots.iter_mut().enumerate() {
+            sub_components.push((indexed("nots", i), component));
+        }
+        for (i, row) in self.subrestores.iter_mut().enumerate() {
+            for (j, component) in row.iter_mut().enumerate() {
+                sub_components.push((format!("subrestores[{}][{}]", i, j), component));
+            }
+        }
+        sub_components
+    }
+
+    /// One pass per row: the borrow of a `FullSubtractorRestore` does not depend on its carry,
+    /// so the quotient bit can drive the carries of the row that computed it.
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        let size = self.size();
+        let mut wires = Vec::new();
+        // Partial remainder of the previous row, LSB first, and the bit above it.
+        let mut remainder = vec![Pin::Constant(false); size];
+        let mut bit_or = Pin::Constant(false);
+        for i in 0..size {
+            let mut row_a = vec![port(&indexed("a", size - 1 - i))];
+            row_a.extend(remainder[..size - 1].iter().cloned());
+            let mut borrow = Pin::Constant(false);
+            for (j, a) in row_a.into_iter().enumerate() {
+                let subrestore = format!("subrestores[{}][{}]", i, j);
+                wires.push((a, pin(&subrestore, "a")));
+                wires.push((port(&indexed("d", j)), pin(&subrestore, "b")));
+                wires.push((borrow, pin(&subrestore, "borrow_in")));
+                wires.push((pin(&indexed("ors", i), "y"), pin(&subrestore, "carry")));
+                borrow = pin(&subrestore, "borrow_out");
+            }
+            let (not, or) = (indexed("nots", i), indexed("ors", i));
+            wires.push((borrow, pin(&not, "a")));
+            wires.push((bit_or, pin(&or, "a")));
+            wires.push((pin(&not, "y"), pin(&or, "b")));
+            wires.push((pin(&or, "y"), port(&indexed("quotient", size - 1 - i))));
+            remainder = (0..size)
+                .map(|j| pin(&format!("subrestores[{}][{}]", i, j), "result"))
+                .collect();
+            bit_or = remainder[size - 1].clone();
+        }
+        for (j, driver) in remainder.into_iter().enumerate() {
+            wires.push((driver, port(&indexed("remainder", j))));
+        }
+        wires
+    }
 }
 
 #[cfg(test)]
@@ -133,14 +343,38 @@ mod tests {
                 let data = Bits::from_int(d1, Some(4));
                 let divider = Bits::from_int(d2, Some(4));
 
-                if d2 != 0 {
-                    let mut bitwise_div = BitwiseDiv::new(4);
-                    let (quotient, remainder) = bitwise_div.evaluate(&data, &divider);
-
-                    assert_eq!(quotient.to_int(), d1 / d2);
-                    assert_eq!(remainder.to_int(), d1 % d2);
+                let mut bitwise_div = BitwiseDiv::new(4);
+                let result = bitwise_div.try_evaluate(&data, &divider);
+                match d1.checked_div(d2) {
+                    Some(expected) => {
+                        let (quotient, remainder) = result.unwrap();
+                        assert_eq!(quotient.to_int(), expected);
+                        assert_eq!(remainder.to_int(), d1 % d2);
+                    }
+                    None => assert_eq!(result, Err(Error::DivisionByZero)),
                 }
             }
         }
     }
+
+    #[test]
+    fn bitwise_div_wrong_size() {
+        let mut bitwise_div = BitwiseDiv::new(4);
+        let result = bitwise_div.try_evaluate(&Bits::from_int(1, Some(4)), &Bits::ones(5));
+        assert_eq!(
+            result,
+            Err(Error::WidthMismatch {
+                input: "d",
+                expected: 4,
+                actual: 5
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn bitwise_div_by_zero() {
+        let mut bitwise_div = BitwiseDiv::new(4);
+        bitwise_div.evaluate(&Bits::from_int(1, Some(4)), &Bits::zeros(4));
+    }
 }
@@ -4,13 +4,14 @@ pub mod mult;
 pub mod sub;
 
 use crate::data::bits::Bits;
+use crate::error::Error;
 
 pub struct Bitwise {
-    size: u8,
+    size: usize,
 }
 
 impl Bitwise {
-    pub fn new(size: u8) -> Self {
+    pub fn new(size: usize) -> Self {
         Bitwise { size }
     }
     // pub fn check_input(&mut self, i: &Bits) {
@@ -21,16 +22,15 @@ impl Bitwise {
 }
 
 pub trait BitwiseCheck {
-    fn size(&self) -> u8;
+    fn size(&self) -> usize;
 
     fn check_input(&mut self, i: &Bits) {
-        if i.len() != self.size() as usize {
-            panic!(
-                "Length of {} should be {} but is {}",
-                stringify!(i),
-                self.size(),
-                i.len()
-            );
-        }
+        self.try_check_input("i", i)
+            .unwrap_or_else(|error| panic!("{}", error));
+    }
+
+    /// Checks that the `input` operand has the width the circuit was built for.
+    fn try_check_input(&self, input: &'static str, i: &Bits) -> Result<(), Error> {
+        Error::check_width(input, self.size(), i.len())
     }
 }
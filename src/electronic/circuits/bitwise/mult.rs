@@ -1,11 +1,14 @@
 use std::iter::zip;
 
 use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
 use crate::electronic::circuits::bitwise::add::BitwiseAdd;
 use crate::electronic::circuits::bitwise::Bitwise;
 use crate::electronic::circuits::logic_gates::and::And;
 
 use super::BitwiseCheck;
+use crate::electronic::circuits::component::{bus_names, indexed, pin, port, Component, Pin};
+use crate::error::Error;
 
 pub struct BitwiseMult {
     bitwise: Bitwise,
@@ -14,13 +17,13 @@ pub struct BitwiseMult {
 }
 
 impl BitwiseCheck for BitwiseMult {
-    fn size(&self) -> u8 {
+    fn size(&self) -> usize {
         self.bitwise.size
     }
 }
 
 impl BitwiseMult {
-    pub fn new(size: u8) -> Self {
+    pub fn new(size: usize) -> Self {
         let mut ands = Vec::<Vec<And>>::new();
         for _ in 0..size {
             let mut ands_row = Vec::<And>::new();
@@ -41,21 +44,26 @@ impl BitwiseMult {
     }
 
     pub fn evaluate(&mut self, d1: &Bits, d2: &Bits) -> Bits {
-        self.check_input(d1);
-        self.check_input(d2);
+        self.try_evaluate(d1, d2)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_evaluate(&mut self, d1: &Bits, d2: &Bits) -> Result<Bits, Error> {
+        self.try_check_input("d1", d1)?;
+        self.try_check_input("d2", d2)?;
 
         // let mut output = Vec::<bool>::new();
         let mut output: Bits = Bits::from_vector_b(Vec::new(), None);
         for (i, (bit2, ands_row)) in zip(d2.iter().rev(), self.ands.iter_mut()).enumerate() {
             let mut and_result = (0..d2.len() - i).map(|_| false).collect::<Vec<bool>>();
             for (bit1, and) in zip(d1.iter(), ands_row) {
-                let and_bit = and.evaluate(*bit1, *bit2);
+                let and_bit = and.evaluate(bit1, bit2);
                 and_result.push(and_bit);
             }
 
             and_result.resize(d1.len() + d2.len(), false);
 
-            if output.len() == 0 {
+            if output.is_empty() {
                 output = Bits::from_vector_b(and_result, None);
             } else {
                 let (aggregated_result, _) = self.bitwise_adds[i - 1].evaluate(
@@ -67,7 +75,147 @@ impl BitwiseMult {
             }
         }
 
-        output
+        Ok(output)
+    }
+
+    /// Four-valued evaluation on buses given MSB first, returns the product bus.
+    pub fn try_evaluate_logic(&mut self, d1: &[Logic], d2: &[Logic]) -> Result<LBits, Error> {
+        Error::check_width("d1", self.size(), d1.len())?;
+        Error::check_width("d2", self.size(), d2.len())?;
+
+        let mut output: Option<LBits> = None;
+        for (i, (bit2, ands_row)) in zip(d2.iter().rev(), self.ands.iter_mut()).enumerate() {
+            let mut and_result = vec![Logic::Zero; d2.len() - i];
+            for (bit1, and) in zip(d1.iter(), ands_row) {
+                and_result.push(and.evaluate_logic(*bit1, *bit2));
+            }
+            and_result.resize(d1.len() + d2.len(), Logic::Zero);
+
+            output = Some(match output {
+                None => LBits::from_vector(and_result),
+                Some(output) => {
+                    let output = output.iter().copied().collect::<Vec<Logic>>();
+                    let (aggregated_result, _) = self.bitwise_adds[i - 1].try_evaluate_logic(
+                        &and_result,
+                        &output,
+                        Logic::Zero,
+                    )?;
+                    aggregated_result
+                }
+            });
+        }
+
+        Ok(output.unwrap_or_default())
+    }
+}
+
+impl Component for BitwiseMult {
+    fn type_name(&self) -> &'static str {
+        "BitwiseMult"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        let mut inputs = bus_names("d1", self.size());
+        inputs.extend(bus_names("d2", self.size()));
+        inputs
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        bus_names("product", self.size() * 2)
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let (d1, d2) = inputs.split_at(self.size());
+        self.try_evaluate(&d1, &d2)
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let inputs = inputs.iter().copied().collect::<Vec<Logic>>();
+        let (d1, d2) = inputs.split_at(self.size());
+        self.try_evaluate_logic(d1, d2)
+    }
+
+    fn logic_depth(&self) -> usize {
+        // Follow the bits through the adds, where each partial product enters one row later.
+        let size = self.size();
+        let and = self.ands[0][0].logic_depth();
+        let mut output: Option<Vec<usize>> = None;
+        for i in 0..size {
+            let mut and_result = vec![0; size - i];
+            and_result.extend(vec![and; size]);
+            and_result.resize(2 * size, 0);
+            output = Some(match output {
+                None => and_result,
+                Some(output) => self.bitwise_adds[i - 1].arrivals(&and_result, &output, 0).0,
+            });
+        }
+        output.unwrap_or_default().into_iter().max().unwrap_or(0)
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> = Vec::new();
+        for (i, row) in self.ands.iter().enumerate() {
+            for (j, component) in row.iter().enumerate() {
+                sub_components.push((format!("ands[{}][{}]", i, j), component));
+            }
+        }
+        for (i, component) in self.bitwise_adds.iter().enumerate() {
+            sub_components.push((indexed("bitwise_adds", i), component));
+        }
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> = Vec::new();
+        for (i, row) in self.ands.iter_mut().enumerate() {
+            for (j, component) in row.iter_mut().enumerate() {
+                sub_components.push((format!("ands[{}][{}]", i, j), component));
+            }
+        }
+        for (i, component) in self.bitwise_adds.iter_mut().enumerate() {
+            sub_components.push((indexed("bitwise_adds", i), component));
+        }
+        sub_components
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        let size = self.size();
+        let mut wires = Vec::new();
+        for i in 0..size {
+            for j in 0..size {
+                let and = format!("ands[{}][{}]", i, j);
+                wires.push((port(&indexed("d1", size - 1 - j)), pin(&and, "a")));
+                wires.push((port(&indexed("d2", i)), pin(&and, "b")));
+            }
+        }
+        // Bit `bit` of the partial product of row `i`, d1 shifted by `i` and zero elsewhere.
+        let partial = |i: usize, bit: usize| {
+            if (i..i + size).contains(&bit) {
+                pin(&format!("ands[{}][{}]", i, size - 1 - (bit - i)), "y")
+            } else {
+                Pin::Constant(false)
+            }
+        };
+        let mut product = (0..2 * size)
+            .map(|bit| partial(0, bit))
+            .collect::<Vec<Pin>>();
+        for i in 1..size {
+            let bitwise_add = indexed("bitwise_adds", i - 1);
+            for (bit, sum) in product.iter().enumerate() {
+                wires.push((partial(i, bit), pin(&bitwise_add, &indexed("d1", bit))));
+                wires.push((sum.clone(), pin(&bitwise_add, &indexed("d2", bit))));
+            }
+            wires.push((Pin::Constant(false), pin(&bitwise_add, "carry")));
+            product = (0..2 * size)
+                .map(|bit| pin(&bitwise_add, &indexed("sum", bit)))
+                .collect();
+        }
+        for (bit, driver) in product.into_iter().enumerate() {
+            wires.push((driver, port(&indexed("product", bit))));
+        }
+        wires
     }
 }
 
@@ -87,4 +235,14 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn bitwise_mult_evaluate_64() {
+        let mut bitwise_mult = BitwiseMult::new(64);
+        for (d1, d2) in [(u64::MAX, u64::MAX), (0xDEAD_BEEF_CAFE, 0x1234_5678)] {
+            let result = bitwise_mult.evaluate(&Bits::from(d1), &Bits::from(d2));
+            assert!(result.len() == 128);
+            assert_eq!(u128::try_from(&result), Ok(d1 as u128 * d2 as u128));
+        }
+    }
 }
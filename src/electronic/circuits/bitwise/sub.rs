@@ -1,10 +1,13 @@
 use std::iter::zip;
 
 use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
 use crate::electronic::circuits::bitwise::Bitwise;
 use crate::electronic::circuits::subtractor::FullSubtractor;
 
 use super::BitwiseCheck;
+use crate::electronic::circuits::component::{bus_names, indexed, pin, port, Component, Pin};
+use crate::error::Error;
 
 pub struct BitwiseSub {
     bitwise: Bitwise,
@@ -12,13 +15,13 @@ pub struct BitwiseSub {
 }
 
 impl BitwiseCheck for BitwiseSub {
-    fn size(&self) -> u8 {
+    fn size(&self) -> usize {
         self.bitwise.size
     }
 }
 
 impl BitwiseSub {
-    pub fn new(size: u8) -> Self {
+    pub fn new(size: usize) -> Self {
         let subtractors = (0..size)
             .map(|_| FullSubtractor::new())
             .collect::<Vec<FullSubtractor>>();
@@ -29,8 +32,18 @@ impl BitwiseSub {
     }
 
     pub fn evaluate(&mut self, d1: &Bits, d2: &Bits, borrow: bool) -> (Bits, bool) {
-        self.check_input(d1);
-        self.check_input(d2);
+        self.try_evaluate(d1, d2, borrow)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_evaluate(
+        &mut self,
+        d1: &Bits,
+        d2: &Bits,
+        borrow: bool,
+    ) -> Result<(Bits, bool), Error> {
+        self.try_check_input("d1", d1)?;
+        self.try_check_input("d2", d2)?;
 
         let mut borrow_in = borrow;
         let mut output = Vec::<bool>::new();
@@ -39,14 +52,138 @@ impl BitwiseSub {
             self.subtractors.iter_mut(),
             zip(d1.iter().rev(), d2.iter().rev()),
         ) {
-            let subtractor_result = subtractor.evaluate(*bit1, *bit2, borrow_in);
+            let subtractor_result = subtractor.evaluate(bit1, bit2, borrow_in);
             borrow_in = subtractor_result.borrow_out;
             output.push(subtractor_result.difference);
             borrow_out = subtractor_result.borrow_out
         }
         output.reverse();
 
-        (Bits::from_vector_b(output, None), borrow_out)
+        Ok((Bits::from_vector_b(output, None), borrow_out))
+    }
+
+    /// Four-valued evaluation on buses given MSB first, returns the difference bus and the
+    /// borrow out.
+    pub fn try_evaluate_logic(
+        &mut self,
+        d1: &[Logic],
+        d2: &[Logic],
+        borrow: Logic,
+    ) -> Result<(LBits, Logic), Error> {
+        Error::check_width("d1", self.size(), d1.len())?;
+        Error::check_width("d2", self.size(), d2.len())?;
+
+        let mut borrow_in = borrow;
+        let mut output = Vec::<Logic>::new();
+        for (subtractor, (bit1, bit2)) in zip(
+            self.subtractors.iter_mut(),
+            zip(d1.iter().rev(), d2.iter().rev()),
+        ) {
+            let (difference, borrow_out) = subtractor.evaluate_logic(*bit1, *bit2, borrow_in);
+            borrow_in = borrow_out;
+            output.push(difference);
+        }
+        output.reverse();
+
+        Ok((LBits::from_vector(output), borrow_in))
+    }
+
+    /// Gate levels each difference bit (MSB first) and the borrow out settle at, for input
+    /// bits settled after the given levels.
+    pub(crate) fn arrivals(
+        &self,
+        d1: &[usize],
+        d2: &[usize],
+        borrow: usize,
+    ) -> (Vec<usize>, usize) {
+        let mut borrow_in = borrow;
+        let mut output = Vec::<usize>::new();
+        for (subtractor, (bit1, bit2)) in
+            zip(&self.subtractors, zip(d1.iter().rev(), d2.iter().rev()))
+        {
+            let (difference, borrow_out) = subtractor.arrivals(*bit1.max(bit2), borrow_in);
+            borrow_in = borrow_out;
+            output.push(difference);
+        }
+        output.reverse();
+        (output, borrow_in)
+    }
+}
+
+impl Component for BitwiseSub {
+    fn type_name(&self) -> &'static str {
+        "BitwiseSub"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        let mut inputs = bus_names("d1", self.size());
+        inputs.extend(bus_names("d2", self.size()));
+        inputs.push("borrow".to_string());
+        inputs
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        let mut outputs = bus_names("difference", self.size());
+        outputs.push("borrow_out".to_string());
+        outputs
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let (d1, rest) = inputs.split_at(self.size());
+        let (d2, borrow) = rest.split_at(self.size());
+        let (difference, borrow_out) = self.try_evaluate(&d1, &d2, borrow[0])?;
+        Ok(difference.concat(&Bits::from_slice_b(&[borrow_out], None)))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let inputs = inputs.iter().copied().collect::<Vec<Logic>>();
+        let (d1, rest) = inputs.split_at(self.size());
+        let (d2, borrow) = rest.split_at(self.size());
+        let (difference, borrow_out) = self.try_evaluate_logic(d1, d2, borrow[0])?;
+        Ok(difference.iter().copied().chain([borrow_out]).collect())
+    }
+
+    fn logic_depth(&self) -> usize {
+        let inputs = vec![0; self.size()];
+        let (difference, borrow_out) = self.arrivals(&inputs, &inputs, 0);
+        difference.into_iter().fold(borrow_out, usize::max)
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> = Vec::new();
+        for (i, component) in self.subtractors.iter().enumerate() {
+            sub_components.push((indexed("subtractors", i), component));
+        }
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> = Vec::new();
+        for (i, component) in self.subtractors.iter_mut().enumerate() {
+            sub_components.push((indexed("subtractors", i), component));
+        }
+        sub_components
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        // `subtractors[j]` handles bit j, from the LSB.
+        let mut wires = Vec::new();
+        let mut borrow = port("borrow");
+        for j in 0..self.size() {
+            let subtractor = indexed("subtractors", j);
+            wires.push((port(&indexed("d1", j)), pin(&subtractor, "a")));
+            wires.push((port(&indexed("d2", j)), pin(&subtractor, "b")));
+            wires.push((borrow, pin(&subtractor, "borrow_in")));
+            wires.push((
+                pin(&subtractor, "difference"),
+                port(&indexed("difference", j)),
+            ));
+            borrow = pin(&subtractor, "borrow_out");
+        }
+        wires.push((borrow, port("borrow_out")));
+        wires
     }
 }
 
@@ -64,19 +201,28 @@ mod tests {
                     let data2 = Bits::from_int(d2, Some(4));
                     let (result, borrow_out) = bitwise_sub.evaluate(&data1, &data2, borrow);
 
-                    let borrow_i32 = borrow as i32;
-                    let d1_i32 = d1 as i32;
-                    let d2_i32 = d2 as i32;
-                    if d1_i32 < d2_i32 + borrow_i32 {
-                        assert_eq!(
-                            result.to_int(),
-                            (16 + (d1_i32 - d2_i32 - borrow_i32)) as u32
-                        );
-                        assert!(borrow_out)
-                    } else {
-                        assert_eq!(result.to_int(), (d1_i32 - d2_i32 - borrow_i32) as u32);
-                        assert!(!borrow_out)
-                    }
+                    let expected = d1 as i64 - d2 as i64 - borrow as i64;
+                    assert_eq!(result.to_i64(), expected);
+                    assert_eq!(borrow_out, expected < 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bitwise_sub_evaluate_signed() {
+        let mut bitwise_sub = BitwiseSub::new(4);
+        for d1 in -8..8 {
+            for d2 in -8..8 {
+                let data1 = Bits::from_i64(d1, Some(4));
+                let data2 = Bits::from_i64(d2, Some(4));
+                let (result, _) = bitwise_sub.evaluate(&data1, &data2, false);
+
+                let expected = d1 - d2;
+                let overflow = !(-8..8).contains(&expected);
+                assert_eq!(Bits::sub_overflowed(&data1, &data2, &result), overflow);
+                if !overflow {
+                    assert_eq!(result.to_i64(), expected);
                 }
             }
         }
@@ -0,0 +1,490 @@
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::fault::Fault;
+use crate::electronic::timing::{instant, Waveform};
+use crate::error::Error;
+
+/// Interface shared by every circuit and transistor, so harnesses and analysers can work on
+/// any of them.
+///
+/// Ports are listed in order; `evaluate_bits` takes all inputs packed into one [`Bits`] in that
+/// order (the first input is the MSB) and returns the outputs packed the same way. Buses
+/// appear as one port per bit, MSB first, e.g. `d1[3]`, `d1[2]`, `d1[1]`, `d1[0]`.
+pub trait Component {
+    fn type_name(&self) -> &'static str;
+
+    fn input_names(&self) -> Vec<String>;
+
+    fn output_names(&self) -> Vec<String>;
+
+    fn input_count(&self) -> usize {
+        self.input_names().len()
+    }
+
+    fn output_count(&self) -> usize {
+        self.output_names().len()
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error>;
+
+    fn evaluate_bits(&mut self, inputs: &Bits) -> Bits {
+        self.try_evaluate_bits(inputs)
+            .unwrap_or_else(|error| panic!("{}: {}", self.type_name(), error))
+    }
+
+    /// Evaluation on four-valued logic, `Z` and `X` inputs make the outputs they reach `X`.
+    /// Components without a logic model evaluate every completion of the unknown inputs.
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        completions(self, inputs)
+    }
+
+    /// Timed evaluation: one waveform per input port, in order, gives one per output port.
+    /// Components without a timing model switch instantly.
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        instant(self, inputs)
+    }
+
+    /// Toggles counted by this component itself, not by its children: control signal changes
+    /// for a transistor, output changes for a CMOS gate.
+    fn own_toggles(&self) -> u64 {
+        0
+    }
+
+    fn reset_own_toggles(&mut self) {}
+
+    /// Faults that can be injected on this component itself, see
+    /// [`inject`](crate::electronic::fault::inject).
+    fn supported_faults(&self) -> &'static [Fault] {
+        &[]
+    }
+
+    fn fault(&self) -> Option<Fault> {
+        None
+    }
+
+    /// Sets or clears the fault of this component, one of its `supported_faults`.
+    fn set_fault(&mut self, _fault: Option<Fault>) {}
+
+    /// Transistors modelled by this component itself, not by its children.
+    fn own_transistors(&self) -> usize {
+        0
+    }
+
+    /// Gates on the longest path from an input to an output. Sub-components count as blocks:
+    /// every output of a child is taken to depend on all its inputs through its full depth.
+    ///
+    /// The default suits children working side by side. A cell built directly from
+    /// transistors is one level, a transistor none.
+    fn logic_depth(&self) -> usize {
+        let sub_components = self.sub_components();
+        let depth = sub_components
+            .iter()
+            .map(|(_, sub_component)| sub_component.logic_depth())
+            .max()
+            .unwrap_or(0);
+        if depth == 0 && !sub_components.is_empty() {
+            1
+        } else {
+            depth
+        }
+    }
+
+    /// Direct children, named after the struct fields (`xor0`, `adders[3]`).
+    fn sub_components(&self) -> Vec<(String, &dyn Component)>;
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)>;
+
+    /// Wires between the ports of this component and those of its children, as (driver, load)
+    /// pairs with one pair per load. A load with several drivers resolves them like a bus.
+    ///
+    /// Empty for a leaf: transistors, and cells built directly from transistors, which the
+    /// exporters keep as gates.
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        Vec::new()
+    }
+
+    /// Logic function of the crate's own gates, which the mapping rewrites with gate
+    /// identities. The type cannot be named outside the crate, so no other component can
+    /// claim one.
+    #[doc(hidden)]
+    fn gate(&self) -> Option<sealed::Gate> {
+        None
+    }
+
+    fn check_inputs(&self, inputs: &Bits) -> Result<(), Error> {
+        Error::check_width("inputs", self.input_count(), inputs.len())
+    }
+
+    fn check_waveforms(&self, inputs: &[Waveform]) -> Result<(), Error> {
+        Error::check_width("inputs", self.input_count(), inputs.len())
+    }
+
+    fn check_logic_inputs(&self, inputs: &LBits) -> Result<(), Error> {
+        Error::check_width("inputs", self.input_count(), inputs.len())
+    }
+}
+
+pub(crate) mod sealed {
+    /// Logic function of a gate of this crate, see [`Component::gate`](super::Component::gate).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Gate {
+        Not,
+        And,
+        Nand,
+        Or,
+        Nor,
+        Xor,
+        Xnor,
+    }
+}
+
+/// End of a wire inside a component, see [`Component::wires`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Pin {
+    /// Port of the component itself: an input drives its wires, an output is driven.
+    Port(String),
+    /// Port of the direct child with that name in `sub_components`.
+    Child(String, String),
+    /// Tied low or high.
+    Constant(bool),
+}
+
+/// Pin of the component's own port `name`.
+pub fn port(name: &str) -> Pin {
+    Pin::Port(name.to_string())
+}
+
+/// Pin of the port `name` of the child `child`.
+pub fn pin(child: &str, name: &str) -> Pin {
+    Pin::Child(child.to_string(), name.to_string())
+}
+
+/// Unknown inputs the default `try_evaluate_logic_bits` enumerates, all outputs being `X`
+/// beyond.
+pub const MAX_COMPLETION_UNKNOWNS: usize = 12;
+
+/// Four-valued evaluation of any component through `try_evaluate_bits`: outputs on which the
+/// completions of the unknown inputs disagree are `X`. With more than
+/// [`MAX_COMPLETION_UNKNOWNS`] unknown inputs every output is taken as `X` rather than
+/// evaluated `2^n` times.
+pub(crate) fn completions<C: Component + ?Sized>(
+    component: &mut C,
+    inputs: &LBits,
+) -> Result<LBits, Error> {
+    component.check_logic_inputs(inputs)?;
+    let unknowns = (0..inputs.len())
+        .filter(|&i| !inputs[i].is_known())
+        .collect::<Vec<usize>>();
+    if unknowns.len() > MAX_COMPLETION_UNKNOWNS {
+        return Ok(vec![Logic::X; component.output_count()]
+            .into_iter()
+            .collect());
+    }
+
+    let mut outputs: Option<LBits> = None;
+    for completion in 0..1_u64 << unknowns.len() {
+        let mut values = inputs
+            .iter()
+            .map(|value| value.to_bool().unwrap_or(false))
+            .collect::<Vec<bool>>();
+        for (bit, &i) in unknowns.iter().enumerate() {
+            values[i] = completion >> bit & 1 == 1;
+        }
+        let result = LBits::from(component.try_evaluate_bits(&Bits::from_slice_b(&values, None))?);
+        outputs = Some(match outputs {
+            None => result,
+            Some(outputs) => outputs
+                .iter()
+                .zip(result.iter())
+                .map(|(&a, &b)| if a == b { a } else { Logic::X })
+                .collect(),
+        });
+    }
+    Ok(outputs.expect("there is always one completion"))
+}
+
+/// Port names of a bus, MSB first: `bus_names("d", 2)` is `["d[1]", "d[0]"]`.
+pub fn bus_names(name: &str, size: usize) -> Vec<String> {
+    (0..size)
+        .rev()
+        .map(|i| format!("{}[{}]", name, i))
+        .collect()
+}
+
+pub fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+/// Name of the `index`th element of a vector field, e.g. `adders[3]`.
+pub fn indexed(name: &str, index: usize) -> String {
+    format!("{}[{}]", name, index)
+}
+
+/// Visits `component` and all its descendants depth first, with their dot-separated path
+/// relative to `component` (empty for `component` itself).
+pub fn walk(component: &dyn Component, f: &mut dyn FnMut(&str, &dyn Component)) {
+    walk_from("", component, f);
+}
+
+fn walk_from(path: &str, component: &dyn Component, f: &mut dyn FnMut(&str, &dyn Component)) {
+    f(path, component);
+    for (name, sub_component) in component.sub_components() {
+        walk_from(&join_path(path, &name), sub_component, f);
+    }
+}
+
+pub fn join_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::electronic::circuits::adder::{FullAdder, HalfAdder};
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::bitwise::div::BitwiseDiv;
+    use crate::electronic::circuits::bitwise::mult::BitwiseMult;
+    use crate::electronic::circuits::bitwise::sub::BitwiseSub;
+    use crate::electronic::circuits::decoder::{Decoder, Decoder2To4};
+    use crate::electronic::circuits::demux::Demux1To2;
+    use crate::electronic::circuits::logic_gates::and3::And3;
+    use crate::electronic::circuits::logic_gates::gate_n::{AndN, XnorN};
+    use crate::electronic::circuits::logic_gates::nand::Nand;
+    use crate::electronic::circuits::mux::Mux2To1;
+    use crate::electronic::circuits::shifter::BarrelShifter;
+    use crate::electronic::circuits::subtractor::{
+        FullSubtractor, FullSubtractorRestore, HalfSubtractor,
+    };
+
+    /// Generic exhaustive harness: every input combination through `evaluate_bits`.
+    fn truth_table(component: &mut dyn Component) -> Vec<Bits> {
+        let inputs = component.input_count();
+        (0..1_u128 << inputs)
+            .map(|value| {
+                let inputs = Bits::try_from_u128(value, Some(inputs)).unwrap();
+                component.evaluate_bits(&inputs)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn component_ports() {
+        let nand = Nand::new();
+        assert_eq!(nand.input_names(), vec!["a", "b"]);
+        assert_eq!(nand.output_count(), 1);
+
+        let bitwise_add = BitwiseAdd::new(2);
+        assert_eq!(
+            bitwise_add.input_names(),
+            vec!["d1[1]", "d1[0]", "d2[1]", "d2[0]", "carry"]
+        );
+        assert_eq!(
+            bitwise_add.output_names(),
+            vec!["sum[1]", "sum[0]", "carry_out"]
+        );
+    }
+
+    #[test]
+    fn component_generic_truth_table() {
+        let table = truth_table(&mut Mux2To1::new());
+        for (value, output) in table.iter().enumerate() {
+            let (a, b, s) = (value & 0b100 != 0, value & 0b010 != 0, value & 0b001 != 0);
+            assert!(output[0] == if s { b } else { a });
+        }
+
+        let table = truth_table(&mut FullAdder::new());
+        for (value, output) in table.iter().enumerate() {
+            let (sum, carry_out) = (output[0] as u32, output[1] as u32);
+            assert_eq!(carry_out * 2 + sum, value.count_ones());
+        }
+
+        let table = truth_table(&mut BitwiseAdd::new(2));
+        for (value, output) in table.iter().enumerate() {
+            let (d1, d2, carry) = (value >> 3, (value >> 1) & 0b11, value & 1);
+            let (sum, carry_out) = (output.slice(..2).to_int() as usize, output[2] as usize);
+            assert_eq!(carry_out * 4 + sum, d1 + d2 + carry);
+        }
+    }
+
+    /// Evaluates `component` through its wires only, its children in turn until no pin
+    /// changes.
+    fn evaluate_wires(component: &mut dyn Component, inputs: &LBits) -> LBits {
+        let wires = component.wires();
+        if wires.is_empty() {
+            return component.try_evaluate_logic_bits(inputs).unwrap();
+        }
+        let mut values = HashMap::new();
+        for (name, &value) in component.input_names().iter().zip(inputs.iter()) {
+            values.insert(port(name), value);
+        }
+        let value_of = |values: &HashMap<Pin, Logic>, load: &Pin| {
+            wires.iter().filter(|(_, to)| to == load).fold(
+                Logic::Z,
+                |value, (from, _)| match from {
+                    Pin::Constant(level) => value.resolve(Logic::from(*level)),
+                    from => value.resolve(values.get(from).copied().unwrap_or(Logic::X)),
+                },
+            )
+        };
+
+        let mut children = component.sub_components_mut();
+        for _ in 0..=children.len() {
+            let mut changed = false;
+            for (name, child) in children.iter_mut() {
+                let inputs = child
+                    .input_names()
+                    .iter()
+                    .map(|input| value_of(&values, &pin(name, input)))
+                    .collect::<LBits>();
+                let outputs = evaluate_wires(&mut **child, &inputs);
+                for (output, &value) in child.output_names().iter().zip(outputs.iter()) {
+                    changed |= values.insert(pin(name, output), value) != Some(value);
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        drop(children);
+        component
+            .output_names()
+            .iter()
+            .map(|output| value_of(&values, &port(output)))
+            .collect()
+    }
+
+    #[test]
+    fn component_wires() {
+        let mut components: Vec<Box<dyn Component>> = vec![
+            Box::new(HalfAdder::new()),
+            Box::new(FullAdder::new()),
+            Box::new(Mux2To1::new()),
+            Box::new(Demux1To2::new()),
+            Box::new(Decoder2To4::new()),
+            Box::new(Decoder::new(2)),
+            Box::new(Decoder::new(3)),
+            Box::new(HalfSubtractor::new()),
+            Box::new(FullSubtractor::new()),
+            Box::new(FullSubtractorRestore::new()),
+            Box::new(And3::new()),
+            Box::new(AndN::new(5)),
+            Box::new(XnorN::new(6)),
+            Box::new(BitwiseAdd::new(3)),
+            Box::new(BitwiseSub::new(3)),
+            Box::new(BitwiseMult::new(2)),
+            Box::new(BitwiseDiv::new(2)),
+            Box::new(BarrelShifter::new(2, true)),
+            Box::new(BarrelShifter::new(2, false)),
+        ];
+        for component in components.iter_mut() {
+            for value in 0..1_u128 << component.input_count() {
+                let inputs = Bits::try_from_u128(value, Some(component.input_count())).unwrap();
+                // Division by zero has no result to compare with.
+                if let Ok(outputs) = component.try_evaluate_bits(&inputs) {
+                    let wired = evaluate_wires(component.as_mut(), &LBits::from(inputs));
+                    assert_eq!(wired, LBits::from(outputs), "{}", component.type_name());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn component_decoder() {
+        let mut decoder = Decoder::new(3);
+        assert_eq!(decoder.input_count(), 4);
+        assert_eq!(decoder.output_count(), 8);
+        let outputs = decoder.evaluate_bits(&Bits::from_int(0b1011, Some(4)));
+        assert!(outputs == Bits::from_int(1 << 5, Some(8)));
+    }
+
+    #[test]
+    fn component_evaluate_logic_bits() {
+        // The unselected input does not matter.
+        let mut mux = Mux2To1::new();
+        let outputs = mux.try_evaluate_logic_bits(&"1x0".parse().unwrap());
+        assert_eq!(outputs.unwrap().to_string(), "1'b1");
+
+        let mut subtractor = FullSubtractor::new();
+        let outputs = subtractor.try_evaluate_logic_bits(&"1x0".parse().unwrap());
+        assert_eq!(outputs.unwrap().to_string(), "2'bx0");
+
+        let mut bitwise_add = BitwiseAdd::new(2);
+        let outputs = bitwise_add.try_evaluate_logic_bits(&"0x010".parse().unwrap());
+        assert_eq!(outputs.unwrap().to_string(), "3'bxx0");
+
+        // The arithmetic units agree with their boolean model on known inputs.
+        let mut components: Vec<Box<dyn Component>> = vec![
+            Box::new(BitwiseSub::new(2)),
+            Box::new(BitwiseMult::new(2)),
+            Box::new(BitwiseDiv::new(2)),
+            Box::new(BarrelShifter::new(2, true)),
+            Box::new(BarrelShifter::new(2, false)),
+        ];
+        for component in components.iter_mut() {
+            let size = component.input_count();
+            for value in 0..1 << size {
+                let inputs = Bits::from_int(value, Some(size));
+                let expected = component.try_evaluate_bits(&inputs).map(LBits::from);
+                let outputs = component.try_evaluate_logic_bits(&LBits::from(inputs));
+                assert_eq!(outputs, expected, "{}", component.type_name());
+            }
+        }
+        // 3 / 1 is 3 remainder 0, the gates cannot tell the remainder from that of 3 / 0.
+        let mut bitwise_div = BitwiseDiv::new(2);
+        let outputs = bitwise_div.try_evaluate_logic_bits(&"110x".parse().unwrap());
+        assert_eq!(outputs.unwrap().to_string(), "4'b11xx");
+        let outputs = bitwise_div.try_evaluate_logic_bits(&"1x00".parse().unwrap());
+        assert_eq!(outputs, Err(Error::DivisionByZero));
+
+        // Wide buses evaluate gate by gate instead of enumerating completions.
+        let mut bitwise_sub = BitwiseSub::new(32);
+        let outputs = bitwise_sub.try_evaluate_logic_bits(&LBits::filled(Logic::X, 65));
+        assert_eq!(outputs, Ok(LBits::filled(Logic::X, 33)));
+        let outputs = completions(&mut BitwiseAdd::new(8), &LBits::filled(Logic::X, 17));
+        assert_eq!(outputs, Ok(LBits::filled(Logic::X, 9)));
+    }
+
+    #[test]
+    fn component_walk() {
+        let full_adder = FullAdder::new();
+        let mut paths = Vec::new();
+        walk(&full_adder, &mut |path, _| paths.push(path.to_string()));
+        assert!(paths.contains(&"xor0".to_string()));
+        assert!(paths.contains(&"xor0.nmos_a".to_string()));
+        assert!(paths.contains(&"and1.nand.pmos_b".to_string()));
+
+        let mut transistors = 0;
+        walk(&full_adder, &mut |_, component| {
+            if component.sub_components().is_empty() {
+                transistors += 1;
+            }
+        });
+        assert_eq!(transistors, 2 * 8 + 2 * 6 + 6);
+    }
+
+    #[test]
+    fn component_try_evaluate_bits() {
+        let mut nand = Nand::new();
+        assert_eq!(
+            nand.try_evaluate_bits(&Bits::from_int(1, Some(3))),
+            Err(Error::WidthMismatch {
+                input: "inputs",
+                expected: 2,
+                actual: 3
+            })
+        );
+        assert!(nand.try_evaluate_bits(&Bits::from_int(3, Some(2))) == Ok(Bits::zeros(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn component_wrong_input_count() {
+        Nand::new().evaluate_bits(&Bits::from_int(1, Some(3)));
+    }
+}
@@ -1,7 +1,13 @@
 use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{
+    bus_names, indexed, names, pin, port, Component, Pin,
+};
 use crate::electronic::circuits::logic_gates::and::And;
 use crate::electronic::circuits::logic_gates::and3::And3;
 use crate::electronic::circuits::logic_gates::not::Not;
+use crate::electronic::timing::Waveform;
+use crate::error::Error;
 
 pub struct Decoder2To4 {
     not0: Not,
@@ -32,6 +38,52 @@ impl Decoder2To4 {
         outputs.reverse();
         outputs
     }
+
+    pub fn evaluate_logic(
+        &mut self,
+        signal_a: Logic,
+        signal_b: Logic,
+        enable: Logic,
+    ) -> Vec<Logic> {
+        let _not0_result = self.not0.evaluate_logic(signal_a);
+        let _not1_result = self.not1.evaluate_logic(signal_b);
+
+        let mut outputs = vec![
+            self.ands3[0].evaluate_logic(_not1_result, _not0_result, enable),
+            self.ands3[1].evaluate_logic(_not1_result, signal_a, enable),
+            self.ands3[2].evaluate_logic(signal_b, _not0_result, enable),
+            self.ands3[3].evaluate_logic(signal_b, signal_a, enable),
+        ];
+
+        outputs.reverse();
+        outputs
+    }
+
+    pub fn evaluate_timed(
+        &mut self,
+        signal_a: &Waveform,
+        signal_b: &Waveform,
+        enable: &Waveform,
+    ) -> Vec<Waveform> {
+        let _not0_result = self.not0.evaluate_timed(signal_a);
+        let _not1_result = self.not1.evaluate_timed(signal_b);
+
+        let mut outputs = vec![
+            self.ands3[0].evaluate_timed(&_not1_result, &_not0_result, enable),
+            self.ands3[1].evaluate_timed(&_not1_result, signal_a, enable),
+            self.ands3[2].evaluate_timed(signal_b, &_not0_result, enable),
+            self.ands3[3].evaluate_timed(signal_b, signal_a, enable),
+        ];
+
+        outputs.reverse();
+        outputs
+    }
+}
+
+impl Default for Decoder2To4 {
+    fn default() -> Self {
+        Decoder2To4::new()
+    }
 }
 
 pub struct Decoder {
@@ -55,7 +107,7 @@ impl Decoder {
                 decoder2to4: Some(Decoder2To4::new()),
             }
         } else {
-            let bits_size = 2_u8.pow(depth as u32);
+            let bits_size = 2_usize.pow(depth as u32);
             let ands = (0..bits_size).map(|_n| And::new()).collect::<Vec<And>>();
 
             let lower_half_decoder = Box::new(Decoder::new(depth - 1));
@@ -73,37 +125,328 @@ impl Decoder {
     }
 
     pub fn evaluate(&mut self, _inputs: Bits, _enable: bool) -> Vec<bool> {
-        if _inputs.len() != self.depth as usize {
-            panic!(
-                "Expected {} input signals, got {}",
-                self.depth,
-                _inputs.len()
-            )
-        }
+        self.try_evaluate(_inputs, _enable)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_evaluate(&mut self, _inputs: Bits, _enable: bool) -> Result<Vec<bool>, Error> {
+        Error::check_width("inputs", self.depth as usize, _inputs.len())?;
 
         if self.depth == 2 {
             if let Some(decoder) = self.decoder2to4.as_mut() {
-                return decoder.evaluate(&_inputs[1], &_inputs[0], _enable);
+                return Ok(decoder.evaluate(&_inputs[1], &_inputs[0], _enable));
             } else {
                 panic!("Decoder2To4 is not initialized.");
             }
         }
 
         let _not_result = self.not.evaluate(_inputs[0]);
-        let _lower_result = self.lower_half_decoder.as_mut().unwrap().evaluate(
-            Bits::from_slice_b(&_inputs[1.._inputs.len()], None),
-            _not_result,
-        );
-        let _upper_result = self.upper_half_decoder.as_mut().unwrap().evaluate(
-            Bits::from_slice_b(&_inputs[1.._inputs.len()], None),
-            _inputs[0],
-        );
+        let _lower_result = self
+            .lower_half_decoder
+            .as_mut()
+            .unwrap()
+            .try_evaluate(_inputs.slice(1..), _not_result)?;
+        let _upper_result = self
+            .upper_half_decoder
+            .as_mut()
+            .unwrap()
+            .try_evaluate(_inputs.slice(1..), _inputs[0])?;
         let combined_upper_lower = [_upper_result, _lower_result].concat();
-        return combined_upper_lower
+        Ok(combined_upper_lower
             .iter()
             .enumerate()
             .map(|(i, bit)| self.ands.as_mut().unwrap()[i].evaluate(*bit, _enable))
-            .collect::<Vec<bool>>();
+            .collect::<Vec<bool>>())
+    }
+
+    /// Four-valued evaluation, `_inputs` MSB first.
+    pub fn try_evaluate_logic(
+        &mut self,
+        _inputs: &[Logic],
+        _enable: Logic,
+    ) -> Result<Vec<Logic>, Error> {
+        Error::check_width("inputs", self.depth as usize, _inputs.len())?;
+
+        if self.depth == 2 {
+            if let Some(decoder) = self.decoder2to4.as_mut() {
+                return Ok(decoder.evaluate_logic(_inputs[1], _inputs[0], _enable));
+            } else {
+                panic!("Decoder2To4 is not initialized.");
+            }
+        }
+
+        let _not_result = self.not.evaluate_logic(_inputs[0]);
+        let _lower_result = self
+            .lower_half_decoder
+            .as_mut()
+            .unwrap()
+            .try_evaluate_logic(&_inputs[1..], _not_result)?;
+        let _upper_result = self
+            .upper_half_decoder
+            .as_mut()
+            .unwrap()
+            .try_evaluate_logic(&_inputs[1..], _inputs[0])?;
+        let combined_upper_lower = [_upper_result, _lower_result].concat();
+        Ok(combined_upper_lower
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| self.ands.as_mut().unwrap()[i].evaluate_logic(*bit, _enable))
+            .collect::<Vec<Logic>>())
+    }
+
+    /// Timed evaluation, `_inputs` MSB first.
+    pub fn evaluate_timed(
+        &mut self,
+        _inputs: &[Waveform],
+        _enable: &Waveform,
+    ) -> Result<Vec<Waveform>, Error> {
+        Error::check_width("inputs", self.depth as usize, _inputs.len())?;
+
+        if self.depth == 2 {
+            if let Some(decoder) = self.decoder2to4.as_mut() {
+                return Ok(decoder.evaluate_timed(&_inputs[1], &_inputs[0], _enable));
+            } else {
+                panic!("Decoder2To4 is not initialized.");
+            }
+        }
+
+        let _not_result = self.not.evaluate_timed(&_inputs[0]);
+        let _lower_result = self
+            .lower_half_decoder
+            .as_mut()
+            .unwrap()
+            .evaluate_timed(&_inputs[1..], &_not_result)?;
+        let _upper_result = self
+            .upper_half_decoder
+            .as_mut()
+            .unwrap()
+            .evaluate_timed(&_inputs[1..], &_inputs[0])?;
+        let combined_upper_lower = [_upper_result, _lower_result].concat();
+        Ok(combined_upper_lower
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| self.ands.as_mut().unwrap()[i].evaluate_timed(bit, _enable))
+            .collect::<Vec<Waveform>>())
+    }
+}
+
+impl Component for Decoder2To4 {
+    fn type_name(&self) -> &'static str {
+        "Decoder2To4"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b", "enable"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        bus_names("y", 4)
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let outputs = self.evaluate(&inputs[0], &inputs[1], inputs[2]);
+        Ok(Bits::from_vector_b(outputs, None))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let outputs = self.evaluate_logic(inputs[0], inputs[1], inputs[2]);
+        Ok(LBits::from_vector(outputs))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(self.evaluate_timed(&inputs[0], &inputs[1], &inputs[2]))
+    }
+
+    fn logic_depth(&self) -> usize {
+        self.not0.logic_depth() + self.ands3[0].logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> = vec![
+            ("not0".to_string(), &self.not0 as &dyn Component),
+            ("not1".to_string(), &self.not1 as &dyn Component),
+        ];
+        for (i, component) in self.ands3.iter().enumerate() {
+            sub_components.push((indexed("ands3", i), component));
+        }
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> = vec![
+            ("not0".to_string(), &mut self.not0 as &mut dyn Component),
+            ("not1".to_string(), &mut self.not1 as &mut dyn Component),
+        ];
+        for (i, component) in self.ands3.iter_mut().enumerate() {
+            sub_components.push((indexed("ands3", i), component));
+        }
+        sub_components
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        let mut wires = vec![(port("a"), pin("not0", "a")), (port("b"), pin("not1", "a"))];
+        let (a, b) = (port("a"), port("b"));
+        let (not_a, not_b) = (pin("not0", "y"), pin("not1", "y"));
+        let inputs = [
+            (not_b.clone(), not_a.clone()),
+            (not_b, a.clone()),
+            (b.clone(), not_a),
+            (b, a),
+        ];
+        for (i, (first, second)) in inputs.into_iter().enumerate() {
+            let and3 = indexed("ands3", i);
+            wires.push((first, pin(&and3, "a")));
+            wires.push((second, pin(&and3, "b")));
+            wires.push((port("enable"), pin(&and3, "c")));
+            wires.push((pin(&and3, "y"), port(&indexed("y", i))));
+        }
+        wires
+    }
+}
+
+impl Component for Decoder {
+    fn type_name(&self) -> &'static str {
+        "Decoder"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        let mut inputs = bus_names("a", self.depth as usize);
+        inputs.push("enable".to_string());
+        inputs
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        bus_names("y", 2_usize.pow(self.depth as u32))
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let outputs = self.try_evaluate(
+            inputs.slice(..self.depth as usize),
+            inputs[self.depth as usize],
+        )?;
+        Ok(Bits::from_vector_b(outputs, None))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let inputs = inputs.iter().copied().collect::<Vec<Logic>>();
+        let (address, enable) = inputs.split_at(self.depth as usize);
+        Ok(LBits::from_vector(
+            self.try_evaluate_logic(address, enable[0])?,
+        ))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        let (address, enable) = inputs.split_at(self.depth as usize);
+        self.evaluate_timed(address, &enable[0])
+    }
+
+    fn logic_depth(&self) -> usize {
+        if let Some(decoder) = &self.decoder2to4 {
+            return decoder.logic_depth();
+        }
+        // The inverted MSB enables the lower half decoder, whose outputs are gated by `ands`.
+        self.not.logic_depth()
+            + self.lower_half_decoder.as_ref().unwrap().logic_depth()
+            + self.ands.as_ref().unwrap()[0].logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> =
+            vec![("not".to_string(), &self.not as &dyn Component)];
+        if let Some(ands) = self.ands.as_ref() {
+            for (i, component) in ands.iter().enumerate() {
+                sub_components.push((indexed("ands", i), component));
+            }
+        }
+        if let Some(lower_half_decoder) = self.lower_half_decoder.as_ref() {
+            sub_components.push((
+                "lower_half_decoder".to_string(),
+                lower_half_decoder.as_ref() as &dyn Component,
+            ));
+        }
+        if let Some(upper_half_decoder) = self.upper_half_decoder.as_ref() {
+            sub_components.push((
+                "upper_half_decoder".to_string(),
+                upper_half_decoder.as_ref() as &dyn Component,
+            ));
+        }
+        if let Some(decoder2to4) = self.decoder2to4.as_ref() {
+            sub_components.push(("decoder2to4".to_string(), decoder2to4 as &dyn Component));
+        }
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> =
+            vec![("not".to_string(), &mut self.not as &mut dyn Component)];
+        if let Some(ands) = self.ands.as_mut() {
+            for (i, component) in ands.iter_mut().enumerate() {
+                sub_components.push((indexed("ands", i), component));
+            }
+        }
+        if let Some(lower_half_decoder) = self.lower_half_decoder.as_mut() {
+            sub_components.push((
+                "lower_half_decoder".to_string(),
+                lower_half_decoder.as_mut() as &mut dyn Component,
+            ));
+        }
+        if let Some(upper_half_decoder) = self.upper_half_decoder.as_mut() {
+            sub_components.push((
+                "upper_half_decoder".to_string(),
+                upper_half_decoder.as_mut() as &mut dyn Component,
+            ));
+        }
+        if let Some(decoder2to4) = self.decoder2to4.as_mut() {
+            sub_components.push(("decoder2to4".to_string(), decoder2to4 as &mut dyn Component));
+        }
+        sub_components
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        let depth = self.depth as usize;
+        if self.decoder2to4.is_some() {
+            let mut wires = vec![
+                (port("a[0]"), pin("decoder2to4", "a")),
+                (port("a[1]"), pin("decoder2to4", "b")),
+                (port("enable"), pin("decoder2to4", "enable")),
+            ];
+            for i in 0..4 {
+                wires.push((pin("decoder2to4", &indexed("y", i)), port(&indexed("y", i))));
+            }
+            return wires;
+        }
+
+        let msb = indexed("a", depth - 1);
+        let mut wires = vec![
+            (port(&msb), pin("not", "a")),
+            (pin("not", "y"), pin("lower_half_decoder", "enable")),
+            (port(&msb), pin("upper_half_decoder", "enable")),
+        ];
+        for i in 0..depth - 1 {
+            let a = indexed("a", i);
+            wires.push((port(&a), pin("lower_half_decoder", &a)));
+            wires.push((port(&a), pin("upper_half_decoder", &a)));
+        }
+        // The upper half decoder gives the upper outputs, each gated by `enable`.
+        let half = 1 << (depth - 1);
+        for i in 0..2 * half {
+            let and = indexed("ands", i);
+            let (decoder, output) = if i < half {
+                ("upper_half_decoder", half - 1 - i)
+            } else {
+                ("lower_half_decoder", 2 * half - 1 - i)
+            };
+            wires.push((pin(decoder, &indexed("y", output)), pin(&and, "a")));
+            wires.push((port("enable"), pin(&and, "b")));
+            wires.push((pin(&and, "y"), port(&indexed("y", 2 * half - 1 - i))));
+        }
+        wires
     }
 }
 
@@ -135,13 +478,8 @@ mod tests {
             for a0 in [false, true] {
                 let result = decoder.evaluate(Bits::from_slice_b(&[a1, a0], Some(2)), true);
 
-                for (bit_index, bit) in result.iter().rev().enumerate() {
-                    if bit_index == bit_true_index {
-                        assert!(bit)
-                    } else {
-                        assert!(!bit)
-                    }
-                }
+                let expected = Bits::from_int(1, Some(4)) << bit_true_index;
+                assert!(expected == result);
                 bit_true_index += 1;
             }
         }
@@ -179,17 +517,25 @@ mod tests {
                         let result =
                             decoder.evaluate(Bits::from_slice_b(&[a3, a2, a1, a0], Some(4)), true);
 
-                        for (bit_index, bit) in result.iter().rev().enumerate() {
-                            if bit_index == bit_true_index {
-                                assert!(bit)
-                            } else {
-                                assert!(!bit)
-                            }
-                        }
+                        let expected = Bits::from_int(1, Some(16)) << bit_true_index;
+                        assert!(expected == result);
                         bit_true_index += 1;
                     }
                 }
             }
         }
     }
+
+    #[test]
+    fn decoder_wrong_input_size() {
+        let mut decoder = Decoder::new(3);
+        assert_eq!(
+            decoder.try_evaluate(Bits::from_int(1, Some(4)), true),
+            Err(Error::WidthMismatch {
+                input: "inputs",
+                expected: 3,
+                actual: 4
+            })
+        );
+    }
 }
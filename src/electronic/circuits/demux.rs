@@ -1,5 +1,9 @@
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{names, pin, port, Component, Pin};
 use crate::electronic::circuits::logic_gates::and::And;
 use crate::electronic::circuits::logic_gates::not::Not;
+use crate::error::Error;
 
 pub struct Demux1To2 {
     not: Not,
@@ -24,6 +28,77 @@ impl Demux1To2 {
 
         (_and1_result, _and0_result)
     }
+
+    pub fn evaluate_logic(&mut self, signal: Logic, s: Logic) -> (Logic, Logic) {
+        let _not_result = self.not.evaluate_logic(s);
+        let _and0_result = self.and0.evaluate_logic(_not_result, signal);
+        let _and1_result = self.and1.evaluate_logic(signal, s);
+        (_and1_result, _and0_result)
+    }
+}
+
+impl Default for Demux1To2 {
+    fn default() -> Self {
+        Demux1To2::new()
+    }
+}
+
+impl Component for Demux1To2 {
+    fn type_name(&self) -> &'static str {
+        "Demux1To2"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["signal", "s"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y1", "y0"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let (y1, y0) = self.evaluate(inputs[0], inputs[1]);
+        Ok(Bits::from_slice_b(&[y1, y0], None))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let (y1, y0) = self.evaluate_logic(inputs[0], inputs[1]);
+        Ok(LBits::from_vector(vec![y1, y0]))
+    }
+
+    fn logic_depth(&self) -> usize {
+        self.not.logic_depth() + self.and0.logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("not".to_string(), &self.not as &dyn Component),
+            ("and0".to_string(), &self.and0 as &dyn Component),
+            ("and1".to_string(), &self.and1 as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("not".to_string(), &mut self.not as &mut dyn Component),
+            ("and0".to_string(), &mut self.and0 as &mut dyn Component),
+            ("and1".to_string(), &mut self.and1 as &mut dyn Component),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("s"), pin("not", "a")),
+            (pin("not", "y"), pin("and0", "a")),
+            (port("signal"), pin("and0", "b")),
+            (port("signal"), pin("and1", "a")),
+            (port("s"), pin("and1", "b")),
+            (pin("and1", "y"), port("y1")),
+            (pin("and0", "y"), port("y0")),
+        ]
+    }
 }
 
 #[cfg(test)]
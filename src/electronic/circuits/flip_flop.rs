@@ -1,6 +1,10 @@
+use crate::data::bits::Bits;
+use crate::data::logic::Logic;
+use crate::electronic::circuits::component::{names, pin, port, Component, Pin};
 use crate::electronic::circuits::logic_gates::nand::Nand;
 use crate::electronic::circuits::logic_gates::not::Not;
-use rand::random;
+use crate::electronic::circuits::power_on::PowerOn;
+use crate::error::Error;
 
 pub struct DFlipFlop {
     nand0: Nand,
@@ -14,26 +18,46 @@ pub struct DFlipFlop {
 
     pub q: bool,
     q_bar: bool,
+
+    // Whether set/reset and q/q_bar hold real values, false while they are unknown since
+    // power-on.
+    sr_known: bool,
+    q_known: bool,
 }
 
 impl DFlipFlop {
+    /// Signal propagations through the cross-coupled nands before the latch is reported as
+    /// not settling. Two are enough once set and reset are valid.
+    pub const PROPAGATIONS: usize = 4;
+
     pub fn new() -> Self {
+        DFlipFlop::with_power_on(&mut PowerOn::random())
+    }
+
+    /// Flip-flop in a legal state: q from `power_on`, q_bar its complement, and set/reset as
+    /// if q had just been latched so they never conflict.
+    pub fn with_power_on(power_on: &mut PowerOn) -> Self {
+        let state = power_on.next_bool();
+        let q = state.unwrap_or(false);
         DFlipFlop {
             nand0: Nand::new(),
             nand1: Nand::new(),
             nand2: Nand::new(),
             nand3: Nand::new(),
             not: Not::new(),
-            set: random(),
-            reset: random(),
-            q: random(),
-            q_bar: random(),
+            set: q,
+            reset: !q,
+            q,
+            q_bar: !q,
+            sr_known: state.is_some(),
+            q_known: state.is_some(),
         }
     }
 
     pub fn set_sr(&mut self, set: bool, reset: bool) {
         self.set = set;
         self.reset = reset;
+        self.sr_known = true;
     }
 
     pub fn set_d(&mut self, _d: bool) {
@@ -45,16 +69,32 @@ impl DFlipFlop {
         (self.q, self.q_bar)
     }
 
+    /// Outputs as four-valued logic: `X` until a known value has been latched.
+    pub fn output_logic(&self) -> (Logic, Logic) {
+        if self.q_known {
+            (Logic::from(self.q), Logic::from(self.q_bar))
+        } else {
+            (Logic::X, Logic::X)
+        }
+    }
+
     pub fn reset_states(&mut self) {
         self.set = false;
         self.set = true;
         self.q = false;
         self.q_bar = true;
+        self.sr_known = true;
+        self.q_known = true;
     }
 
     pub fn clock_tick(&mut self, enable: bool) -> (bool, bool) {
-        if self.set && self.reset {
-            panic!("Invalid state: set and reset are both high")
+        self.try_clock_tick(enable)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_clock_tick(&mut self, enable: bool) -> Result<(bool, bool), Error> {
+        if self.sr_known && self.set && self.reset {
+            return Err(Error::SetResetConflict);
         }
 
         let _nand0_result = self.nand0.evaluate(self.set, enable);
@@ -63,14 +103,99 @@ impl DFlipFlop {
         let mut _next_q = self.nand2.evaluate(_nand0_result, self.q_bar);
         let mut _next_q_bar = self.nand3.evaluate(self.q, _nand1_result);
 
-        // 2nd signal propagation
-        _next_q = self.nand2.evaluate(_nand0_result, _next_q_bar);
-        _next_q_bar = self.nand3.evaluate(_next_q, _nand1_result);
+        // Further signal propagations until the cross-coupled nands settle
+        let mut settled = false;
+        for _ in 0..DFlipFlop::PROPAGATIONS {
+            let _q = self.nand2.evaluate(_nand0_result, _next_q_bar);
+            let _q_bar = self.nand3.evaluate(_q, _nand1_result);
+            settled = (_q, _q_bar) == (_next_q, _next_q_bar);
+            (_next_q, _next_q_bar) = (_q, _q_bar);
+            if settled {
+                break;
+            }
+        }
+        if !settled {
+            return Err(Error::NoConvergence {
+                iterations: DFlipFlop::PROPAGATIONS,
+                nodes: names(&["q", "q_bar"]),
+            });
+        }
 
         self.q = _next_q;
         self.q_bar = _next_q_bar;
+        if enable && self.sr_known && self.set != self.reset {
+            self.q_known = true;
+        }
+
+        Ok(self.output())
+    }
+}
+
+impl Default for DFlipFlop {
+    fn default() -> Self {
+        DFlipFlop::new()
+    }
+}
+
+impl Component for DFlipFlop {
+    fn type_name(&self) -> &'static str {
+        "DFlipFlop"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["d", "enable"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["q", "q_bar"])
+    }
 
-        self.output()
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        self.set_d(inputs[0]);
+        let (q, q_bar) = self.try_clock_tick(inputs[1])?;
+        Ok(Bits::from_slice_b(&[q, q_bar], None))
+    }
+
+    fn logic_depth(&self) -> usize {
+        // Enable through the gating nand, then both cross-coupled nands.
+        self.nand0.logic_depth() + self.nand2.logic_depth() + self.nand3.logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("nand0".to_string(), &self.nand0 as &dyn Component),
+            ("nand1".to_string(), &self.nand1 as &dyn Component),
+            ("nand2".to_string(), &self.nand2 as &dyn Component),
+            ("nand3".to_string(), &self.nand3 as &dyn Component),
+            ("not".to_string(), &self.not as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("nand0".to_string(), &mut self.nand0 as &mut dyn Component),
+            ("nand1".to_string(), &mut self.nand1 as &mut dyn Component),
+            ("nand2".to_string(), &mut self.nand2 as &mut dyn Component),
+            ("nand3".to_string(), &mut self.nand3 as &mut dyn Component),
+            ("not".to_string(), &mut self.not as &mut dyn Component),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("d"), pin("not", "a")),
+            (port("d"), pin("nand0", "a")),
+            (port("enable"), pin("nand0", "b")),
+            (port("enable"), pin("nand1", "a")),
+            (pin("not", "y"), pin("nand1", "b")),
+            (pin("nand0", "y"), pin("nand2", "a")),
+            (pin("nand3", "y"), pin("nand2", "b")),
+            (pin("nand2", "y"), pin("nand3", "a")),
+            (pin("nand1", "y"), pin("nand3", "b")),
+            (pin("nand2", "y"), port("q")),
+            (pin("nand3", "y"), port("q_bar")),
+        ]
     }
 }
 
@@ -209,4 +334,53 @@ mod tests {
         assert!(dflipflop.clock_tick(false) == (false, true));
         assert!(dflipflop.clock_tick(false) == (false, true));
     }
+
+    #[test]
+    fn d_flip_flop_set_reset_conflict() {
+        let mut dflipflop = DFlipFlop::new();
+        dflipflop.set_sr(true, true);
+        assert_eq!(dflipflop.try_clock_tick(true), Err(Error::SetResetConflict));
+
+        dflipflop.set_sr(true, false);
+        assert_eq!(dflipflop.try_clock_tick(true), Ok((true, false)));
+    }
+
+    #[test]
+    fn d_flip_flop_power_on() {
+        let mut zeros = PowerOn::Zeros;
+        let dflipflop = DFlipFlop::with_power_on(&mut zeros);
+        assert_eq!(dflipflop.output_logic(), (Logic::Zero, Logic::One));
+
+        // All ones is a legal state too, that can be clocked.
+        let mut dflipflop = DFlipFlop::with_power_on(&mut PowerOn::Ones);
+        assert_eq!(dflipflop.output_logic(), (Logic::One, Logic::Zero));
+        assert_eq!(dflipflop.try_clock_tick(true), Ok((true, false)));
+
+        let outputs = |seed| {
+            let mut power_on = PowerOn::seeded(seed);
+            (0..8)
+                .map(|_| DFlipFlop::with_power_on(&mut power_on).output_logic())
+                .collect::<Vec<(Logic, Logic)>>()
+        };
+        assert_eq!(outputs(7), outputs(7));
+        for (q, q_bar) in outputs(7) {
+            assert_eq!(q_bar, !q);
+        }
+    }
+
+    #[test]
+    fn d_flip_flop_unknown_until_latched() {
+        let mut dflipflop = DFlipFlop::with_power_on(&mut PowerOn::Unknown);
+        assert_eq!(dflipflop.output_logic(), (Logic::X, Logic::X));
+
+        dflipflop.clock_tick(true);
+        assert_eq!(dflipflop.output_logic(), (Logic::X, Logic::X));
+
+        dflipflop.set_d(true);
+        dflipflop.clock_tick(false);
+        assert_eq!(dflipflop.output_logic(), (Logic::X, Logic::X));
+
+        dflipflop.clock_tick(true);
+        assert_eq!(dflipflop.output_logic(), (Logic::One, Logic::Zero));
+    }
 }
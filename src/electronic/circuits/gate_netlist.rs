@@ -0,0 +1,615 @@
+use std::collections::HashMap;
+
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{pin, port, Component, Pin};
+use crate::electronic::circuits::logic_gates::nand::Nand;
+use crate::electronic::circuits::logic_gates::not::Not;
+use crate::error::Error;
+
+/// Handle to a net (wire) of a [`GateNetlist`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NetId(pub(crate) usize);
+
+/// Handle to a cell instance of a [`GateNetlist`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CellId(pub(crate) usize);
+
+struct Net {
+    name: String,
+    value: Logic,
+    /// Cell outputs driving the net, resolved together when there are several.
+    drivers: Vec<(CellId, usize)>,
+    fanout: Vec<CellId>,
+}
+
+struct Cell {
+    name: String,
+    component: Box<dyn Component>,
+    inputs: Vec<NetId>,
+    outputs: Vec<NetId>,
+    values: Vec<Logic>,
+}
+
+/// Gate-level circuit built at runtime: instances of existing cells (`Nand`, `FullAdder`, any
+/// [`Component`]) connected by named nets.
+///
+/// Simulation is event driven and on four-valued logic. Nets keep their values between calls,
+/// and only the cells reading a net that changed are evaluated again. All cells evaluated in
+/// a step see the values of the previous step, so feedback such as cross-coupled latches
+/// settles over several steps, or keeps oscillating.
+pub struct GateNetlist {
+    nets: Vec<Net>,
+    by_name: HashMap<String, NetId>,
+    cells: Vec<Cell>,
+    inputs: Vec<NetId>,
+    outputs: Vec<NetId>,
+    scheduled: Vec<bool>,
+    pending: Vec<CellId>,
+    evaluations: u64,
+}
+
+impl GateNetlist {
+    pub fn new() -> Self {
+        GateNetlist {
+            nets: Vec::new(),
+            by_name: HashMap::new(),
+            cells: Vec::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            scheduled: Vec::new(),
+            pending: Vec::new(),
+            evaluations: 0,
+        }
+    }
+
+    /// The net called `name`, created if needed. A net no cell drives is `Z`.
+    pub fn net(&mut self, name: &str) -> NetId {
+        if let Some(&id) = self.by_name.get(name) {
+            return id;
+        }
+        let id = NetId(self.nets.len());
+        self.nets.push(Net {
+            name: name.to_string(),
+            value: Logic::Z,
+            drivers: Vec::new(),
+            fanout: Vec::new(),
+        });
+        self.by_name.insert(name.to_string(), id);
+        id
+    }
+
+    pub fn find(&self, name: &str) -> Option<NetId> {
+        self.by_name.get(name).copied()
+    }
+
+    pub fn net_name(&self, net: NetId) -> &str {
+        &self.nets[net.0].name
+    }
+
+    pub fn net_count(&self) -> usize {
+        self.nets.len()
+    }
+
+    /// Declares the net called `name` as an input, `X` until `simulate` drives it. Declaring
+    /// it again returns the same port.
+    pub fn input(&mut self, name: &str) -> NetId {
+        let id = self.net(name);
+        if !self.inputs.contains(&id) {
+            self.nets[id.0].value = Logic::X;
+            self.inputs.push(id);
+        }
+        id
+    }
+
+    /// Declares the net called `name` as an output. Declaring it again returns the same port.
+    pub fn output(&mut self, name: &str) -> NetId {
+        let id = self.net(name);
+        if !self.outputs.contains(&id) {
+            self.outputs.push(id);
+        }
+        id
+    }
+
+    pub fn inputs(&self) -> &[NetId] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[NetId] {
+        &self.outputs
+    }
+
+    /// Adds an instance of `component` reading `inputs` and driving `outputs`, one net per
+    /// port in order. Its outputs are `X` until it is first evaluated.
+    ///
+    /// Cells are addressed by name like struct fields, a second cell called `name` is an
+    /// [`Error::DuplicateComponent`]. A net id from another netlist is an [`Error::UnknownNet`],
+    /// and nothing is added.
+    pub fn add(
+        &mut self,
+        name: &str,
+        component: impl Component + 'static,
+        inputs: &[NetId],
+        outputs: &[NetId],
+    ) -> Result<CellId, Error> {
+        if self.cell(name).is_some() {
+            return Err(Error::DuplicateComponent {
+                name: name.to_string(),
+            });
+        }
+        Error::check_width("inputs", component.input_count(), inputs.len())?;
+        Error::check_width("outputs", component.output_count(), outputs.len())?;
+        if let Some(net) = inputs
+            .iter()
+            .chain(outputs)
+            .find(|net| net.0 >= self.nets.len())
+        {
+            return Err(Error::UnknownNet { net: net.0 });
+        }
+
+        let id = CellId(self.cells.len());
+        for &net in inputs {
+            self.nets[net.0].fanout.push(id);
+        }
+        for (port, &net) in outputs.iter().enumerate() {
+            self.nets[net.0].drivers.push((id, port));
+            self.nets[net.0].value = Logic::X;
+        }
+        self.cells.push(Cell {
+            name: name.to_string(),
+            component: Box::new(component),
+            inputs: inputs.to_vec(),
+            outputs: outputs.to_vec(),
+            values: vec![Logic::X; outputs.len()],
+        });
+        self.scheduled.push(false);
+        self.schedule(id);
+        Ok(id)
+    }
+
+    pub fn cell(&self, name: &str) -> Option<CellId> {
+        self.cells
+            .iter()
+            .position(|cell| cell.name == name)
+            .map(CellId)
+    }
+
+    pub fn cell_name(&self, cell: CellId) -> &str {
+        &self.cells[cell.0].name
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn component(&self, cell: CellId) -> &dyn Component {
+        self.cells[cell.0].component.as_ref()
+    }
+
+    pub fn component_mut(&mut self, cell: CellId) -> &mut dyn Component {
+        self.cells[cell.0].component.as_mut()
+    }
+
+    pub fn cell_inputs(&self, cell: CellId) -> &[NetId] {
+        &self.cells[cell.0].inputs
+    }
+
+    pub fn cell_outputs(&self, cell: CellId) -> &[NetId] {
+        &self.cells[cell.0].outputs
+    }
+
+    /// Cells reading `net`.
+    pub fn fanout(&self, net: NetId) -> &[CellId] {
+        &self.nets[net.0].fanout
+    }
+
+    /// Cells driving `net`.
+    pub fn drivers(&self, net: NetId) -> Vec<CellId> {
+        self.nets[net.0]
+            .drivers
+            .iter()
+            .map(|&(cell, _)| cell)
+            .collect()
+    }
+
+    pub fn value(&self, net: NetId) -> Logic {
+        self.nets[net.0].value
+    }
+
+    /// Cell evaluations since the netlist was built.
+    pub fn evaluations(&self) -> u64 {
+        self.evaluations
+    }
+
+    /// Whether events are waiting for the next step.
+    pub fn is_settled(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Drives the input `net` to `value`, scheduling its fan-out if it changes.
+    pub fn set_input(&mut self, net: NetId, value: Logic) {
+        if self.nets[net.0].value != value {
+            self.nets[net.0].value = value;
+            self.schedule_fanout(net);
+        }
+    }
+
+    /// Drives the inputs, in declaration order, and runs until no net changes any more.
+    ///
+    /// A settled combinational netlist takes at most one step per cell. Feedback is given as
+    /// many steps again before the simulation is reported as not converging.
+    pub fn simulate(&mut self, inputs: &[Logic]) -> Result<LBits, Error> {
+        Error::check_width("inputs", self.inputs.len(), inputs.len())?;
+        for (i, &value) in inputs.iter().enumerate() {
+            self.set_input(self.inputs[i], value);
+        }
+        self.settle(2 * self.cells.len() + 1)?;
+        Ok(self.outputs.iter().map(|&net| self.value(net)).collect())
+    }
+
+    /// Steps until a fixed point, at most `max_steps` times, and returns the number of steps
+    /// taken. Without a fixed point, the error lists the nets that changed during the last
+    /// `cell_count() + 1` steps, enough for a change to go around any loop.
+    pub fn settle(&mut self, max_steps: usize) -> Result<usize, Error> {
+        let mut changed_at = vec![None; self.nets.len()];
+        for steps in 0..max_steps {
+            if self.is_settled() {
+                return Ok(steps);
+            }
+            for net in self.step()? {
+                changed_at[net.0] = Some(steps);
+            }
+        }
+        if self.is_settled() {
+            return Ok(max_steps);
+        }
+        let window = max_steps.saturating_sub(self.cells.len() + 1);
+        let nodes = (0..self.nets.len())
+            .filter(|&net| changed_at[net].is_some_and(|step| step >= window))
+            .map(|net| self.nets[net].name.clone())
+            .collect();
+        Err(Error::NoConvergence {
+            iterations: max_steps,
+            nodes,
+        })
+    }
+
+    /// Evaluates every scheduled cell on the current net values, then updates the nets they
+    /// drive and schedules the fan-out of those that changed. Returns the nets that changed.
+    pub fn step(&mut self) -> Result<Vec<NetId>, Error> {
+        let cells = std::mem::take(&mut self.pending);
+        let mut touched = Vec::new();
+        for &id in &cells {
+            self.scheduled[id.0] = false;
+            let inputs = self.cells[id.0]
+                .inputs
+                .iter()
+                .map(|&net| self.nets[net.0].value)
+                .collect::<LBits>();
+            let cell = &mut self.cells[id.0];
+            let outputs = cell.component.try_evaluate_logic_bits(&inputs)?;
+            cell.values = outputs.iter().copied().collect();
+            touched.extend_from_slice(&cell.outputs);
+            self.evaluations += 1;
+        }
+
+        let mut changed = Vec::new();
+        for net in touched {
+            let value = self.nets[net.0]
+                .drivers
+                .iter()
+                .fold(Logic::Z, |value, &(cell, port)| {
+                    value.resolve(self.cells[cell.0].values[port])
+                });
+            if self.nets[net.0].value != value {
+                self.nets[net.0].value = value;
+                self.schedule_fanout(net);
+                changed.push(net);
+            }
+        }
+        Ok(changed)
+    }
+
+    fn schedule(&mut self, cell: CellId) {
+        if !self.scheduled[cell.0] {
+            self.scheduled[cell.0] = true;
+            self.pending.push(cell);
+        }
+    }
+
+    fn schedule_fanout(&mut self, net: NetId) {
+        for i in 0..self.nets[net.0].fanout.len() {
+            self.schedule(self.nets[net.0].fanout[i]);
+        }
+    }
+
+    /// Cells on the longest path ending at `cell`, a feedback edge ending the path.
+    fn depth_to(&self, cell: CellId, depths: &mut [Option<usize>], visiting: &mut [bool]) -> usize {
+        if let Some(depth) = depths[cell.0] {
+            return depth;
+        }
+        visiting[cell.0] = true;
+        let mut fanin_depth = 0;
+        for &net in &self.cells[cell.0].inputs {
+            for &(driver, _) in &self.nets[net.0].drivers {
+                if !visiting[driver.0] {
+                    fanin_depth = fanin_depth.max(self.depth_to(driver, depths, visiting));
+                }
+            }
+        }
+        visiting[cell.0] = false;
+        let depth = fanin_depth + self.cells[cell.0].component.logic_depth();
+        depths[cell.0] = Some(depth);
+        depth
+    }
+
+    // Sample circuits
+
+    /// The gated latch of [`DFlipFlop`](crate::electronic::circuits::flip_flop::DFlipFlop),
+    /// with the same cell names, inputs `d` and `enable` and outputs `q` and `q_bar`.
+    pub fn d_flip_flop() -> Self {
+        let mut netlist = GateNetlist::new();
+        let d = netlist.input("d");
+        let enable = netlist.input("enable");
+        let q = netlist.output("q");
+        let q_bar = netlist.output("q_bar");
+        let not_d = netlist.net("not_d");
+        let set_bar = netlist.net("set_bar");
+        let reset_bar = netlist.net("reset_bar");
+        let ports = "ports match the cells";
+        netlist.add("not", Not::new(), &[d], &[not_d]).expect(ports);
+        netlist
+            .add("nand0", Nand::new(), &[d, enable], &[set_bar])
+            .expect(ports);
+        netlist
+            .add("nand1", Nand::new(), &[enable, not_d], &[reset_bar])
+            .expect(ports);
+        netlist
+            .add("nand2", Nand::new(), &[set_bar, q_bar], &[q])
+            .expect(ports);
+        netlist
+            .add("nand3", Nand::new(), &[q, reset_bar], &[q_bar])
+            .expect(ports);
+        netlist
+    }
+}
+
+impl Default for GateNetlist {
+    fn default() -> Self {
+        GateNetlist::new()
+    }
+}
+
+impl Component for GateNetlist {
+    fn type_name(&self) -> &'static str {
+        "GateNetlist"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        self.inputs
+            .iter()
+            .map(|&net| self.net_name(net).to_string())
+            .collect()
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        self.outputs
+            .iter()
+            .map(|&net| self.net_name(net).to_string())
+            .collect()
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let inputs = inputs.iter().map(Logic::from).collect::<Vec<Logic>>();
+        let outputs = self.simulate(&inputs)?;
+        self.outputs
+            .iter()
+            .zip(outputs.iter())
+            .map(|(&net, &value)| {
+                let name = self.net_name(net).to_string();
+                match value {
+                    Logic::Z => Err(Error::FloatingNode { node: name }),
+                    value => value.to_bool().ok_or(Error::UnknownValue { node: name }),
+                }
+            })
+            .collect()
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        self.simulate(&inputs.iter().copied().collect::<Vec<Logic>>())
+    }
+
+    /// Cells on the longest path through the netlist, each counted with its own depth.
+    /// Feedback edges are cut, so a latch counts once around its loop.
+    fn logic_depth(&self) -> usize {
+        let mut depths = vec![None; self.cells.len()];
+        let mut visiting = vec![false; self.cells.len()];
+        (0..self.cells.len())
+            .map(|cell| self.depth_to(CellId(cell), &mut depths, &mut visiting))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        self.cells
+            .iter()
+            .map(|cell| (cell.name.clone(), cell.component.as_ref()))
+            .collect()
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        self.cells
+            .iter_mut()
+            .map(|cell| {
+                let component = cell.component.as_mut() as &mut dyn Component;
+                (cell.name.clone(), component)
+            })
+            .collect()
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        let mut drivers = vec![Vec::new(); self.nets.len()];
+        for &net in &self.inputs {
+            drivers[net.0].push(port(self.net_name(net)));
+        }
+        for cell in &self.cells {
+            for (net, name) in cell.outputs.iter().zip(cell.component.output_names()) {
+                drivers[net.0].push(pin(&cell.name, &name));
+            }
+        }
+
+        let mut wires = Vec::new();
+        for cell in &self.cells {
+            for (net, name) in cell.inputs.iter().zip(cell.component.input_names()) {
+                for driver in &drivers[net.0] {
+                    wires.push((driver.clone(), pin(&cell.name, &name)));
+                }
+            }
+        }
+        for &net in &self.outputs {
+            for driver in &drivers[net.0] {
+                wires.push((driver.clone(), port(self.net_name(net))));
+            }
+        }
+        wires
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::flip_flop::DFlipFlop;
+    use crate::electronic::circuits::logic_gates::and::And;
+    use crate::electronic::circuits::logic_gates::or::Or;
+    use crate::electronic::circuits::logic_gates::xor::Xor;
+    use crate::electronic::circuits::power_on::PowerOn;
+
+    fn full_adder() -> GateNetlist {
+        let mut netlist = GateNetlist::new();
+        let a = netlist.input("a");
+        let b = netlist.input("b");
+        let carry_in = netlist.input("carry_in");
+        let sum = netlist.output("sum");
+        let carry_out = netlist.output("carry_out");
+        let (xor0, and0, and1) = (
+            netlist.net("xor0"),
+            netlist.net("and0"),
+            netlist.net("and1"),
+        );
+        netlist.add("xor0", Xor::new(), &[a, b], &[xor0]).unwrap();
+        netlist
+            .add("xor1", Xor::new(), &[xor0, carry_in], &[sum])
+            .unwrap();
+        netlist
+            .add("and0", And::new(), &[carry_in, xor0], &[and0])
+            .unwrap();
+        netlist.add("and1", And::new(), &[a, b], &[and1]).unwrap();
+        netlist
+            .add("or", Or::new(), &[and0, and1], &[carry_out])
+            .unwrap();
+        netlist
+    }
+
+    #[test]
+    fn gate_netlist_full_adder() {
+        let mut netlist = full_adder();
+        let mut full_adder = FullAdder::new();
+        for value in 0..8 {
+            let inputs = Bits::from_int(value, Some(3));
+            assert!(netlist.evaluate_bits(&inputs) == full_adder.evaluate_bits(&inputs));
+        }
+        assert_eq!(netlist.logic_depth(), full_adder.logic_depth());
+        assert_eq!(netlist.sub_components().len(), 5);
+        assert_eq!(
+            netlist.add("or1", Or::new(), &[], &[]),
+            Err(Error::WidthMismatch {
+                input: "inputs",
+                expected: 2,
+                actual: 0
+            })
+        );
+        let (a, b) = (netlist.inputs()[0], netlist.inputs()[1]);
+        let y = netlist.net("y");
+        assert_eq!(
+            netlist.add("xor0", Xor::new(), &[a, b], &[y]),
+            Err(Error::DuplicateComponent {
+                name: "xor0".to_string()
+            })
+        );
+        let foreign = NetId(netlist.net_count());
+        assert_eq!(
+            netlist.add("xor2", Xor::new(), &[a, b], &[foreign]),
+            Err(Error::UnknownNet { net: foreign.0 })
+        );
+        assert_eq!(netlist.sub_components().len(), 5);
+        assert!(netlist.fanout(a).len() == 2);
+
+        // Declaring a port again is the same port.
+        assert_eq!(netlist.input("a"), a);
+        assert_eq!(netlist.output("sum"), netlist.outputs()[0]);
+        assert_eq!((netlist.inputs().len(), netlist.outputs().len()), (3, 2));
+    }
+
+    #[test]
+    fn gate_netlist_fanout_only() {
+        let mut netlist = full_adder();
+        // The carry in only reaches xor1, and0 and then or.
+        netlist
+            .simulate(&[Logic::Zero, Logic::One, Logic::One])
+            .unwrap();
+        let evaluations = netlist.evaluations();
+        netlist
+            .simulate(&[Logic::Zero, Logic::One, Logic::Zero])
+            .unwrap();
+        assert_eq!(netlist.evaluations() - evaluations, 3);
+        netlist
+            .simulate(&[Logic::Zero, Logic::One, Logic::Zero])
+            .unwrap();
+        assert_eq!(netlist.evaluations() - evaluations, 3);
+
+        // Unknown inputs only make the outputs they reach unknown.
+        let outputs = netlist.simulate(&[Logic::One, Logic::One, Logic::X]);
+        assert_eq!(outputs.unwrap().to_string(), "2'bx1");
+    }
+
+    #[test]
+    fn gate_netlist_feedback() {
+        let mut netlist = GateNetlist::d_flip_flop();
+        let mut d_flip_flop = DFlipFlop::with_power_on(&mut PowerOn::Zeros);
+        d_flip_flop.reset_states();
+        assert_eq!(
+            netlist
+                .simulate(&[Logic::Zero, Logic::Zero])
+                .unwrap()
+                .to_string(),
+            "2'bxx"
+        );
+        for (d, enable) in [(false, true), (true, false), (true, true), (false, false)] {
+            let inputs = Bits::from_slice_b(&[d, enable], None);
+            assert!(netlist.evaluate_bits(&inputs) == d_flip_flop.evaluate_bits(&inputs));
+        }
+    }
+
+    #[test]
+    fn gate_netlist_oscillation() {
+        // A nand fed back on itself is a ring oscillator once enabled.
+        let mut netlist = GateNetlist::new();
+        let enable = netlist.input("enable");
+        let y = netlist.output("y");
+        netlist
+            .add("nand", Nand::new(), &[enable, y], &[y])
+            .unwrap();
+
+        assert!(netlist.evaluate_bits(&Bits::zeros(1)) == Bits::ones(1));
+        assert_eq!(
+            netlist.simulate(&[Logic::One]),
+            Err(Error::NoConvergence {
+                iterations: 3,
+                nodes: vec!["y".to_string()]
+            })
+        );
+    }
+}
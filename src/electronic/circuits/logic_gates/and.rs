@@ -1,4 +1,9 @@
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{names, pin, port, sealed::Gate, Component, Pin};
 use crate::electronic::circuits::logic_gates::{nand::Nand, not::Not};
+use crate::electronic::timing::Waveform;
+use crate::error::Error;
 
 pub struct And {
     nand: Nand,
@@ -17,6 +22,87 @@ impl And {
         let _nand_result = self.nand.evaluate(signal_a, signal_b);
         self.not.evaluate(_nand_result)
     }
+
+    pub fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic) -> Logic {
+        let _nand_result = self.nand.evaluate_logic(signal_a, signal_b);
+        self.not.evaluate_logic(_nand_result)
+    }
+
+    pub fn evaluate_timed(&mut self, signal_a: &Waveform, signal_b: &Waveform) -> Waveform {
+        let _nand_result = self.nand.evaluate_timed(signal_a, signal_b);
+        self.not.evaluate_timed(&_nand_result)
+    }
+}
+
+impl Default for And {
+    fn default() -> Self {
+        And::new()
+    }
+}
+
+impl Component for And {
+    fn type_name(&self) -> &'static str {
+        "And"
+    }
+
+    fn gate(&self) -> Option<Gate> {
+        Some(Gate::And)
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(
+            &[self.evaluate(inputs[0], inputs[1])],
+            None,
+        ))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![
+            self.evaluate_logic(inputs[0], inputs[1])
+        ]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(vec![self.evaluate_timed(&inputs[0], &inputs[1])])
+    }
+
+    fn logic_depth(&self) -> usize {
+        self.nand.logic_depth() + self.not.logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("nand".to_string(), &self.nand as &dyn Component),
+            ("not".to_string(), &self.not as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("nand".to_string(), &mut self.nand as &mut dyn Component),
+            ("not".to_string(), &mut self.not as &mut dyn Component),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("a"), pin("nand", "a")),
+            (port("b"), pin("nand", "b")),
+            (pin("nand", "y"), pin("not", "a")),
+            (pin("not", "y"), port("y")),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -50,4 +136,12 @@ mod tests {
         let result = and.evaluate(true, true);
         assert!(result);
     }
+
+    #[test]
+    fn and_evaluate_logic() {
+        let mut and = And::new();
+        assert_eq!(and.evaluate_logic(Logic::One, Logic::One), Logic::One);
+        assert_eq!(and.evaluate_logic(Logic::Zero, Logic::X), Logic::Zero);
+        assert_eq!(and.evaluate_logic(Logic::One, Logic::Z), Logic::X);
+    }
 }
@@ -1,4 +1,9 @@
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{names, pin, port, sealed::Gate, Component, Pin};
 use crate::electronic::circuits::logic_gates::and::And;
+use crate::electronic::timing::Waveform;
+use crate::error::Error;
 
 pub struct And3 {
     and0: And,
@@ -17,6 +22,93 @@ impl And3 {
         let _and0_result = self.and0.evaluate(signal_a, signal_b);
         self.and1.evaluate(_and0_result, signal_c)
     }
+
+    pub fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic, signal_c: Logic) -> Logic {
+        let _and0_result = self.and0.evaluate_logic(signal_a, signal_b);
+        self.and1.evaluate_logic(_and0_result, signal_c)
+    }
+
+    pub fn evaluate_timed(
+        &mut self,
+        signal_a: &Waveform,
+        signal_b: &Waveform,
+        signal_c: &Waveform,
+    ) -> Waveform {
+        let _and0_result = self.and0.evaluate_timed(signal_a, signal_b);
+        self.and1.evaluate_timed(&_and0_result, signal_c)
+    }
+}
+
+impl Default for And3 {
+    fn default() -> Self {
+        And3::new()
+    }
+}
+
+impl Component for And3 {
+    fn type_name(&self) -> &'static str {
+        "And3"
+    }
+
+    fn gate(&self) -> Option<Gate> {
+        Some(Gate::And)
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b", "c"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(
+            &[self.evaluate(inputs[0], inputs[1], inputs[2])],
+            None,
+        ))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![
+            self.evaluate_logic(inputs[0], inputs[1], inputs[2])
+        ]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(vec![self.evaluate_timed(&inputs[0], &inputs[1], &inputs[2])])
+    }
+
+    fn logic_depth(&self) -> usize {
+        self.and0.logic_depth() + self.and1.logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("and0".to_string(), &self.and0 as &dyn Component),
+            ("and1".to_string(), &self.and1 as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("and0".to_string(), &mut self.and0 as &mut dyn Component),
+            ("and1".to_string(), &mut self.and1 as &mut dyn Component),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("a"), pin("and0", "a")),
+            (port("b"), pin("and0", "b")),
+            (pin("and0", "y"), pin("and1", "a")),
+            (port("c"), pin("and1", "b")),
+            (pin("and1", "y"), port("y")),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -78,4 +170,21 @@ mod tests {
         let result = and3.evaluate(true, true, true);
         assert!(result);
     }
+
+    #[test]
+    fn and3_evaluate_logic() {
+        let mut and3 = And3::new();
+        assert_eq!(
+            and3.evaluate_logic(Logic::One, Logic::One, Logic::One),
+            Logic::One
+        );
+        assert_eq!(
+            and3.evaluate_logic(Logic::X, Logic::Z, Logic::Zero),
+            Logic::Zero
+        );
+        assert_eq!(
+            and3.evaluate_logic(Logic::One, Logic::X, Logic::One),
+            Logic::X
+        );
+    }
 }
@@ -0,0 +1,367 @@
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{
+    bus_names, indexed, names, pin, port, sealed::Gate, Component, Pin,
+};
+use crate::electronic::circuits::logic_gates::{
+    and::And, nand::Nand, nor::Nor, or::Or, xnor::Xnor, xor::Xor,
+};
+use crate::electronic::timing::Waveform;
+use crate::error::Error;
+
+/// Two-input gate usable as a node of a [`GateN`] tree.
+pub trait TwoInputGate: Component + Default {
+    /// Type name of the wide gate whose root is this gate, e.g. `NandN` for `Nand`.
+    const WIDE_NAME: &'static str;
+
+    fn evaluate(&mut self, signal_a: bool, signal_b: bool) -> bool;
+
+    fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic) -> Logic;
+
+    fn evaluate_timed(&mut self, signal_a: &Waveform, signal_b: &Waveform) -> Waveform;
+}
+
+macro_rules! impl_two_input_gate {
+    ($($gate:ident => $wide_name:literal),*) => {
+        $(
+            impl TwoInputGate for $gate {
+                const WIDE_NAME: &'static str = $wide_name;
+
+                fn evaluate(&mut self, signal_a: bool, signal_b: bool) -> bool {
+                    $gate::evaluate(self, signal_a, signal_b)
+                }
+
+                fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic) -> Logic {
+                    $gate::evaluate_logic(self, signal_a, signal_b)
+                }
+
+                fn evaluate_timed(&mut self, signal_a: &Waveform, signal_b: &Waveform) -> Waveform {
+                    $gate::evaluate_timed(self, signal_a, signal_b)
+                }
+            }
+        )*
+    };
+}
+
+impl_two_input_gate!(
+    And => "AndN",
+    Nand => "NandN",
+    Or => "OrN",
+    Nor => "NorN",
+    Xor => "XorN",
+    Xnor => "XnorN"
+);
+
+/// Gate with `size` inputs: a balanced tree of two-input `G` gates under a `R` root, so its
+/// depth grows as `log2(size)`. The root is the inverting variant for `NandN`, `NorN` and
+/// `XnorN`.
+pub struct GateN<G, R> {
+    size: usize,
+    gates: Vec<G>,
+    root: R,
+}
+
+pub type AndN = GateN<And, And>;
+pub type NandN = GateN<And, Nand>;
+pub type OrN = GateN<Or, Or>;
+pub type NorN = GateN<Or, Nor>;
+pub type XorN = GateN<Xor, Xor>;
+pub type XnorN = GateN<Xor, Xnor>;
+
+impl<G: TwoInputGate, R: TwoInputGate> GateN<G, R> {
+    pub fn new(size: usize) -> Self {
+        Self::try_new(size).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// A gate needs at least 2 inputs, fewer are a [`Error::WidthMismatch`] expecting 2.
+    pub fn try_new(size: usize) -> Result<Self, Error> {
+        if size < 2 {
+            return Err(Error::WidthMismatch {
+                input: "inputs",
+                expected: 2,
+                actual: size,
+            });
+        }
+        Ok(GateN {
+            size,
+            gates: (0..size - 2).map(|_| G::default()).collect(),
+            root: R::default(),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn evaluate(&mut self, inputs: &Bits) -> bool {
+        self.try_evaluate(inputs)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_evaluate(&mut self, inputs: &Bits) -> Result<bool, Error> {
+        Error::check_width("inputs", self.size, inputs.len())?;
+        Ok(self.reduce(
+            inputs.iter().collect(),
+            |gate, a, b| gate.evaluate(a, b),
+            |root, a, b| root.evaluate(a, b),
+        ))
+    }
+
+    pub fn try_evaluate_logic(&mut self, inputs: &LBits) -> Result<Logic, Error> {
+        Error::check_width("inputs", self.size, inputs.len())?;
+        Ok(self.reduce(
+            inputs.iter().copied().collect(),
+            |gate, a, b| gate.evaluate_logic(a, b),
+            |root, a, b| root.evaluate_logic(a, b),
+        ))
+    }
+
+    pub fn try_evaluate_timed(&mut self, inputs: &[Waveform]) -> Result<Waveform, Error> {
+        Error::check_width("inputs", self.size, inputs.len())?;
+        Ok(self.reduce(
+            inputs.to_vec(),
+            |gate, a, b| gate.evaluate_timed(&a, &b),
+            |root, a, b| root.evaluate_timed(&a, &b),
+        ))
+    }
+
+    /// Pairs the values level by level, an odd value out moves up to the next level as is.
+    fn reduce<T>(
+        &mut self,
+        mut values: Vec<T>,
+        mut gate: impl FnMut(&mut G, T, T) -> T,
+        root: impl FnOnce(&mut R, T, T) -> T,
+    ) -> T {
+        let mut gates = self.gates.iter_mut();
+        while values.len() > 2 {
+            let mut level = Vec::with_capacity(values.len().div_ceil(2));
+            let mut values_iter = values.into_iter();
+            while let Some(a) = values_iter.next() {
+                match values_iter.next() {
+                    Some(b) => level.push(gate(gates.next().unwrap(), a, b)),
+                    None => level.push(a),
+                }
+            }
+            values = level;
+        }
+        let mut values_iter = values.into_iter();
+        let (a, b) = (values_iter.next().unwrap(), values_iter.next().unwrap());
+        root(&mut self.root, a, b)
+    }
+
+    /// Levels of two-input gates between an input and the output.
+    fn levels(&self) -> usize {
+        self.size.next_power_of_two().ilog2() as usize
+    }
+}
+
+/// Whether every bit of `bits` is high, through an `AndN`. True for no bits.
+pub fn all(bits: &Bits) -> bool {
+    reduce_bits::<And, And>(bits, true)
+}
+
+/// Whether any bit of `bits` is high, through an `OrN`. False for no bits.
+pub fn any(bits: &Bits) -> bool {
+    reduce_bits::<Or, Or>(bits, false)
+}
+
+/// Whether an odd number of bits of `bits` are high, through a `XorN`.
+pub fn parity(bits: &Bits) -> bool {
+    reduce_bits::<Xor, Xor>(bits, false)
+}
+
+/// Pads `bits` to the two inputs a tree needs with the `identity` of its operation.
+fn reduce_bits<G: TwoInputGate, R: TwoInputGate>(bits: &Bits, identity: bool) -> bool {
+    let mut bits = bits.clone();
+    while bits.len() < 2 {
+        bits = bits.concat(&Bits::from_slice_b(&[identity], None));
+    }
+    GateN::<G, R>::new(bits.len()).evaluate(&bits)
+}
+
+impl<G: TwoInputGate, R: TwoInputGate> Component for GateN<G, R> {
+    fn type_name(&self) -> &'static str {
+        R::WIDE_NAME
+    }
+
+    fn gate(&self) -> Option<Gate> {
+        self.root.gate()
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        bus_names("a", self.size)
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(&[self.try_evaluate(inputs)?], None))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![self.try_evaluate_logic(inputs)?]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(vec![self.try_evaluate_timed(inputs)?])
+    }
+
+    fn logic_depth(&self) -> usize {
+        let gate = self.gates.first().map_or(0, |gate| gate.logic_depth());
+        (self.levels() - 1) * gate + self.root.logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> = Vec::new();
+        for (i, component) in self.gates.iter().enumerate() {
+            sub_components.push((indexed("gates", i), component));
+        }
+        sub_components.push(("root".to_string(), &self.root));
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> = Vec::new();
+        for (i, component) in self.gates.iter_mut().enumerate() {
+            sub_components.push((indexed("gates", i), component));
+        }
+        sub_components.push(("root".to_string(), &mut self.root));
+        sub_components
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        // The pairing of `reduce`, on the pins carrying the values.
+        let mut wires = Vec::new();
+        let mut values = self
+            .input_names()
+            .iter()
+            .map(|name| port(name))
+            .collect::<Vec<Pin>>();
+        let mut gates = (0..self.gates.len()).map(|i| indexed("gates", i));
+        while values.len() > 2 {
+            let mut level = Vec::with_capacity(values.len().div_ceil(2));
+            for pair in values.chunks(2) {
+                match pair {
+                    [a, b] => {
+                        let gate = gates.next().unwrap();
+                        wires.push((a.clone(), pin(&gate, "a")));
+                        wires.push((b.clone(), pin(&gate, "b")));
+                        level.push(pin(&gate, "y"));
+                    }
+                    [a] => level.push(a.clone()),
+                    _ => unreachable!(),
+                }
+            }
+            values = level;
+        }
+        wires.push((values[0].clone(), pin("root", "a")));
+        wires.push((values[1].clone(), pin("root", "b")));
+        wires.push((pin("root", "y"), port("y")));
+        wires
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::stats::stats;
+
+    #[test]
+    fn gate_n_truth_tables() {
+        for size in 2..=7 {
+            let mut and_n = AndN::new(size);
+            let mut nand_n = NandN::new(size);
+            let mut or_n = OrN::new(size);
+            let mut nor_n = NorN::new(size);
+            let mut xor_n = XorN::new(size);
+            let mut xnor_n = XnorN::new(size);
+            for value in 0..1_u32 << size {
+                let inputs = Bits::from_int(value, Some(size));
+                let ones = value.count_ones() as usize;
+                assert_eq!(and_n.evaluate(&inputs), ones == size);
+                assert_eq!(nand_n.evaluate(&inputs), ones != size);
+                assert_eq!(or_n.evaluate(&inputs), ones > 0);
+                assert_eq!(nor_n.evaluate(&inputs), ones == 0);
+                assert_eq!(xor_n.evaluate(&inputs), !ones.is_multiple_of(2));
+                assert_eq!(xnor_n.evaluate(&inputs), ones.is_multiple_of(2));
+            }
+        }
+    }
+
+    #[test]
+    fn gate_n_logic_and_waveforms() {
+        let mut and_n = AndN::new(5);
+        let inputs = "1x10z".parse::<LBits>().unwrap();
+        assert_eq!(and_n.try_evaluate_logic(&inputs), Ok(Logic::Zero));
+        let inputs = "1x111".parse::<LBits>().unwrap();
+        assert_eq!(and_n.try_evaluate_logic(&inputs), Ok(Logic::X));
+
+        let mut xor_n = XorN::new(4);
+        let from = Bits::zeros(4);
+        let to = Bits::from_int(0b0001, Some(4));
+        let outputs = xor_n
+            .evaluate_waveforms(&Waveform::bus_step(&from, &to, 0))
+            .unwrap();
+        assert!(outputs[0].final_value());
+        assert_eq!(
+            xor_n.try_evaluate(&Bits::zeros(3)),
+            Err(Error::WidthMismatch {
+                input: "inputs",
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn gate_n_depth_and_size() {
+        assert_eq!(AndN::new(2).logic_depth(), 2);
+        assert_eq!(AndN::new(8).logic_depth(), 3 * 2);
+        assert_eq!(AndN::new(9).logic_depth(), 4 * 2);
+        assert_eq!(NandN::new(8).logic_depth(), 2 * 2 + 1);
+        assert_eq!(XorN::new(16).logic_depth(), 4);
+        assert_eq!(XorN::new(32).logic_depth(), 5);
+
+        let report = stats(&OrN::new(8), 0);
+        assert_eq!(report.count("Or"), 7);
+        assert_eq!(report.total().transistors, 7 * 6);
+        assert_eq!(NorN::new(3).type_name(), "NorN");
+    }
+
+    #[test]
+    fn gate_n_reductions() {
+        assert!(all(&Bits::ones(13)));
+        assert!(!all(&Bits::from_int(0b1011, Some(4))));
+        assert!(all(&Bits::zeros(0)));
+        assert!(any(&Bits::from_int(0b0100, Some(4))));
+        assert!(!any(&Bits::zeros(1)));
+        assert!(parity(&Bits::from_int(0b1011, Some(4))));
+        assert!(!parity(&Bits::from_int(0b1001, Some(4))));
+        assert!(parity(&Bits::ones(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn gate_n_one_input() {
+        AndN::new(1);
+    }
+
+    #[test]
+    fn gate_n_try_new() {
+        assert_eq!(
+            OrN::try_new(1).err(),
+            Some(Error::WidthMismatch {
+                input: "inputs",
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert!(XorN::try_new(0).is_err());
+        assert_eq!(NandN::try_new(3).unwrap().size(), 3);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod and;
 pub mod and3;
+pub mod gate_n;
 pub mod nand;
 pub mod nor;
 pub mod not;
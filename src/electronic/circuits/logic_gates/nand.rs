@@ -1,11 +1,23 @@
-use crate::electronic::components::transistor::{NMOSTransistor, PMOSTransistor, Transistor};
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::activity::ToggleCounter;
+use crate::electronic::circuits::component::{names, sealed::Gate, Component};
+use crate::electronic::components::transistor::{
+    cmos_output, NMOSTransistor, PMOSTransistor, Transistor,
+};
 use crate::electronic::components::voltage_levels::{GND, VDD};
+use crate::electronic::fault::{held, stuck, Fault};
+use crate::electronic::timing::{propagate, Delay, Waveform};
+use crate::error::Error;
 
 pub struct Nand {
     nmos_a: NMOSTransistor,
     nmos_b: NMOSTransistor,
     pmos_a: PMOSTransistor,
     pmos_b: PMOSTransistor,
+    delay: Option<Delay>,
+    output_toggles: ToggleCounter,
+    fault: Option<Fault>,
 }
 
 impl Nand {
@@ -15,10 +27,22 @@ impl Nand {
             nmos_b: NMOSTransistor::new(),
             pmos_a: PMOSTransistor::new(),
             pmos_b: PMOSTransistor::new(),
+            delay: None,
+            output_toggles: ToggleCounter::new(),
+            fault: None,
         }
     }
 
     pub fn evaluate(&mut self, signal_a: bool, signal_b: bool) -> bool {
+        if self.is_faulty() {
+            let output = held(
+                self.drive(Logic::from(signal_a), Logic::from(signal_b)),
+                self.output_toggles.last(),
+            );
+            self.output_toggles.record(Logic::from(output));
+            return output;
+        }
+
         self.nmos_a.apply_control_signal(signal_a);
         self.pmos_a.apply_control_signal(signal_a);
         self.nmos_b.apply_control_signal(signal_b);
@@ -30,7 +54,141 @@ impl Nand {
         self.nmos_b.connect_source(GND);
         self.nmos_a.connect_source(self.nmos_b.drain());
 
-        (self.pmos_a.drain() || self.pmos_b.drain()) && !self.nmos_a.drain()
+        let output = (self.pmos_a.drain() || self.pmos_b.drain()) && !self.nmos_a.drain();
+        self.output_toggles.record(Logic::from(output));
+        output
+    }
+
+    pub fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic) -> Logic {
+        let output = self.drive(signal_a, signal_b);
+        self.output_toggles.record(output);
+        output
+    }
+
+    /// Applies the inputs to the transistors and returns the output, after a stuck-at fault.
+    fn drive(&mut self, signal_a: Logic, signal_b: Logic) -> Logic {
+        self.nmos_a.apply_control_logic(signal_a);
+        self.pmos_a.apply_control_logic(signal_a);
+        self.nmos_b.apply_control_logic(signal_b);
+        self.pmos_b.apply_control_logic(signal_b);
+
+        let pull_up = self.pmos_a.channel() | self.pmos_b.channel();
+        let pull_down = self.nmos_a.channel() & self.nmos_b.channel();
+        stuck(cmos_output(pull_up, pull_down), self.fault)
+    }
+
+    /// Whether the gate or one of its transistors carries a fault.
+    fn is_faulty(&self) -> bool {
+        self.fault.is_some()
+            || [
+                &self.nmos_a as &dyn Component,
+                &self.nmos_b,
+                &self.pmos_a,
+                &self.pmos_b,
+            ]
+            .iter()
+            .any(|transistor| transistor.fault().is_some())
+    }
+
+    /// Rise time of the pull-up network and fall time of the pull-down network, unless set.
+    pub fn delay(&self) -> Delay {
+        self.delay.unwrap_or(Delay::new(
+            self.pmos_a.delay().rise.max(self.pmos_b.delay().rise),
+            self.nmos_a.delay().fall + self.nmos_b.delay().fall,
+        ))
+    }
+
+    pub fn set_delay(&mut self, delay: Delay) {
+        self.delay = Some(delay);
+    }
+
+    pub fn evaluate_timed(&mut self, signal_a: &Waveform, signal_b: &Waveform) -> Waveform {
+        let delay = self.delay();
+        propagate(&[signal_a, signal_b], delay, |values| {
+            self.evaluate(values[0], values[1])
+        })
+    }
+}
+
+impl Default for Nand {
+    fn default() -> Self {
+        Nand::new()
+    }
+}
+
+impl Component for Nand {
+    fn type_name(&self) -> &'static str {
+        "Nand"
+    }
+
+    fn gate(&self) -> Option<Gate> {
+        Some(Gate::Nand)
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(
+            &[self.evaluate(inputs[0], inputs[1])],
+            None,
+        ))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![
+            self.evaluate_logic(inputs[0], inputs[1])
+        ]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(vec![self.evaluate_timed(&inputs[0], &inputs[1])])
+    }
+
+    fn own_toggles(&self) -> u64 {
+        self.output_toggles.count()
+    }
+
+    fn reset_own_toggles(&mut self) {
+        self.output_toggles.reset();
+    }
+
+    fn supported_faults(&self) -> &'static [Fault] {
+        Fault::STUCK_AT
+    }
+
+    fn fault(&self) -> Option<Fault> {
+        self.fault
+    }
+
+    fn set_fault(&mut self, fault: Option<Fault>) {
+        self.fault = fault;
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("nmos_a".to_string(), &self.nmos_a as &dyn Component),
+            ("nmos_b".to_string(), &self.nmos_b as &dyn Component),
+            ("pmos_a".to_string(), &self.pmos_a as &dyn Component),
+            ("pmos_b".to_string(), &self.pmos_b as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("nmos_a".to_string(), &mut self.nmos_a as &mut dyn Component),
+            ("nmos_b".to_string(), &mut self.nmos_b as &mut dyn Component),
+            ("pmos_a".to_string(), &mut self.pmos_a as &mut dyn Component),
+            ("pmos_b".to_string(), &mut self.pmos_b as &mut dyn Component),
+        ]
     }
 }
 
@@ -65,4 +223,15 @@ mod tests {
         let result = nand.evaluate(true, true);
         assert!(!result);
     }
+
+    #[test]
+    fn nand_evaluate_logic() {
+        let mut nand = Nand::new();
+        assert_eq!(nand.evaluate_logic(Logic::One, Logic::One), Logic::Zero);
+        assert_eq!(nand.evaluate_logic(Logic::Zero, Logic::One), Logic::One);
+        assert_eq!(nand.evaluate_logic(Logic::Zero, Logic::X), Logic::One);
+        assert_eq!(nand.evaluate_logic(Logic::X, Logic::Zero), Logic::One);
+        assert_eq!(nand.evaluate_logic(Logic::One, Logic::X), Logic::X);
+        assert_eq!(nand.evaluate_logic(Logic::Z, Logic::One), Logic::X);
+    }
 }
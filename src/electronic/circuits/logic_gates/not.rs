@@ -1,9 +1,21 @@
-use crate::electronic::components::transistor::{NMOSTransistor, PMOSTransistor, Transistor};
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::activity::ToggleCounter;
+use crate::electronic::circuits::component::{names, sealed::Gate, Component};
+use crate::electronic::components::transistor::{
+    cmos_output, NMOSTransistor, PMOSTransistor, Transistor,
+};
 use crate::electronic::components::voltage_levels::{GND, VDD};
+use crate::electronic::fault::{held, stuck, Fault};
+use crate::electronic::timing::{propagate, Delay, Waveform};
+use crate::error::Error;
 
 pub struct Not {
     nmos: NMOSTransistor,
     pmos: PMOSTransistor,
+    delay: Option<Delay>,
+    output_toggles: ToggleCounter,
+    fault: Option<Fault>,
 }
 
 impl Not {
@@ -11,17 +23,141 @@ impl Not {
         Not {
             nmos: NMOSTransistor::new(),
             pmos: PMOSTransistor::new(),
+            delay: None,
+            output_toggles: ToggleCounter::new(),
+            fault: None,
         }
     }
 
     pub fn evaluate(&mut self, signal: bool) -> bool {
+        if self.is_faulty() {
+            let output = held(self.drive(Logic::from(signal)), self.output_toggles.last());
+            self.output_toggles.record(Logic::from(output));
+            return output;
+        }
+
         self.nmos.apply_control_signal(signal);
         self.pmos.apply_control_signal(signal);
 
         self.nmos.connect_source(GND);
         self.pmos.connect_source(VDD);
 
-        self.pmos.drain() && !self.nmos.drain()
+        let output = self.pmos.drain() && !self.nmos.drain();
+        self.output_toggles.record(Logic::from(output));
+        output
+    }
+
+    pub fn evaluate_logic(&mut self, signal: Logic) -> Logic {
+        let output = self.drive(signal);
+        self.output_toggles.record(output);
+        output
+    }
+
+    /// Applies the inputs to the transistors and returns the output, after a stuck-at fault.
+    fn drive(&mut self, signal: Logic) -> Logic {
+        self.nmos.apply_control_logic(signal);
+        self.pmos.apply_control_logic(signal);
+
+        stuck(
+            cmos_output(self.pmos.channel(), self.nmos.channel()),
+            self.fault,
+        )
+    }
+
+    /// Whether the gate or one of its transistors carries a fault.
+    fn is_faulty(&self) -> bool {
+        self.fault.is_some()
+            || [&self.nmos as &dyn Component, &self.pmos]
+                .iter()
+                .any(|transistor| transistor.fault().is_some())
+    }
+
+    /// Rise time of the pull-up network and fall time of the pull-down network, unless set.
+    pub fn delay(&self) -> Delay {
+        self.delay
+            .unwrap_or(Delay::new(self.pmos.delay().rise, self.nmos.delay().fall))
+    }
+
+    pub fn set_delay(&mut self, delay: Delay) {
+        self.delay = Some(delay);
+    }
+
+    pub fn evaluate_timed(&mut self, signal: &Waveform) -> Waveform {
+        let delay = self.delay();
+        propagate(&[signal], delay, |values| self.evaluate(values[0]))
+    }
+}
+
+impl Default for Not {
+    fn default() -> Self {
+        Not::new()
+    }
+}
+
+impl Component for Not {
+    fn type_name(&self) -> &'static str {
+        "Not"
+    }
+
+    fn gate(&self) -> Option<Gate> {
+        Some(Gate::Not)
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(&[self.evaluate(inputs[0])], None))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![self.evaluate_logic(inputs[0])]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(vec![self.evaluate_timed(&inputs[0])])
+    }
+
+    fn own_toggles(&self) -> u64 {
+        self.output_toggles.count()
+    }
+
+    fn reset_own_toggles(&mut self) {
+        self.output_toggles.reset();
+    }
+
+    fn supported_faults(&self) -> &'static [Fault] {
+        Fault::STUCK_AT
+    }
+
+    fn fault(&self) -> Option<Fault> {
+        self.fault
+    }
+
+    fn set_fault(&mut self, fault: Option<Fault>) {
+        self.fault = fault;
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("nmos".to_string(), &self.nmos as &dyn Component),
+            ("pmos".to_string(), &self.pmos as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("nmos".to_string(), &mut self.nmos as &mut dyn Component),
+            ("pmos".to_string(), &mut self.pmos as &mut dyn Component),
+        ]
     }
 }
 
@@ -29,6 +165,15 @@ impl Not {
 mod tests {
     use super::*;
 
+    #[test]
+    fn not_delay_from_transistor_edges() {
+        let mut not = Not::new();
+        not.pmos.set_delay(Delay::new(5, 50));
+        not.nmos.set_delay(Delay::new(60, 7));
+        // Only the pull-up rising and the pull-down falling move the output.
+        assert_eq!(not.delay(), Delay::new(5, 7));
+    }
+
     #[test]
     fn not_evaluate_with_signal_true() {
         let mut not = Not::new();
@@ -42,4 +187,13 @@ mod tests {
         let result = not.evaluate(false);
         assert!(result);
     }
+
+    #[test]
+    fn not_evaluate_logic() {
+        let mut not = Not::new();
+        assert_eq!(not.evaluate_logic(Logic::One), Logic::Zero);
+        assert_eq!(not.evaluate_logic(Logic::Zero), Logic::One);
+        assert_eq!(not.evaluate_logic(Logic::Z), Logic::X);
+        assert_eq!(not.evaluate_logic(Logic::X), Logic::X);
+    }
 }
@@ -1,5 +1,10 @@
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{names, pin, port, sealed::Gate, Component, Pin};
 use crate::electronic::circuits::logic_gates::nor::Nor;
 use crate::electronic::circuits::logic_gates::not::Not;
+use crate::electronic::timing::Waveform;
+use crate::error::Error;
 
 pub struct Or {
     nor: Nor,
@@ -18,6 +23,87 @@ impl Or {
         let _nor_result = self.nor.evaluate(signal_a, signal_b);
         self.not.evaluate(_nor_result)
     }
+
+    pub fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic) -> Logic {
+        let _nor_result = self.nor.evaluate_logic(signal_a, signal_b);
+        self.not.evaluate_logic(_nor_result)
+    }
+
+    pub fn evaluate_timed(&mut self, signal_a: &Waveform, signal_b: &Waveform) -> Waveform {
+        let _nor_result = self.nor.evaluate_timed(signal_a, signal_b);
+        self.not.evaluate_timed(&_nor_result)
+    }
+}
+
+impl Default for Or {
+    fn default() -> Self {
+        Or::new()
+    }
+}
+
+impl Component for Or {
+    fn type_name(&self) -> &'static str {
+        "Or"
+    }
+
+    fn gate(&self) -> Option<Gate> {
+        Some(Gate::Or)
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(
+            &[self.evaluate(inputs[0], inputs[1])],
+            None,
+        ))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![
+            self.evaluate_logic(inputs[0], inputs[1])
+        ]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(vec![self.evaluate_timed(&inputs[0], &inputs[1])])
+    }
+
+    fn logic_depth(&self) -> usize {
+        self.nor.logic_depth() + self.not.logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("nor".to_string(), &self.nor as &dyn Component),
+            ("not".to_string(), &self.not as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("nor".to_string(), &mut self.nor as &mut dyn Component),
+            ("not".to_string(), &mut self.not as &mut dyn Component),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("a"), pin("nor", "a")),
+            (port("b"), pin("nor", "b")),
+            (pin("nor", "y"), pin("not", "a")),
+            (pin("not", "y"), port("y")),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -51,4 +137,12 @@ mod tests {
         let result = or.evaluate(true, true);
         assert!(result);
     }
+
+    #[test]
+    fn or_evaluate_logic() {
+        let mut or = Or::new();
+        assert_eq!(or.evaluate_logic(Logic::Zero, Logic::Zero), Logic::Zero);
+        assert_eq!(or.evaluate_logic(Logic::One, Logic::X), Logic::One);
+        assert_eq!(or.evaluate_logic(Logic::Zero, Logic::Z), Logic::X);
+    }
 }
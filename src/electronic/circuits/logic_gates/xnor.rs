@@ -1,5 +1,14 @@
-use crate::electronic::components::transistor::{NMOSTransistor, PMOSTransistor, Transistor};
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::activity::ToggleCounter;
+use crate::electronic::circuits::component::{names, sealed::Gate, Component};
+use crate::electronic::components::transistor::{
+    cmos_output, NMOSTransistor, PMOSTransistor, Transistor,
+};
 use crate::electronic::components::voltage_levels::{GND, VDD};
+use crate::electronic::fault::{held, stuck, Fault};
+use crate::electronic::timing::{propagate, Delay, Waveform};
+use crate::error::Error;
 
 pub struct Xnor {
     pmos_a: PMOSTransistor,
@@ -10,6 +19,9 @@ pub struct Xnor {
     nmos_a_bar: NMOSTransistor,
     nmos_b: NMOSTransistor,
     nmos_b_bar: NMOSTransistor,
+    delay: Option<Delay>,
+    output_toggles: ToggleCounter,
+    fault: Option<Fault>,
 }
 
 impl Xnor {
@@ -23,10 +35,22 @@ impl Xnor {
             nmos_a_bar: NMOSTransistor::new(),
             nmos_b: NMOSTransistor::new(),
             nmos_b_bar: NMOSTransistor::new(),
+            delay: None,
+            output_toggles: ToggleCounter::new(),
+            fault: None,
         }
     }
 
     pub fn evaluate(&mut self, signal_a: bool, signal_b: bool) -> bool {
+        if self.is_faulty() {
+            let output = held(
+                self.drive(Logic::from(signal_a), Logic::from(signal_b)),
+                self.output_toggles.last(),
+            );
+            self.output_toggles.record(Logic::from(output));
+            return output;
+        }
+
         self.pmos_a.apply_control_signal(signal_a);
         self.pmos_a_bar.apply_control_signal(!signal_a);
         self.pmos_b.apply_control_signal(signal_b);
@@ -47,8 +71,175 @@ impl Xnor {
         self.nmos_a.connect_source(self.nmos_b_bar.drain());
         self.nmos_a_bar.connect_source(self.nmos_b.drain());
 
-        (self.pmos_b.drain() || self.pmos_b_bar.drain())
-            && (!self.nmos_a_bar.drain() || !self.nmos_a.drain())
+        let output = (self.pmos_b.drain() || self.pmos_b_bar.drain())
+            && (!self.nmos_a_bar.drain() || !self.nmos_a.drain());
+        self.output_toggles.record(Logic::from(output));
+        output
+    }
+
+    pub fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic) -> Logic {
+        let output = self.drive(signal_a, signal_b);
+        self.output_toggles.record(output);
+        output
+    }
+
+    /// Applies the inputs to the transistors and returns the output, after a stuck-at fault.
+    fn drive(&mut self, signal_a: Logic, signal_b: Logic) -> Logic {
+        self.pmos_a.apply_control_logic(signal_a);
+        self.pmos_a_bar.apply_control_logic(!signal_a);
+        self.pmos_b.apply_control_logic(signal_b);
+        self.pmos_b_bar.apply_control_logic(!signal_b);
+
+        self.nmos_a.apply_control_logic(signal_a);
+        self.nmos_a_bar.apply_control_logic(!signal_a);
+        self.nmos_b_bar.apply_control_logic(!signal_b);
+        self.nmos_b.apply_control_logic(signal_b);
+
+        let pull_up = (self.pmos_a.channel() & self.pmos_b.channel())
+            | (self.pmos_a_bar.channel() & self.pmos_b_bar.channel());
+        let pull_down = (self.nmos_a.channel() & self.nmos_b_bar.channel())
+            | (self.nmos_a_bar.channel() & self.nmos_b.channel());
+        stuck(cmos_output(pull_up, pull_down), self.fault)
+    }
+
+    /// Whether the gate or one of its transistors carries a fault.
+    fn is_faulty(&self) -> bool {
+        self.fault.is_some()
+            || [
+                &self.pmos_a as &dyn Component,
+                &self.pmos_a_bar,
+                &self.pmos_b,
+                &self.pmos_b_bar,
+                &self.nmos_a,
+                &self.nmos_a_bar,
+                &self.nmos_b,
+                &self.nmos_b_bar,
+            ]
+            .iter()
+            .any(|transistor| transistor.fault().is_some())
+    }
+
+    /// Rise time of the pull-up network and fall time of the pull-down network, unless set.
+    pub fn delay(&self) -> Delay {
+        self.delay.unwrap_or(Delay::new(
+            (self.pmos_a.delay().rise + self.pmos_b.delay().rise)
+                .max(self.pmos_a_bar.delay().rise + self.pmos_b_bar.delay().rise),
+            (self.nmos_b_bar.delay().fall + self.nmos_a.delay().fall)
+                .max(self.nmos_b.delay().fall + self.nmos_a_bar.delay().fall),
+        ))
+    }
+
+    pub fn set_delay(&mut self, delay: Delay) {
+        self.delay = Some(delay);
+    }
+
+    pub fn evaluate_timed(&mut self, signal_a: &Waveform, signal_b: &Waveform) -> Waveform {
+        let delay = self.delay();
+        propagate(&[signal_a, signal_b], delay, |values| {
+            self.evaluate(values[0], values[1])
+        })
+    }
+}
+
+impl Default for Xnor {
+    fn default() -> Self {
+        Xnor::new()
+    }
+}
+
+impl Component for Xnor {
+    fn type_name(&self) -> &'static str {
+        "Xnor"
+    }
+
+    fn gate(&self) -> Option<Gate> {
+        Some(Gate::Xnor)
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(
+            &[self.evaluate(inputs[0], inputs[1])],
+            None,
+        ))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![
+            self.evaluate_logic(inputs[0], inputs[1])
+        ]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(vec![self.evaluate_timed(&inputs[0], &inputs[1])])
+    }
+
+    fn own_toggles(&self) -> u64 {
+        self.output_toggles.count()
+    }
+
+    fn reset_own_toggles(&mut self) {
+        self.output_toggles.reset();
+    }
+
+    fn supported_faults(&self) -> &'static [Fault] {
+        Fault::STUCK_AT
+    }
+
+    fn fault(&self) -> Option<Fault> {
+        self.fault
+    }
+
+    fn set_fault(&mut self, fault: Option<Fault>) {
+        self.fault = fault;
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("pmos_a".to_string(), &self.pmos_a as &dyn Component),
+            ("pmos_a_bar".to_string(), &self.pmos_a_bar as &dyn Component),
+            ("pmos_b".to_string(), &self.pmos_b as &dyn Component),
+            ("pmos_b_bar".to_string(), &self.pmos_b_bar as &dyn Component),
+            ("nmos_a".to_string(), &self.nmos_a as &dyn Component),
+            ("nmos_a_bar".to_string(), &self.nmos_a_bar as &dyn Component),
+            ("nmos_b".to_string(), &self.nmos_b as &dyn Component),
+            ("nmos_b_bar".to_string(), &self.nmos_b_bar as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("pmos_a".to_string(), &mut self.pmos_a as &mut dyn Component),
+            (
+                "pmos_a_bar".to_string(),
+                &mut self.pmos_a_bar as &mut dyn Component,
+            ),
+            ("pmos_b".to_string(), &mut self.pmos_b as &mut dyn Component),
+            (
+                "pmos_b_bar".to_string(),
+                &mut self.pmos_b_bar as &mut dyn Component,
+            ),
+            ("nmos_a".to_string(), &mut self.nmos_a as &mut dyn Component),
+            (
+                "nmos_a_bar".to_string(),
+                &mut self.nmos_a_bar as &mut dyn Component,
+            ),
+            ("nmos_b".to_string(), &mut self.nmos_b as &mut dyn Component),
+            (
+                "nmos_b_bar".to_string(),
+                &mut self.nmos_b_bar as &mut dyn Component,
+            ),
+        ]
     }
 }
 
@@ -83,4 +274,17 @@ mod tests {
         let result = xnor.evaluate(true, true);
         assert!(result);
     }
+
+    #[test]
+    fn xnor_evaluate_logic() {
+        let mut xnor = Xnor::new();
+        for a in [false, true] {
+            for b in [false, true] {
+                let result = xnor.evaluate_logic(Logic::from(a), Logic::from(b));
+                assert_eq!(result, Logic::from(a == b));
+            }
+        }
+        assert_eq!(xnor.evaluate_logic(Logic::X, Logic::One), Logic::X);
+        assert_eq!(xnor.evaluate_logic(Logic::Zero, Logic::Z), Logic::X);
+    }
 }
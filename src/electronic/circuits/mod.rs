@@ -1,10 +1,13 @@
 pub mod adder;
 pub mod bitwise;
+pub mod component;
 pub mod decoder;
 pub mod demux;
 pub mod flip_flop;
+pub mod gate_netlist;
 pub mod logic_gates;
 pub mod mux;
+pub mod power_on;
 pub mod register;
 pub mod shifter;
 pub mod subtractor;
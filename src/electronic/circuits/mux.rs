@@ -1,6 +1,12 @@
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{names, pin, port, Component, Pin};
 use crate::electronic::circuits::logic_gates::and::And;
 use crate::electronic::circuits::logic_gates::not::Not;
 use crate::electronic::circuits::logic_gates::or::Or;
+use crate::electronic::components::transmission_gate::TransmissionGate;
+use crate::electronic::timing::Waveform;
+use crate::error::Error;
 
 pub struct Mux2To1 {
     not: Not,
@@ -25,6 +31,223 @@ impl Mux2To1 {
         let _and1_result = self.and1.evaluate(signal_b, _s);
         self.or.evaluate(_and0_result, _and1_result)
     }
+
+    pub fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic, _s: Logic) -> Logic {
+        let _not_result = self.not.evaluate_logic(_s);
+        let _and0_result = self.and0.evaluate_logic(signal_a, _not_result);
+        let _and1_result = self.and1.evaluate_logic(signal_b, _s);
+        self.or.evaluate_logic(_and0_result, _and1_result)
+    }
+
+    /// Gate level the output settles at, for inputs settled after `a`, `b` and `s` levels.
+    pub(crate) fn arrivals(&self, a: usize, b: usize, s: usize) -> usize {
+        (a.max(s + self.not.logic_depth()) + self.and0.logic_depth())
+            .max(b.max(s) + self.and1.logic_depth())
+            + self.or.logic_depth()
+    }
+
+    pub fn evaluate_timed(
+        &mut self,
+        signal_a: &Waveform,
+        signal_b: &Waveform,
+        _s: &Waveform,
+    ) -> Waveform {
+        let _not_result = self.not.evaluate_timed(_s);
+        let _and0_result = self.and0.evaluate_timed(signal_a, &_not_result);
+        let _and1_result = self.and1.evaluate_timed(signal_b, _s);
+        self.or.evaluate_timed(&_and0_result, &_and1_result)
+    }
+}
+
+impl Default for Mux2To1 {
+    fn default() -> Self {
+        Mux2To1::new()
+    }
+}
+
+impl Component for Mux2To1 {
+    fn type_name(&self) -> &'static str {
+        "Mux2To1"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b", "s"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(
+            &[self.evaluate(inputs[0], inputs[1], inputs[2])],
+            None,
+        ))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![
+            self.evaluate_logic(inputs[0], inputs[1], inputs[2])
+        ]))
+    }
+
+    fn evaluate_waveforms(&mut self, inputs: &[Waveform]) -> Result<Vec<Waveform>, Error> {
+        self.check_waveforms(inputs)?;
+        Ok(vec![self.evaluate_timed(&inputs[0], &inputs[1], &inputs[2])])
+    }
+
+    fn logic_depth(&self) -> usize {
+        self.arrivals(0, 0, 0)
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("not".to_string(), &self.not as &dyn Component),
+            ("and0".to_string(), &self.and0 as &dyn Component),
+            ("and1".to_string(), &self.and1 as &dyn Component),
+            ("or".to_string(), &self.or as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("not".to_string(), &mut self.not as &mut dyn Component),
+            ("and0".to_string(), &mut self.and0 as &mut dyn Component),
+            ("and1".to_string(), &mut self.and1 as &mut dyn Component),
+            ("or".to_string(), &mut self.or as &mut dyn Component),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("s"), pin("not", "a")),
+            (port("a"), pin("and0", "a")),
+            (pin("not", "y"), pin("and0", "b")),
+            (port("b"), pin("and1", "a")),
+            (port("s"), pin("and1", "b")),
+            (pin("and0", "y"), pin("or", "a")),
+            (pin("and1", "y"), pin("or", "b")),
+            (pin("or", "y"), port("y")),
+        ]
+    }
+}
+
+/// Pass-transistor 2:1 multiplexer: two transmission gates driving the same output, 6
+/// transistors against 20 for [`Mux2To1`].
+pub struct PassMux2To1 {
+    not: Not,
+    transmission_gate_a: TransmissionGate,
+    transmission_gate_b: TransmissionGate,
+}
+
+impl PassMux2To1 {
+    pub fn new() -> Self {
+        PassMux2To1 {
+            not: Not::new(),
+            transmission_gate_a: TransmissionGate::new(),
+            transmission_gate_b: TransmissionGate::new(),
+        }
+    }
+
+    pub fn evaluate(&mut self, signal_a: bool, signal_b: bool, _s: bool) -> bool {
+        self.evaluate_logic(
+            Logic::from(signal_a),
+            Logic::from(signal_b),
+            Logic::from(_s),
+        )
+        .to_bool()
+        .expect("one transmission gate is always on")
+    }
+
+    pub fn evaluate_logic(&mut self, signal_a: Logic, signal_b: Logic, _s: Logic) -> Logic {
+        let _not_result = self.not.evaluate_logic(_s);
+        let _a = self
+            .transmission_gate_a
+            .evaluate_logic(signal_a, _not_result);
+        let _b = self.transmission_gate_b.evaluate_logic(signal_b, _s);
+        _a.resolve(_b)
+    }
+}
+
+impl Default for PassMux2To1 {
+    fn default() -> Self {
+        PassMux2To1::new()
+    }
+}
+
+impl Component for PassMux2To1 {
+    fn type_name(&self) -> &'static str {
+        "PassMux2To1"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "b", "s"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        Ok(Bits::from_slice_b(
+            &[self.evaluate(inputs[0], inputs[1], inputs[2])],
+            None,
+        ))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        Ok(LBits::from_vector(vec![
+            self.evaluate_logic(inputs[0], inputs[1], inputs[2])
+        ]))
+    }
+
+    fn logic_depth(&self) -> usize {
+        self.not.logic_depth() + self.transmission_gate_a.logic_depth()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("not".to_string(), &self.not as &dyn Component),
+            (
+                "transmission_gate_a".to_string(),
+                &self.transmission_gate_a as &dyn Component,
+            ),
+            (
+                "transmission_gate_b".to_string(),
+                &self.transmission_gate_b as &dyn Component,
+            ),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("not".to_string(), &mut self.not as &mut dyn Component),
+            (
+                "transmission_gate_a".to_string(),
+                &mut self.transmission_gate_a as &mut dyn Component,
+            ),
+            (
+                "transmission_gate_b".to_string(),
+                &mut self.transmission_gate_b as &mut dyn Component,
+            ),
+        ]
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        vec![
+            (port("s"), pin("not", "a")),
+            (port("a"), pin("transmission_gate_a", "a")),
+            (pin("not", "y"), pin("transmission_gate_a", "enable")),
+            (port("b"), pin("transmission_gate_b", "a")),
+            (port("s"), pin("transmission_gate_b", "enable")),
+            (pin("transmission_gate_a", "y"), port("y")),
+            (pin("transmission_gate_b", "y"), port("y")),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -45,4 +268,26 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn pass_mux2to1_evaluate() {
+        let mut pass_mux2to1 = PassMux2To1::new();
+        let mut mux2to1 = Mux2To1::new();
+
+        for a in [false, true] {
+            for b in [false, true] {
+                for s in [false, true] {
+                    assert!(pass_mux2to1.evaluate(a, b, s) == mux2to1.evaluate(a, b, s));
+                }
+            }
+        }
+        assert_eq!(
+            pass_mux2to1.evaluate_logic(Logic::One, Logic::One, Logic::X),
+            Logic::X
+        );
+        assert_eq!(
+            pass_mux2to1.evaluate_logic(Logic::Z, Logic::One, Logic::Zero),
+            Logic::Z
+        );
+    }
 }
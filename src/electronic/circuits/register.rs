@@ -1,35 +1,54 @@
 use crate::data::bits::Bits;
+use crate::data::logic::LBits;
+use crate::electronic::circuits::component::{bus_names, indexed, pin, port, Component, Pin};
 use crate::electronic::circuits::flip_flop::DFlipFlop;
-use rand::random;
+use crate::electronic::circuits::power_on::PowerOn;
+use crate::error::Error;
 
 pub struct PIPORegister {
-    size: u8,
+    size: usize,
     d_flip_flops: Vec<DFlipFlop>,
     ds: Vec<bool>,
+    // False while the inputs are unknown since power-on, they are then not fed to the
+    // flip-flops.
+    ds_known: bool,
 }
 
 impl PIPORegister {
-    pub fn new(size: u8) -> Self {
+    pub fn new(size: usize) -> Self {
+        PIPORegister::with_power_on(size, &mut PowerOn::random())
+    }
+
+    pub fn with_power_on(size: usize, power_on: &mut PowerOn) -> Self {
         let _d_flip_flops = (0..size)
-            .map(|_| DFlipFlop::new())
+            .map(|_| DFlipFlop::with_power_on(power_on))
             .collect::<Vec<DFlipFlop>>();
-        let _ds = (0..size).map(|_| random()).collect::<Vec<bool>>();
+        let _ds = (0..size)
+            .map(|_| power_on.next_bool())
+            .collect::<Vec<Option<bool>>>();
         PIPORegister {
             size,
             d_flip_flops: _d_flip_flops,
-            ds: _ds,
+            ds_known: _ds.iter().all(|d| d.is_some()),
+            ds: _ds.iter().map(|d| d.unwrap_or(false)).collect(),
         }
     }
 
     pub fn output(&mut self) -> Bits {
-        Bits::from_vector_b(
-            self.d_flip_flops
-                .iter()
-                .rev()
-                .map(|d_flip_flop| d_flip_flop.q)
-                .collect::<Vec<bool>>(),
-            None,
-        )
+        self.d_flip_flops
+            .iter()
+            .rev()
+            .map(|d_flip_flop| d_flip_flop.q)
+            .collect()
+    }
+
+    /// Outputs as four-valued logic, with `X` for the flip-flops still unknown since power-on.
+    pub fn output_logic(&self) -> LBits {
+        self.d_flip_flops
+            .iter()
+            .rev()
+            .map(|d_flip_flop| d_flip_flop.output_logic().0)
+            .collect()
     }
 
     pub fn reset_states(&mut self) {
@@ -37,26 +56,87 @@ impl PIPORegister {
             d_flip_flop.reset_states();
         }
         self.ds = (0..self.size).map(|_| false).collect::<Vec<bool>>();
+        self.ds_known = true;
     }
 
     pub fn set_d(&mut self, ds: &[bool]) {
-        if ds.len() != self.size as usize {
-            panic!("Input length should be {} but is {}", self.size, ds.len());
-        }
-        self.ds = ds.to_vec();
-        self.ds.reverse();
+        self.try_set_d(ds)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_set_d(&mut self, ds: &[bool]) -> Result<(), Error> {
+        Error::check_width("d", self.size, ds.len())?;
+        self.ds.clear();
+        self.ds.extend(ds.iter().rev());
+        self.ds_known = true;
+        Ok(())
     }
 
     pub fn clock_tick(&mut self, enable: bool) -> Bits {
         for (d_flip_flop, ds) in self.d_flip_flops.iter_mut().zip(&self.ds) {
-            d_flip_flop.set_d(*ds);
+            if self.ds_known {
+                d_flip_flop.set_d(*ds);
+            }
             d_flip_flop.clock_tick(enable);
         }
         self.output()
     }
 }
 
+impl Component for PIPORegister {
+    fn type_name(&self) -> &'static str {
+        "PIPORegister"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        let mut inputs = bus_names("d", self.size);
+        inputs.push("enable".to_string());
+        inputs
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        bus_names("q", self.size)
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        self.ds.clear();
+        self.ds.extend(inputs.view(..self.size).iter().rev());
+        self.ds_known = true;
+        Ok(self.clock_tick(inputs[self.size]))
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> = Vec::new();
+        for (i, component) in self.d_flip_flops.iter().enumerate() {
+            sub_components.push((indexed("d_flip_flops", i), component));
+        }
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> = Vec::new();
+        for (i, component) in self.d_flip_flops.iter_mut().enumerate() {
+            sub_components.push((indexed("d_flip_flops", i), component));
+        }
+        sub_components
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        let mut wires = Vec::new();
+        for i in 0..self.size {
+            let d_flip_flop = indexed("d_flip_flops", i);
+            wires.push((port(&indexed("d", i)), pin(&d_flip_flop, "d")));
+            wires.push((port("enable"), pin(&d_flip_flop, "enable")));
+            wires.push((pin(&d_flip_flop, "q"), port(&indexed("q", i))));
+        }
+        wires
+    }
+}
+
 #[cfg(test)]
+// The baseline asserts compare with `&[…]`.
+#[allow(clippy::op_ref)]
 mod tests {
     use super::*;
 
@@ -104,4 +184,45 @@ mod tests {
         let mut pipo_register = PIPORegister::new(4);
         pipo_register.set_d(&[true, true, true, false, true]);
     }
+
+    #[test]
+    fn pipo_try_set_d() {
+        let mut pipo_register = PIPORegister::new(4);
+        assert_eq!(
+            pipo_register.try_set_d(&[true, true, true, false, true]),
+            Err(Error::WidthMismatch {
+                input: "d",
+                expected: 4,
+                actual: 5
+            })
+        );
+        assert_eq!(pipo_register.try_set_d(&[true, true, true, false]), Ok(()));
+    }
+
+    #[test]
+    fn pipo_power_on() {
+        let mut pipo_register = PIPORegister::with_power_on(4, &mut PowerOn::Ones);
+        assert_eq!(pipo_register.output(), [true, true, true, true]);
+
+        let outputs = |seed| {
+            let mut power_on = PowerOn::seeded(seed);
+            let mut first = PIPORegister::with_power_on(16, &mut power_on);
+            let mut second = PIPORegister::with_power_on(16, &mut power_on);
+            (first.output(), second.output())
+        };
+        assert_eq!(outputs(3), outputs(3));
+    }
+
+    #[test]
+    fn pipo_unknown_power_on() {
+        let mut pipo_register = PIPORegister::with_power_on(4, &mut PowerOn::Unknown);
+        assert_eq!(pipo_register.output_logic().to_string(), "4'bxxxx");
+
+        pipo_register.clock_tick(true);
+        assert_eq!(pipo_register.output_logic().to_string(), "4'bxxxx");
+
+        pipo_register.set_d(&[true, false, true, true]);
+        pipo_register.clock_tick(true);
+        assert_eq!(pipo_register.output_logic().to_string(), "4'b1011");
+    }
 }
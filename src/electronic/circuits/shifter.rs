@@ -1,18 +1,21 @@
 use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{bus_names, indexed, pin, port, Component, Pin};
 use crate::electronic::circuits::mux::Mux2To1;
+use crate::error::Error;
 
 pub struct BarrelShifter {
-    size: u8,
+    size: usize,
     right: bool,
     muxes: Vec<Vec<Mux2To1>>,
 }
 
 impl BarrelShifter {
-    pub fn new(size: u8, right: bool) -> Self {
+    pub fn new(size: usize, right: bool) -> Self {
         let mut muxes = Vec::<Vec<Mux2To1>>::new();
         for _ in 0..size {
             let mut row_muxes = Vec::<Mux2To1>::new();
-            for _ in 0..2_u8.pow(size as u32) {
+            for _ in 0..2_usize.pow(size as u32) {
                 row_muxes.push(Mux2To1::new());
             }
             muxes.push(row_muxes);
@@ -22,49 +25,179 @@ impl BarrelShifter {
     }
 
     pub fn evaluate(&mut self, i: &Bits, s: &Bits) -> (Bits, bool) {
-        let i_length = 2_u8.pow(self.size as u32);
-        if i.len() as u8 != i_length {
-            panic!("Length of i should be {} but is {}", i_length, i.len());
-        }
+        self.try_evaluate(i, s)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        if s.len() as u8 != self.size {
-            panic!("Length of s should be {} but is {}", self.size, s.len());
-        }
+    pub fn try_evaluate(&mut self, i: &Bits, s: &Bits) -> Result<(Bits, bool), Error> {
+        let i_length = 2_usize.pow(self.size as u32);
+        Error::check_width("i", i_length, i.len())?;
+        Error::check_width("s", self.size, s.len())?;
 
-        let mut current_i = i.data();
-        if !self.right {
-            current_i.reverse();
-        }
+        let mut current_i = if self.right {
+            i.iter().collect::<Vec<bool>>()
+        } else {
+            i.iter().rev().collect::<Vec<bool>>()
+        };
+        let mut output = vec![false; i_length];
 
         for (index_s, bit_s) in s.iter().rev().enumerate() {
-            let mut output = Vec::<bool>::new();
-            let number_of_zeros = 2_u8.pow(index_s as u32);
+            let number_of_zeros = 2_usize.pow(index_s as u32);
             for (index_i, bit_i) in current_i.iter().enumerate() {
-                let a1 = if (index_i as u8) < number_of_zeros {
-                    current_i[(i_length - number_of_zeros) as usize + index_i]
+                let a1 = if index_i < number_of_zeros {
+                    current_i[i_length - number_of_zeros + index_i]
                 } else {
-                    current_i[index_i - (number_of_zeros as usize)]
+                    current_i[index_i - number_of_zeros]
                 };
 
                 let a0 = bit_i;
-                let mux_result = self.muxes[index_s][index_i].evaluate(*a0, a1, *bit_s);
-
-                output.push(mux_result);
+                output[index_i] = self.muxes[index_s][index_i].evaluate(*a0, a1, bit_s);
             }
-            current_i = output.clone();
+            std::mem::swap(&mut current_i, &mut output);
         }
 
         if self.right {
             let result = Bits::from_slice_b(&current_i, None);
             let first_bit = result[0];
-            (result, first_bit)
+            Ok((result, first_bit))
         } else {
             current_i.reverse();
             let result = Bits::from_slice_b(&current_i, None);
             let first_bit = result[result.len() - 1];
-            (result, first_bit)
+            Ok((result, first_bit))
         }
     }
+
+    /// Four-valued evaluation on buses given MSB first, returns the shifted bus and the first
+    /// bit.
+    pub fn try_evaluate_logic(
+        &mut self,
+        i: &[Logic],
+        s: &[Logic],
+    ) -> Result<(LBits, Logic), Error> {
+        let i_length = 2_usize.pow(self.size as u32);
+        Error::check_width("i", i_length, i.len())?;
+        Error::check_width("s", self.size, s.len())?;
+
+        let mut current_i = if self.right {
+            i.to_vec()
+        } else {
+            i.iter().rev().copied().collect::<Vec<Logic>>()
+        };
+        let mut output = vec![Logic::Zero; i_length];
+
+        for (index_s, bit_s) in s.iter().rev().enumerate() {
+            let number_of_zeros = 2_usize.pow(index_s as u32);
+            for (index_i, bit_i) in current_i.iter().enumerate() {
+                let a1 = if index_i < number_of_zeros {
+                    current_i[i_length - number_of_zeros + index_i]
+                } else {
+                    current_i[index_i - number_of_zeros]
+                };
+
+                output[index_i] = self.muxes[index_s][index_i].evaluate_logic(*bit_i, a1, *bit_s);
+            }
+            std::mem::swap(&mut current_i, &mut output);
+        }
+
+        if !self.right {
+            current_i.reverse();
+        }
+        let first_bit = if self.right {
+            current_i[0]
+        } else {
+            current_i[current_i.len() - 1]
+        };
+        Ok((LBits::from_vector(current_i), first_bit))
+    }
+}
+
+impl Component for BarrelShifter {
+    fn type_name(&self) -> &'static str {
+        "BarrelShifter"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        let mut inputs = bus_names("i", 2_usize.pow(self.size as u32));
+        inputs.extend(bus_names("s", self.size));
+        inputs
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        let mut outputs = bus_names("o", 2_usize.pow(self.size as u32));
+        outputs.push("first_bit".to_string());
+        outputs
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let (i, s) = inputs.split_at(2_usize.pow(self.size as u32));
+        let (output, first_bit) = self.try_evaluate(&i, &s)?;
+        Ok(output.concat(&Bits::from_slice_b(&[first_bit], None)))
+    }
+
+    fn try_evaluate_logic_bits(&mut self, inputs: &LBits) -> Result<LBits, Error> {
+        self.check_logic_inputs(inputs)?;
+        let inputs = inputs.iter().copied().collect::<Vec<Logic>>();
+        let (i, s) = inputs.split_at(2_usize.pow(self.size as u32));
+        let (output, first_bit) = self.try_evaluate_logic(i, s)?;
+        Ok(output.iter().copied().chain([first_bit]).collect())
+    }
+
+    fn logic_depth(&self) -> usize {
+        self.muxes.iter().map(|row| row[0].logic_depth()).sum()
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        let mut sub_components: Vec<(String, &dyn Component)> = Vec::new();
+        for (i, row) in self.muxes.iter().enumerate() {
+            for (j, component) in row.iter().enumerate() {
+                sub_components.push((format!("muxes[{}][{}]", i, j), component));
+            }
+        }
+        sub_components
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        let mut sub_components: Vec<(String, &mut dyn Component)> = Vec::new();
+        for (i, row) in self.muxes.iter_mut().enumerate() {
+            for (j, component) in row.iter_mut().enumerate() {
+                sub_components.push((format!("muxes[{}][{}]", i, j), component));
+            }
+        }
+        sub_components
+    }
+
+    fn wires(&self) -> Vec<(Pin, Pin)> {
+        // Row `t` rotates by 2^t when `s[t]` is set, on the bits in the order `try_evaluate`
+        // walks them.
+        let length = 2_usize.pow(self.size as u32);
+        let bit = |k: usize| if self.right { length - 1 - k } else { k };
+        let mut current = (0..length)
+            .map(|k| port(&indexed("i", bit(k))))
+            .collect::<Vec<Pin>>();
+        let mut wires = Vec::new();
+        for t in 0..self.size {
+            let shift = 2_usize.pow(t as u32);
+            for k in 0..length {
+                let mux = format!("muxes[{}][{}]", t, k);
+                wires.push((current[k].clone(), pin(&mux, "a")));
+                wires.push((
+                    current[(k + length - shift) % length].clone(),
+                    pin(&mux, "b"),
+                ));
+                wires.push((port(&indexed("s", t)), pin(&mux, "s")));
+            }
+            current = (0..length)
+                .map(|k| pin(&format!("muxes[{}][{}]", t, k), "y"))
+                .collect();
+        }
+        wires.push((current[0].clone(), port("first_bit")));
+        for (k, driver) in current.into_iter().enumerate() {
+            wires.push((driver, port(&indexed("o", bit(k)))));
+        }
+        wires
+    }
 }
 
 #[cfg(test)]
@@ -82,19 +215,13 @@ mod tests {
             for index_s in 0..8 {
                 let s = Bits::from_int(index_s, Some(3));
 
+                let shift = s.to_int() as usize;
+
                 let (result_right, _) = barrel_shiter_right.evaluate(&i, &s);
-                assert!(
-                    ((i.to_int() >> s.to_int()) | (i.to_int() << (8 - s.to_int())) & 0xFF)
-                        % (2u32.pow(8))
-                        == result_right.to_int()
-                );
+                assert!(result_right == (&i >> shift) | (&i << (8 - shift)));
 
                 let (result_left, _) = barrel_shiter_left.evaluate(&i, &s);
-                assert!(
-                    ((i.to_int() << s.to_int()) | (i.to_int() >> (8 - s.to_int())) & 0xFF)
-                        % (2u32.pow(8))
-                        == result_left.to_int()
-                );
+                assert!(result_left == (&i << shift) | (&i >> (8 - shift)));
             }
         }
     }
@@ -118,4 +245,21 @@ mod tests {
 
         barrel_shiter.evaluate(&i, &s);
     }
+
+    #[test]
+    fn barrel_shifter_try_evaluate() {
+        let mut barrel_shiter = BarrelShifter::new(3, true);
+        let i = Bits::from_int(10, Some(8));
+        assert_eq!(
+            barrel_shiter.try_evaluate(&i, &Bits::from_int(2, Some(4))),
+            Err(Error::WidthMismatch {
+                input: "s",
+                expected: 3,
+                actual: 4
+            })
+        );
+        assert!(barrel_shiter
+            .try_evaluate(&i, &Bits::from_int(2, Some(3)))
+            .is_ok());
+    }
 }
@@ -1,2 +1,5 @@
+pub mod netlist;
 pub mod transistor;
+pub mod transmission_gate;
+pub mod tri_state;
 pub mod voltage_levels;
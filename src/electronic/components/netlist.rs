@@ -0,0 +1,600 @@
+use std::collections::HashMap;
+
+use crate::data::bits::Bits;
+use crate::data::logic::Logic;
+use crate::electronic::circuits::component::Component;
+use crate::error::Error;
+
+/// Handle to a node of a [`Netlist`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransistorKind {
+    NMOS,
+    PMOS,
+}
+
+impl TransistorKind {
+    /// Whether the channel is closed for the `gate` value, `X` when the gate is `Z` or `X`.
+    pub fn channel(self, gate: Logic) -> Logic {
+        match self {
+            TransistorKind::NMOS => match gate {
+                Logic::Z => Logic::X,
+                gate => gate,
+            },
+            TransistorKind::PMOS => !gate,
+        }
+    }
+}
+
+/// A transistor of a netlist, connecting `source` and `drain` when its channel is closed.
+#[derive(Clone, Debug)]
+pub struct Device {
+    pub name: String,
+    pub kind: TransistorKind,
+    pub gate: NodeId,
+    pub source: NodeId,
+    pub drain: NodeId,
+}
+
+/// Switch-level description of a cell: named nodes, the VDD and GND rails, and NMOS/PMOS
+/// transistors connected between them.
+///
+/// Transistors are bidirectional switches. [`Netlist::simulate`] solves the node values from
+/// the inputs, repeating until no gate value changes, so feedback such as latches is allowed.
+pub struct Netlist {
+    nodes: Vec<String>,
+    by_name: HashMap<String, NodeId>,
+    devices: Vec<Device>,
+    inputs: Vec<NodeId>,
+    outputs: Vec<NodeId>,
+}
+
+/// Node values computed by [`Netlist::simulate`].
+#[derive(Clone, Debug)]
+pub struct Solution {
+    values: Vec<Logic>,
+    shorts: Vec<NodeId>,
+}
+
+impl Netlist {
+    pub const VDD: NodeId = NodeId(0);
+    pub const GND: NodeId = NodeId(1);
+
+    pub fn new() -> Self {
+        let mut netlist = Netlist {
+            nodes: Vec::new(),
+            by_name: HashMap::new(),
+            devices: Vec::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        };
+        netlist.node("vdd");
+        netlist.node("gnd");
+        netlist
+    }
+
+    /// The node called `name`, created if needed.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.by_name.get(name) {
+            return id;
+        }
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(name.to_string());
+        self.by_name.insert(name.to_string(), id);
+        id
+    }
+
+    pub fn find(&self, name: &str) -> Option<NodeId> {
+        self.by_name.get(name).copied()
+    }
+
+    pub fn node_name(&self, node: NodeId) -> &str {
+        &self.nodes[node.0]
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Declares the node called `name` as an input, driven by the caller of `simulate`.
+    pub fn input(&mut self, name: &str) -> NodeId {
+        let id = self.node(name);
+        self.inputs.push(id);
+        id
+    }
+
+    pub fn output(&mut self, name: &str) -> NodeId {
+        let id = self.node(name);
+        self.outputs.push(id);
+        id
+    }
+
+    pub fn inputs(&self) -> &[NodeId] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[NodeId] {
+        &self.outputs
+    }
+
+    pub fn devices(&self) -> &[Device] {
+        &self.devices
+    }
+
+    pub fn transistor(
+        &mut self,
+        name: &str,
+        kind: TransistorKind,
+        gate: NodeId,
+        source: NodeId,
+        drain: NodeId,
+    ) {
+        self.devices.push(Device {
+            name: name.to_string(),
+            kind,
+            gate,
+            source,
+            drain,
+        });
+    }
+
+    pub fn nmos(&mut self, name: &str, gate: NodeId, source: NodeId, drain: NodeId) {
+        self.transistor(name, TransistorKind::NMOS, gate, source, drain);
+    }
+
+    pub fn pmos(&mut self, name: &str, gate: NodeId, source: NodeId, drain: NodeId) {
+        self.transistor(name, TransistorKind::PMOS, gate, source, drain);
+    }
+
+    /// Solves every node for the values of the inputs, given in declaration order.
+    ///
+    /// Internal nodes start as `X` and are recomputed until they settle. A node connected to
+    /// no driver is `Z` (floating), a node connected to both rails is `X` and reported as a
+    /// short, and a node whose value depends on a transistor with an unknown gate is `X`.
+    pub fn simulate(&self, inputs: &[Logic]) -> Result<Solution, Error> {
+        Error::check_width("inputs", self.inputs.len(), inputs.len())?;
+
+        let mut drivers = vec![None; self.nodes.len()];
+        drivers[Netlist::VDD.0] = Some(Logic::One);
+        drivers[Netlist::GND.0] = Some(Logic::Zero);
+        for (&node, &value) in self.inputs.iter().zip(inputs) {
+            drivers[node.0] = Some(value);
+        }
+
+        let mut values = drivers
+            .iter()
+            .map(|driver| driver.unwrap_or(Logic::X))
+            .collect::<Vec<Logic>>();
+        // Each pass fixes at least one more node along any acyclic path.
+        let iterations = self.nodes.len() + 1;
+        for _ in 0..iterations {
+            let (next, shorts) = self.solve_once(&drivers, &values);
+            if next == values {
+                return Ok(Solution { values, shorts });
+            }
+            values = next;
+        }
+        let (next, _) = self.solve_once(&drivers, &values);
+        let nodes = (0..self.nodes.len())
+            .filter(|&node| next[node] != values[node])
+            .map(|node| self.nodes[node].clone())
+            .collect();
+        Err(Error::NoConvergence { iterations, nodes })
+    }
+
+    /// One relaxation step: groups the nodes connected through closed channels, with gates
+    /// read from `values`, and gives each group the value of its drivers.
+    fn solve_once(&self, drivers: &[Option<Logic>], values: &[Logic]) -> (Vec<Logic>, Vec<NodeId>) {
+        let channels = self
+            .devices
+            .iter()
+            .map(|device| device.kind.channel(values[device.gate.0]))
+            .collect::<Vec<Logic>>();
+        // Only closed channels, then also those that might be closed.
+        let sure = self.drive_groups(drivers, &channels, |channel| channel == Logic::One);
+        let maybe = self.drive_groups(drivers, &channels, |channel| channel != Logic::Zero);
+
+        let mut shorts = Vec::new();
+        let next = (0..self.nodes.len())
+            .map(|node| {
+                let (sure_value, short) = sure[node];
+                if short {
+                    shorts.push(NodeId(node));
+                }
+                if let Some(driver) = drivers[node] {
+                    return driver;
+                }
+                if sure_value == maybe[node].0 {
+                    sure_value
+                } else {
+                    Logic::X
+                }
+            })
+            .collect();
+        (next, shorts)
+    }
+
+    /// For each node, the resolved value of the drivers connected to it through the channels
+    /// accepted by `closed`, and whether that connects drivers of opposite values. A driven
+    /// node is shorted when a device connects it straight to a driver of the opposite value.
+    ///
+    /// Driven nodes (rails and inputs) are sources, not wires: two nodes both connected to VDD
+    /// are not connected to each other.
+    fn drive_groups(
+        &self,
+        drivers: &[Option<Logic>],
+        channels: &[Logic],
+        closed: impl Fn(Logic) -> bool,
+    ) -> Vec<(Logic, bool)> {
+        let closed_devices = self
+            .devices
+            .iter()
+            .zip(channels)
+            .filter(|(_, &channel)| closed(channel))
+            .map(|(device, _)| (device.source.0, device.drain.0))
+            .collect::<Vec<(usize, usize)>>();
+
+        let mut parents = (0..self.nodes.len()).collect::<Vec<usize>>();
+        for &(a, b) in &closed_devices {
+            if drivers[a].is_none() && drivers[b].is_none() {
+                let a = root(&mut parents, a);
+                let b = root(&mut parents, b);
+                parents[a] = b;
+            }
+        }
+
+        let mut groups = vec![(Logic::Z, false); self.nodes.len()];
+        for &(a, b) in &closed_devices {
+            let (node, driver) = match (drivers[a], drivers[b]) {
+                (None, Some(driver)) => (a, driver),
+                (Some(driver), None) => (b, driver),
+                // Driven at both ends: a short across the device itself when they disagree.
+                (Some(x), Some(y)) => {
+                    if x.is_known() && y.is_known() && x != y {
+                        groups[a].1 = true;
+                        groups[b].1 = true;
+                    }
+                    continue;
+                }
+                (None, None) => continue,
+            };
+            let group = root(&mut parents, node);
+            let (value, short) = groups[group];
+            let resolved = value.resolve(driver);
+            let conflict = resolved == Logic::X && value.is_known() && driver.is_known();
+            groups[group] = (resolved, short || conflict);
+        }
+        (0..self.nodes.len())
+            .map(|node| groups[root(&mut parents, node)])
+            .collect()
+    }
+
+    // Standard cells
+
+    pub fn inverter() -> Self {
+        let mut netlist = Netlist::new();
+        let a = netlist.input("a");
+        let y = netlist.output("y");
+        netlist.pmos("pmos", a, Netlist::VDD, y);
+        netlist.nmos("nmos", a, Netlist::GND, y);
+        netlist
+    }
+
+    pub fn nand2() -> Self {
+        let mut netlist = Netlist::new();
+        let a = netlist.input("a");
+        let b = netlist.input("b");
+        let y = netlist.output("y");
+        let n = netlist.node("n");
+        netlist.pmos("pmos_a", a, Netlist::VDD, y);
+        netlist.pmos("pmos_b", b, Netlist::VDD, y);
+        netlist.nmos("nmos_a", a, n, y);
+        netlist.nmos("nmos_b", b, Netlist::GND, n);
+        netlist
+    }
+
+    pub fn nor2() -> Self {
+        let mut netlist = Netlist::new();
+        let a = netlist.input("a");
+        let b = netlist.input("b");
+        let y = netlist.output("y");
+        let p = netlist.node("p");
+        netlist.pmos("pmos_a", a, Netlist::VDD, p);
+        netlist.pmos("pmos_b", b, p, y);
+        netlist.nmos("nmos_a", a, Netlist::GND, y);
+        netlist.nmos("nmos_b", b, Netlist::GND, y);
+        netlist
+    }
+
+    /// Pass gate with a built-in inverter for the PMOS gate.
+    pub fn transmission_gate() -> Self {
+        let mut netlist = Netlist::new();
+        let a = netlist.input("a");
+        let enable = netlist.input("enable");
+        let y = netlist.output("y");
+        let enable_bar = netlist.node("enable_bar");
+        netlist.pmos("not.pmos", enable, Netlist::VDD, enable_bar);
+        netlist.nmos("not.nmos", enable, Netlist::GND, enable_bar);
+        netlist.nmos("nmos", enable, a, y);
+        netlist.pmos("pmos", enable_bar, a, y);
+        netlist
+    }
+
+    pub fn tri_state_buffer() -> Self {
+        let mut netlist = Netlist::new();
+        let a = netlist.input("a");
+        let enable = netlist.input("enable");
+        let y = netlist.output("y");
+        let a_bar = netlist.node("a_bar");
+        let enable_bar = netlist.node("enable_bar");
+        let p = netlist.node("p");
+        let n = netlist.node("n");
+        netlist.pmos("not_signal.pmos", a, Netlist::VDD, a_bar);
+        netlist.nmos("not_signal.nmos", a, Netlist::GND, a_bar);
+        netlist.pmos("not_enable.pmos", enable, Netlist::VDD, enable_bar);
+        netlist.nmos("not_enable.nmos", enable, Netlist::GND, enable_bar);
+        netlist.pmos("pmos_signal", a_bar, Netlist::VDD, p);
+        netlist.pmos("pmos_enable", enable_bar, p, y);
+        netlist.nmos("nmos_enable", enable, y, n);
+        netlist.nmos("nmos_signal", a_bar, n, Netlist::GND);
+        netlist
+    }
+}
+
+fn root(parents: &mut [usize], node: usize) -> usize {
+    let mut root = node;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    let mut node = node;
+    while parents[node] != root {
+        let parent = parents[node];
+        parents[node] = root;
+        node = parent;
+    }
+    root
+}
+
+impl Default for Netlist {
+    fn default() -> Self {
+        Netlist::new()
+    }
+}
+
+impl Solution {
+    pub fn value(&self, node: NodeId) -> Logic {
+        self.values[node.0]
+    }
+
+    /// Nodes connected to VDD and GND at the same time, including driven nodes (rails and
+    /// inputs) connected to one another.
+    pub fn shorts(&self) -> &[NodeId] {
+        &self.shorts
+    }
+
+    pub fn is_floating(&self, node: NodeId) -> bool {
+        self.value(node) == Logic::Z
+    }
+}
+
+impl Component for Netlist {
+    fn type_name(&self) -> &'static str {
+        "Netlist"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        self.inputs
+            .iter()
+            .map(|&node| self.node_name(node).to_string())
+            .collect()
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        self.outputs
+            .iter()
+            .map(|&node| self.node_name(node).to_string())
+            .collect()
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        let inputs = inputs.iter().map(Logic::from).collect::<Vec<Logic>>();
+        let solution = self.simulate(&inputs)?;
+        // A short between driven nodes does not reach the outputs but still burns the cell.
+        let driven = |node: &&NodeId| {
+            **node == Netlist::VDD || **node == Netlist::GND || self.inputs.contains(node)
+        };
+        if let Some(node) = solution.shorts().iter().find(driven) {
+            return Err(Error::ShortCircuit {
+                node: self.node_name(*node).to_string(),
+            });
+        }
+        self.outputs
+            .iter()
+            .map(|&node| {
+                let name = self.node_name(node).to_string();
+                match solution.value(node) {
+                    Logic::Z => Err(Error::FloatingNode { node: name }),
+                    _ if solution.shorts().contains(&node) => {
+                        Err(Error::ShortCircuit { node: name })
+                    }
+                    value => value.to_bool().ok_or(Error::UnknownValue { node: name }),
+                }
+            })
+            .collect()
+    }
+
+    fn own_transistors(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Counted as a single cell, whatever its transistors implement.
+    fn logic_depth(&self) -> usize {
+        usize::from(!self.devices.is_empty())
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        Vec::new()
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::logic_gates::nand::Nand;
+    use crate::electronic::circuits::logic_gates::nor::Nor;
+    use crate::electronic::components::transmission_gate::TransmissionGate;
+    use crate::electronic::components::tri_state::TriStateBuffer;
+
+    #[test]
+    fn netlist_inverter() {
+        let netlist = Netlist::inverter();
+        let y = netlist.find("y").unwrap();
+        let value = |a| netlist.simulate(&[a]).unwrap().value(y);
+        assert_eq!(value(Logic::Zero), Logic::One);
+        assert_eq!(value(Logic::One), Logic::Zero);
+        assert_eq!(value(Logic::X), Logic::X);
+        assert_eq!(value(Logic::Z), Logic::X);
+    }
+
+    #[test]
+    fn netlist_matches_gates() {
+        let mut nand2 = Netlist::nand2();
+        let mut nor2 = Netlist::nor2();
+        for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+            let inputs = Bits::from_slice_b(&[a, b], None);
+            assert!(nand2.evaluate_bits(&inputs)[0] == Nand::new().evaluate(a, b));
+            assert!(nor2.evaluate_bits(&inputs)[0] == Nor::new().evaluate(a, b));
+        }
+    }
+
+    #[test]
+    fn netlist_high_impedance_cells() {
+        let transmission_gate = Netlist::transmission_gate();
+        let tri_state_buffer = Netlist::tri_state_buffer();
+        for a in [false, true] {
+            for enable in [false, true] {
+                let inputs = [Logic::from(a), Logic::from(enable)];
+                let y = transmission_gate.find("y").unwrap();
+                assert_eq!(
+                    transmission_gate.simulate(&inputs).unwrap().value(y),
+                    TransmissionGate::new().evaluate(a, enable)
+                );
+                let y = tri_state_buffer.find("y").unwrap();
+                assert_eq!(
+                    tri_state_buffer.simulate(&inputs).unwrap().value(y),
+                    TriStateBuffer::new().evaluate(a, enable)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn netlist_short() {
+        let mut netlist = Netlist::new();
+        let up = netlist.input("up");
+        let down = netlist.input("down");
+        let y = netlist.output("y");
+        netlist.pmos("pmos", up, Netlist::VDD, y);
+        netlist.nmos("nmos", down, Netlist::GND, y);
+
+        let solution = netlist.simulate(&[Logic::Zero, Logic::One]).unwrap();
+        assert_eq!(solution.value(y), Logic::X);
+        assert_eq!(solution.shorts(), &[y]);
+        assert_eq!(
+            netlist.evaluate_bits(&Bits::from_int(0b00, Some(2))),
+            Bits::from_int(1, Some(1))
+        );
+        assert_eq!(
+            netlist.try_evaluate_bits(&Bits::from_int(0b01, Some(2))),
+            Err(Error::ShortCircuit {
+                node: "y".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn netlist_rail_short() {
+        let mut netlist = Netlist::new();
+        let enable = netlist.input("enable");
+        let y = netlist.output("y");
+        netlist.nmos("nmos", enable, Netlist::VDD, Netlist::GND);
+        netlist.pmos("pmos", enable, Netlist::VDD, y);
+
+        let solution = netlist.simulate(&[Logic::One]).unwrap();
+        assert_eq!(solution.shorts(), &[Netlist::VDD, Netlist::GND]);
+        assert_eq!(
+            netlist.try_evaluate_bits(&Bits::ones(1)),
+            Err(Error::ShortCircuit {
+                node: "vdd".to_string()
+            })
+        );
+        assert!(netlist
+            .simulate(&[Logic::Zero])
+            .unwrap()
+            .shorts()
+            .is_empty());
+        assert_eq!(
+            netlist.try_evaluate_bits(&Bits::zeros(1)),
+            Ok(Bits::ones(1))
+        );
+    }
+
+    #[test]
+    fn netlist_floating() {
+        let mut netlist = Netlist::new();
+        let enable = netlist.input("enable");
+        let y = netlist.output("y");
+        netlist.nmos("nmos", enable, Netlist::GND, y);
+
+        assert!(netlist.simulate(&[Logic::Zero]).unwrap().is_floating(y));
+        assert_eq!(
+            netlist.simulate(&[Logic::One]).unwrap().value(y),
+            Logic::Zero
+        );
+        assert_eq!(
+            netlist.try_evaluate_bits(&Bits::zeros(1)),
+            Err(Error::FloatingNode {
+                node: "y".to_string()
+            })
+        );
+    }
+
+    fn nor_into(netlist: &mut Netlist, name: &str, a: NodeId, b: NodeId, y: NodeId) {
+        let p = netlist.node(&format!("{}.p", name));
+        netlist.pmos(&format!("{}.pmos_a", name), a, Netlist::VDD, p);
+        netlist.pmos(&format!("{}.pmos_b", name), b, p, y);
+        netlist.nmos(&format!("{}.nmos_a", name), a, Netlist::GND, y);
+        netlist.nmos(&format!("{}.nmos_b", name), b, Netlist::GND, y);
+    }
+
+    #[test]
+    fn netlist_feedback() {
+        // SR latch of two cross-coupled NORs: unknown until set or reset.
+        let mut netlist = Netlist::new();
+        let set = netlist.input("set");
+        let reset = netlist.input("reset");
+        let q = netlist.output("q");
+        let q_bar = netlist.output("q_bar");
+        nor_into(&mut netlist, "nor0", reset, q_bar, q);
+        nor_into(&mut netlist, "nor1", set, q, q_bar);
+
+        let solution = netlist.simulate(&[Logic::Zero, Logic::Zero]).unwrap();
+        assert_eq!(solution.value(q), Logic::X);
+        let solution = netlist.simulate(&[Logic::One, Logic::Zero]).unwrap();
+        assert_eq!(solution.value(q), Logic::One);
+        assert_eq!(solution.value(q_bar), Logic::Zero);
+        assert_eq!(
+            netlist.try_evaluate_bits(&Bits::zeros(2)),
+            Err(Error::UnknownValue {
+                node: "q".to_string()
+            })
+        );
+    }
+}
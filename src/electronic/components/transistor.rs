@@ -1,3 +1,10 @@
+use crate::data::bits::Bits;
+use crate::data::logic::Logic;
+use crate::electronic::circuits::component::{names, Component};
+use crate::electronic::fault::{stuck, Fault};
+use crate::electronic::timing::{Delay, NMOS_DELAY, PMOS_DELAY};
+use crate::error::Error;
+
 pub trait Transistor {
     fn apply_control_signal(&mut self, signal: bool);
     fn connect_source(&mut self, source: bool);
@@ -6,22 +13,92 @@ pub trait Transistor {
     fn drain(&self) -> bool {
         self.is_conducting()
     }
+
+    fn apply_control_logic(&mut self, signal: Logic);
+
+    /// Whether the channel is closed for the current control signal, regardless of the source.
+    /// `X` when the control signal is `Z` or `X`.
+    fn channel(&self) -> Logic;
+
+    /// Time for the node the channel drives to rise or fall after the control signal changes.
+    fn delay(&self) -> Delay;
+
+    fn set_delay(&mut self, delay: Delay);
+
+    /// Number of changes of the control signal since creation or the last reset.
+    fn toggles(&self) -> u64;
+
+    fn reset_toggles(&mut self);
 }
 
-#[derive(Default)]
-pub struct TransistorState {
-    control_gate: bool,
+/// Output of a CMOS stage given the conduction of its pull-up and pull-down networks.
+///
+/// Only one network conducting gives a driven value. Both conducting is a short and neither
+/// conducting leaves the output undetermined, both report `X`, as does any unknown network.
+pub(crate) fn cmos_output(pull_up: Logic, pull_down: Logic) -> Logic {
+    match (pull_up, pull_down) {
+        (Logic::One, Logic::Zero) => Logic::One,
+        (Logic::Zero, Logic::One) => Logic::Zero,
+        _ => Logic::X,
+    }
+}
+
+/// Output of a CMOS stage that may be disconnected: like [`cmos_output`], but neither network
+/// conducting releases the output to `Z`.
+pub(crate) fn tri_state_output(pull_up: Logic, pull_down: Logic) -> Logic {
+    match (pull_up, pull_down) {
+        (Logic::Zero, Logic::Zero) => Logic::Z,
+        _ => cmos_output(pull_up, pull_down),
+    }
+}
+
+pub(crate) struct TransistorState {
+    control_gate: Logic,
     source: bool,
+    delay: Delay,
+    toggles: u64,
+    fault: Option<Fault>,
 }
 
 impl TransistorState {
+    pub fn new(delay: Delay) -> Self {
+        TransistorState {
+            control_gate: Logic::default(),
+            source: false,
+            delay,
+            toggles: 0,
+            fault: None,
+        }
+    }
+
     pub fn apply_control_signal(&mut self, signal: bool) {
+        self.apply_control_logic(Logic::from(signal));
+    }
+
+    pub fn apply_control_logic(&mut self, signal: Logic) {
+        if signal != self.control_gate {
+            self.toggles += 1;
+        }
         self.control_gate = signal;
     }
 
     pub fn connect_source(&mut self, source: bool) {
         self.source = source;
     }
+
+    /// Control signal seen by the channel, after a stuck-at fault.
+    fn control(&self) -> Logic {
+        stuck(self.control_gate, self.fault)
+    }
+
+    /// `channel` of a healthy transistor, after a stuck-open or stuck-short fault.
+    fn faulty_channel(&self, channel: Logic) -> Logic {
+        match self.fault {
+            Some(Fault::StuckOpen) => Logic::Zero,
+            Some(Fault::StuckShort) => Logic::One,
+            _ => channel,
+        }
+    }
 }
 
 pub struct NMOSTransistor {
@@ -31,11 +108,17 @@ pub struct NMOSTransistor {
 impl NMOSTransistor {
     pub fn new() -> Self {
         NMOSTransistor {
-            state: TransistorState::default(),
+            state: TransistorState::new(Delay::new(NMOS_DELAY, NMOS_DELAY)),
         }
     }
 }
 
+impl Default for NMOSTransistor {
+    fn default() -> Self {
+        NMOSTransistor::new()
+    }
+}
+
 impl Transistor for NMOSTransistor {
     fn apply_control_signal(&mut self, signal: bool) {
         self.state.apply_control_signal(signal);
@@ -46,7 +129,87 @@ impl Transistor for NMOSTransistor {
     }
 
     fn is_conducting(&self) -> bool {
-        self.state.control_gate && self.state.source
+        self.channel() == Logic::One && self.state.source
+    }
+
+    fn apply_control_logic(&mut self, signal: Logic) {
+        self.state.apply_control_logic(signal);
+    }
+
+    fn channel(&self) -> Logic {
+        self.state.faulty_channel(match self.state.control() {
+            Logic::Z => Logic::X,
+            control => control,
+        })
+    }
+
+    fn delay(&self) -> Delay {
+        self.state.delay
+    }
+
+    fn set_delay(&mut self, delay: Delay) {
+        self.state.delay = delay;
+    }
+
+    fn toggles(&self) -> u64 {
+        self.state.toggles
+    }
+
+    fn reset_toggles(&mut self) {
+        self.state.toggles = 0;
+    }
+}
+
+impl Component for NMOSTransistor {
+    fn type_name(&self) -> &'static str {
+        "NMOSTransistor"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["gate", "source"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["drain"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        self.apply_control_signal(inputs[0]);
+        self.connect_source(inputs[1]);
+        Ok(Bits::from_slice_b(&[self.drain()], None))
+    }
+
+    fn own_toggles(&self) -> u64 {
+        self.toggles()
+    }
+
+    fn reset_own_toggles(&mut self) {
+        self.reset_toggles();
+    }
+
+    fn own_transistors(&self) -> usize {
+        1
+    }
+
+    fn supported_faults(&self) -> &'static [Fault] {
+        Fault::ALL
+    }
+
+    fn fault(&self) -> Option<Fault> {
+        self.state.fault
+    }
+
+    fn set_fault(&mut self, fault: Option<Fault>) {
+        self.state.fault = fault;
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        Vec::new()
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        Vec::new()
     }
 }
 
@@ -57,11 +220,17 @@ pub struct PMOSTransistor {
 impl PMOSTransistor {
     pub fn new() -> Self {
         PMOSTransistor {
-            state: TransistorState::default(),
+            state: TransistorState::new(Delay::new(PMOS_DELAY, PMOS_DELAY)),
         }
     }
 }
 
+impl Default for PMOSTransistor {
+    fn default() -> Self {
+        PMOSTransistor::new()
+    }
+}
+
 impl Transistor for PMOSTransistor {
     fn apply_control_signal(&mut self, signal: bool) {
         self.state.apply_control_signal(signal);
@@ -72,7 +241,84 @@ impl Transistor for PMOSTransistor {
     }
 
     fn is_conducting(&self) -> bool {
-        !self.state.control_gate && self.state.source
+        self.channel() == Logic::One && self.state.source
+    }
+
+    fn apply_control_logic(&mut self, signal: Logic) {
+        self.state.apply_control_logic(signal);
+    }
+
+    fn channel(&self) -> Logic {
+        self.state.faulty_channel(!self.state.control())
+    }
+
+    fn delay(&self) -> Delay {
+        self.state.delay
+    }
+
+    fn set_delay(&mut self, delay: Delay) {
+        self.state.delay = delay;
+    }
+
+    fn toggles(&self) -> u64 {
+        self.state.toggles
+    }
+
+    fn reset_toggles(&mut self) {
+        self.state.toggles = 0;
+    }
+}
+
+impl Component for PMOSTransistor {
+    fn type_name(&self) -> &'static str {
+        "PMOSTransistor"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["gate", "source"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["drain"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        self.apply_control_signal(inputs[0]);
+        self.connect_source(inputs[1]);
+        Ok(Bits::from_slice_b(&[self.drain()], None))
+    }
+
+    fn own_toggles(&self) -> u64 {
+        self.toggles()
+    }
+
+    fn reset_own_toggles(&mut self) {
+        self.reset_toggles();
+    }
+
+    fn own_transistors(&self) -> usize {
+        1
+    }
+
+    fn supported_faults(&self) -> &'static [Fault] {
+        Fault::ALL
+    }
+
+    fn fault(&self) -> Option<Fault> {
+        self.state.fault
+    }
+
+    fn set_fault(&mut self, fault: Option<Fault>) {
+        self.state.fault = fault;
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        Vec::new()
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        Vec::new()
     }
 }
 
@@ -209,4 +455,37 @@ mod tests {
         pmos.apply_control_signal(true);
         assert!(!pmos.is_conducting());
     }
+
+    #[test]
+    fn nmos_channel() {
+        let mut nmos = NMOSTransistor::new();
+        nmos.apply_control_logic(Logic::One);
+        assert_eq!(nmos.channel(), Logic::One);
+        nmos.apply_control_logic(Logic::Zero);
+        assert_eq!(nmos.channel(), Logic::Zero);
+        nmos.apply_control_logic(Logic::Z);
+        assert_eq!(nmos.channel(), Logic::X);
+    }
+
+    #[test]
+    fn pmos_channel() {
+        let mut pmos = PMOSTransistor::new();
+        pmos.apply_control_logic(Logic::One);
+        assert_eq!(pmos.channel(), Logic::Zero);
+        pmos.apply_control_logic(Logic::Zero);
+        assert_eq!(pmos.channel(), Logic::One);
+        pmos.apply_control_logic(Logic::X);
+        assert_eq!(pmos.channel(), Logic::X);
+    }
+
+    #[test]
+    fn cmos_output_networks() {
+        assert_eq!(cmos_output(Logic::One, Logic::Zero), Logic::One);
+        assert_eq!(cmos_output(Logic::Zero, Logic::One), Logic::Zero);
+        assert_eq!(cmos_output(Logic::One, Logic::One), Logic::X);
+        assert_eq!(cmos_output(Logic::Zero, Logic::Zero), Logic::X);
+        assert_eq!(cmos_output(Logic::X, Logic::Zero), Logic::X);
+        assert_eq!(tri_state_output(Logic::Zero, Logic::Zero), Logic::Z);
+        assert_eq!(tri_state_output(Logic::One, Logic::Zero), Logic::One);
+    }
 }
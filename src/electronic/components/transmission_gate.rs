@@ -0,0 +1,107 @@
+use crate::data::bits::Bits;
+use crate::data::logic::Logic;
+use crate::electronic::circuits::component::{names, Component};
+use crate::electronic::components::transistor::{NMOSTransistor, PMOSTransistor, Transistor};
+use crate::error::Error;
+
+/// CMOS pass gate: an NMOS and a PMOS in parallel, with complementary gates, pass the input
+/// through when enabled and leave the output floating (`Z`) otherwise.
+pub struct TransmissionGate {
+    nmos: NMOSTransistor,
+    pmos: PMOSTransistor,
+}
+
+impl TransmissionGate {
+    pub fn new() -> Self {
+        TransmissionGate {
+            nmos: NMOSTransistor::new(),
+            pmos: PMOSTransistor::new(),
+        }
+    }
+
+    pub fn evaluate(&mut self, signal: bool, enable: bool) -> Logic {
+        self.evaluate_logic(Logic::from(signal), Logic::from(enable))
+    }
+
+    pub fn evaluate_logic(&mut self, signal: Logic, enable: Logic) -> Logic {
+        self.nmos.apply_control_logic(enable);
+        self.pmos.apply_control_logic(!enable);
+
+        match self.nmos.channel() | self.pmos.channel() {
+            Logic::One => signal,
+            Logic::Zero => Logic::Z,
+            // Unknown whether the output is connected, only a floating input stays known.
+            _ if signal == Logic::Z => Logic::Z,
+            _ => Logic::X,
+        }
+    }
+}
+
+impl Default for TransmissionGate {
+    fn default() -> Self {
+        TransmissionGate::new()
+    }
+}
+
+impl Component for TransmissionGate {
+    fn type_name(&self) -> &'static str {
+        "TransmissionGate"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "enable"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        match self.evaluate(inputs[0], inputs[1]).to_bool() {
+            Some(value) => Ok(Bits::from_slice_b(&[value], None)),
+            None => Err(Error::FloatingNode {
+                node: "y".to_string(),
+            }),
+        }
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("nmos".to_string(), &self.nmos as &dyn Component),
+            ("pmos".to_string(), &self.pmos as &dyn Component),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            ("nmos".to_string(), &mut self.nmos as &mut dyn Component),
+            ("pmos".to_string(), &mut self.pmos as &mut dyn Component),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transmission_gate_evaluate() {
+        let mut transmission_gate = TransmissionGate::new();
+        assert_eq!(transmission_gate.evaluate(true, true), Logic::One);
+        assert_eq!(transmission_gate.evaluate(false, true), Logic::Zero);
+        assert_eq!(transmission_gate.evaluate(true, false), Logic::Z);
+        assert_eq!(transmission_gate.evaluate(false, false), Logic::Z);
+        assert_eq!(
+            transmission_gate.evaluate_logic(Logic::One, Logic::X),
+            Logic::X
+        );
+        assert_eq!(
+            transmission_gate.evaluate_logic(Logic::X, Logic::One),
+            Logic::X
+        );
+        assert!(transmission_gate
+            .try_evaluate_bits(&Bits::from_int(0b10, Some(2)))
+            .is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::data::bits::Bits;
+use crate::data::logic::Logic;
+use crate::electronic::circuits::component::{names, Component};
+use crate::electronic::circuits::logic_gates::not::Not;
+use crate::electronic::components::transistor::{
+    tri_state_output, NMOSTransistor, PMOSTransistor, Transistor,
+};
+use crate::error::Error;
+
+/// Non-inverting tri-state buffer: drives the input when enabled, releases the output (`Z`)
+/// otherwise, so several buffers can share a bus.
+///
+/// An inverter followed by a clocked inverter: two PMOS in series to VDD and two NMOS in
+/// series to GND, the outer ones gated by the enable signal.
+pub struct TriStateBuffer {
+    not_signal: Not,
+    not_enable: Not,
+    pmos_signal: PMOSTransistor,
+    pmos_enable: PMOSTransistor,
+    nmos_enable: NMOSTransistor,
+    nmos_signal: NMOSTransistor,
+}
+
+impl TriStateBuffer {
+    pub fn new() -> Self {
+        TriStateBuffer {
+            not_signal: Not::new(),
+            not_enable: Not::new(),
+            pmos_signal: PMOSTransistor::new(),
+            pmos_enable: PMOSTransistor::new(),
+            nmos_enable: NMOSTransistor::new(),
+            nmos_signal: NMOSTransistor::new(),
+        }
+    }
+
+    pub fn evaluate(&mut self, signal: bool, enable: bool) -> Logic {
+        self.evaluate_logic(Logic::from(signal), Logic::from(enable))
+    }
+
+    pub fn evaluate_logic(&mut self, signal: Logic, enable: Logic) -> Logic {
+        let not_signal = self.not_signal.evaluate_logic(signal);
+        let not_enable = self.not_enable.evaluate_logic(enable);
+
+        self.pmos_signal.apply_control_logic(not_signal);
+        self.pmos_enable.apply_control_logic(not_enable);
+        self.nmos_enable.apply_control_logic(enable);
+        self.nmos_signal.apply_control_logic(not_signal);
+
+        let pull_up = self.pmos_signal.channel() & self.pmos_enable.channel();
+        let pull_down = self.nmos_enable.channel() & self.nmos_signal.channel();
+        tri_state_output(pull_up, pull_down)
+    }
+}
+
+impl Default for TriStateBuffer {
+    fn default() -> Self {
+        TriStateBuffer::new()
+    }
+}
+
+impl Component for TriStateBuffer {
+    fn type_name(&self) -> &'static str {
+        "TriStateBuffer"
+    }
+
+    fn input_names(&self) -> Vec<String> {
+        names(&["a", "enable"])
+    }
+
+    fn output_names(&self) -> Vec<String> {
+        names(&["y"])
+    }
+
+    fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+        self.check_inputs(inputs)?;
+        match self.evaluate(inputs[0], inputs[1]).to_bool() {
+            Some(value) => Ok(Bits::from_slice_b(&[value], None)),
+            None => Err(Error::FloatingNode {
+                node: "y".to_string(),
+            }),
+        }
+    }
+
+    fn logic_depth(&self) -> usize {
+        // The output stage is one more level after the inverters.
+        self.not_signal.logic_depth() + 1
+    }
+
+    fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+        vec![
+            ("not_signal".to_string(), &self.not_signal as &dyn Component),
+            ("not_enable".to_string(), &self.not_enable as &dyn Component),
+            (
+                "pmos_signal".to_string(),
+                &self.pmos_signal as &dyn Component,
+            ),
+            (
+                "pmos_enable".to_string(),
+                &self.pmos_enable as &dyn Component,
+            ),
+            (
+                "nmos_enable".to_string(),
+                &self.nmos_enable as &dyn Component,
+            ),
+            (
+                "nmos_signal".to_string(),
+                &self.nmos_signal as &dyn Component,
+            ),
+        ]
+    }
+
+    fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+        vec![
+            (
+                "not_signal".to_string(),
+                &mut self.not_signal as &mut dyn Component,
+            ),
+            (
+                "not_enable".to_string(),
+                &mut self.not_enable as &mut dyn Component,
+            ),
+            (
+                "pmos_signal".to_string(),
+                &mut self.pmos_signal as &mut dyn Component,
+            ),
+            (
+                "pmos_enable".to_string(),
+                &mut self.pmos_enable as &mut dyn Component,
+            ),
+            (
+                "nmos_enable".to_string(),
+                &mut self.nmos_enable as &mut dyn Component,
+            ),
+            (
+                "nmos_signal".to_string(),
+                &mut self.nmos_signal as &mut dyn Component,
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tri_state_buffer_evaluate() {
+        let mut buffer = TriStateBuffer::new();
+        assert_eq!(buffer.evaluate(true, true), Logic::One);
+        assert_eq!(buffer.evaluate(false, true), Logic::Zero);
+        assert_eq!(buffer.evaluate(true, false), Logic::Z);
+        assert_eq!(buffer.evaluate(false, false), Logic::Z);
+        assert_eq!(buffer.evaluate_logic(Logic::X, Logic::Zero), Logic::Z);
+        assert_eq!(buffer.evaluate_logic(Logic::One, Logic::X), Logic::X);
+    }
+
+    #[test]
+    fn tri_state_shared_bus() {
+        let mut drivers = [TriStateBuffer::new(), TriStateBuffer::new()];
+        let mut bus = |values: [bool; 2], enables: [bool; 2]| {
+            drivers
+                .iter_mut()
+                .zip(values.iter().zip(enables))
+                .map(|(driver, (&value, enable))| driver.evaluate(value, enable))
+                .fold(Logic::Z, Logic::resolve)
+        };
+        assert_eq!(bus([true, false], [true, false]), Logic::One);
+        assert_eq!(bus([true, false], [false, true]), Logic::Zero);
+        assert_eq!(bus([true, false], [false, false]), Logic::Z);
+        assert_eq!(bus([true, false], [true, true]), Logic::X);
+        assert_eq!(bus([true, true], [true, true]), Logic::One);
+    }
+}
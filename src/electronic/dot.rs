@@ -0,0 +1,679 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::{join_path, Component, Pin};
+use crate::error::Error;
+
+/// What [`dot`] draws in colour, besides the values of [`evaluate_dot`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Highlight {
+    #[default]
+    None,
+    /// The path with the most gate levels from an input to an output, drawn nodes counting
+    /// their `logic_depth`.
+    CriticalPath,
+}
+
+/// How [`dot`] draws a circuit.
+#[derive(Clone, Debug, Default)]
+pub struct DotOptions {
+    /// Levels of sub-circuits below the top opened as clusters, deeper ones are drawn as a
+    /// single node. All of them, down to the gates, when `None`.
+    pub depth: Option<usize>,
+    pub highlight: Highlight,
+}
+
+impl DotOptions {
+    pub fn new() -> Self {
+        DotOptions::default()
+    }
+
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub fn with_critical_path(mut self) -> Self {
+        self.highlight = Highlight::CriticalPath;
+        self
+    }
+}
+
+/// Colour of the highlighted path where no value colours it, one the values do not use. The
+/// path is also drawn thicker, so it stays visible on wires coloured by their value.
+const HIGHLIGHT: &str = "orange";
+const HIGHLIGHT_STYLE: &str = "penwidth=3, style=bold";
+
+/// Graphviz schematic of `component`, left to right from its inputs to its outputs.
+///
+/// Gates, and sub-circuits below `options.depth`, are record nodes with their input ports on
+/// the left and output ports on the right. Opened sub-circuits are clusters labelled with
+/// their field name and type, and wires go straight from the gate driving them to every gate
+/// they load, through any number of cluster boundaries.
+pub fn dot(component: &dyn Component, options: &DotOptions) -> String {
+    draw(component, options, None)
+}
+
+/// Evaluates `component` on `inputs` and draws it like [`dot`], every wire labelled and
+/// coloured with the value its driver outputs.
+///
+/// Signal values are not kept by the circuits, so every drawn child is evaluated again on the
+/// values reaching it. This is a real evaluation, as by `try_evaluate_bits`: a latch enabled
+/// by `inputs` takes the new data and keeps it afterwards.
+pub fn evaluate_dot(
+    component: &mut dyn Component,
+    inputs: &Bits,
+    options: &DotOptions,
+) -> Result<String, Error> {
+    component.check_inputs(inputs)?;
+    let inputs = LBits::from(inputs.clone());
+    let outputs = component.try_evaluate_logic_bits(&inputs)?;
+    let mut values = HashMap::new();
+    evaluate(
+        component,
+        "",
+        &inputs,
+        &outputs,
+        0,
+        options.depth,
+        &mut values,
+    )?;
+    Ok(draw(&*component, options, Some(values)))
+}
+
+fn draw(
+    component: &dyn Component,
+    options: &DotOptions,
+    values: Option<HashMap<(String, String), Logic>>,
+) -> String {
+    let mut schematic = Schematic {
+        depth: options.depth,
+        show_values: values.is_some(),
+        values: values.unwrap_or_default(),
+        ..Schematic::default()
+    };
+    let mut text = String::new();
+    writeln!(text, "digraph \"{}\" {{", escape(component.type_name())).unwrap();
+    writeln!(text, "    rankdir=LR;").unwrap();
+    writeln!(text, "    node [shape=record];").unwrap();
+    for name in &component.input_names() {
+        let node = schematic.node(format!("input {}", name), 0);
+        schematic.driver(("", name), End { node, port: None });
+    }
+    for name in component.output_names() {
+        let node = schematic.node(format!("output {}", name), 0);
+        schematic.load(("", &name), End { node, port: None });
+    }
+    schematic.collect(component, "", "", 0, 1);
+    text.push_str(&schematic.text);
+
+    let edges = schematic.edges();
+    let path = match options.highlight {
+        Highlight::CriticalPath => schematic.critical_path(&edges),
+        _ => Vec::new(),
+    };
+    let on_path = |node: usize| {
+        path.iter().any(|&edge| edges[edge].to.node == node)
+            || path.iter().any(|&edge| edges[edge].from.node == node)
+    };
+
+    for (kind, ports) in [
+        ("input", component.input_names()),
+        ("output", component.output_names()),
+    ] {
+        let rank = if kind == "input" { "source" } else { "sink" };
+        let mut line = format!("    {{ rank={};", rank);
+        for name in ports {
+            let node = schematic.index[&format!("{} {}", kind, name)];
+            let color = if on_path(node) {
+                format!(", color={}", HIGHLIGHT)
+            } else {
+                String::new()
+            };
+            write!(
+                line,
+                " \"{} {}\" [shape=oval, label=\"{}\"{}];",
+                kind,
+                escape(&name),
+                escape(&name),
+                color
+            )
+            .unwrap();
+        }
+        writeln!(text, "{} }}", line).unwrap();
+    }
+    for (node, id) in schematic.nodes.iter().enumerate() {
+        if on_path(node) && !id.starts_with("input ") && !id.starts_with("output ") {
+            writeln!(
+                text,
+                "    \"{}\" [color={}, {}];",
+                escape(id),
+                HIGHLIGHT,
+                HIGHLIGHT_STYLE
+            )
+            .unwrap();
+        }
+    }
+
+    for (i, edge) in edges.iter().enumerate() {
+        let mut attributes = Vec::new();
+        if let Some(value) = edge.value {
+            let color = match value {
+                Logic::One => "red",
+                Logic::Zero => "blue",
+                Logic::Z | Logic::X => "gray",
+            };
+            attributes.push(format!("label=\"{}\"", value.to_char()));
+            attributes.push(format!("color={}", color));
+            attributes.push(format!("fontcolor={}", color));
+        } else if path.contains(&i) {
+            attributes.push(format!("color={}", HIGHLIGHT));
+        }
+        if path.contains(&i) {
+            attributes.push(HIGHLIGHT_STYLE.to_string());
+        }
+        let attributes = match attributes.is_empty() {
+            true => String::new(),
+            false => format!(" [{}]", attributes.join(", ")),
+        };
+        writeln!(
+            text,
+            "    {} -> {}{};",
+            schematic.end(&edge.from),
+            schematic.end(&edge.to),
+            attributes
+        )
+        .unwrap();
+    }
+    writeln!(text, "}}").unwrap();
+    text
+}
+
+fn expanded(component: &dyn Component, level: usize, depth: Option<usize>) -> bool {
+    !component.wires().is_empty() && depth.is_none_or(|depth| level <= depth)
+}
+
+/// Whether the child `name` is connected, parts only some variants use are left out like the
+/// `not` of a two-input `Decoder`.
+fn used(wires: &[(Pin, Pin)], name: &str) -> bool {
+    let used = |pin: &Pin| matches!(pin, Pin::Child(child, _) if child == name);
+    wires.iter().any(|(from, to)| used(from) || used(to))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Text of a record field, where braces, bars, angle brackets and spaces are record syntax
+/// and quotes end the label.
+fn escape_record(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if "{}|<> \"".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Port value of every drawn component, by path and port name, the children of an opened one
+/// being evaluated in turns until they settle. Children with unknown inputs wait until the
+/// others settle, so a latch is not clocked on a guess.
+fn evaluate(
+    component: &mut dyn Component,
+    path: &str,
+    inputs: &LBits,
+    outputs: &LBits,
+    level: usize,
+    depth: Option<usize>,
+    values: &mut HashMap<(String, String), Logic>,
+) -> Result<(), Error> {
+    for (name, &value) in component.input_names().iter().zip(inputs.iter()) {
+        values.insert((path.to_string(), name.clone()), value);
+    }
+    for (name, &value) in component.output_names().iter().zip(outputs.iter()) {
+        values.insert((path.to_string(), name.clone()), value);
+    }
+    if !expanded(component, level, depth) {
+        return Ok(());
+    }
+
+    let wires = component.wires();
+    let mut local = HashMap::new();
+    for (name, &value) in component.input_names().iter().zip(inputs.iter()) {
+        local.insert(Pin::Port(name.clone()), value);
+    }
+    // The outputs also give the state of cross-coupled children.
+    for (name, &value) in component.output_names().iter().zip(outputs.iter()) {
+        for (from, to) in &wires {
+            if matches!(from, Pin::Child(..)) && *to == Pin::Port(name.clone()) {
+                local.insert(from.clone(), value);
+            }
+        }
+    }
+    let resolve = |local: &HashMap<Pin, Logic>, load: &Pin| {
+        let drivers = wires.iter().filter(|(_, to)| to == load);
+        drivers.fold(Logic::Z, |value, (from, _)| match from {
+            Pin::Constant(level) => value.resolve(Logic::from(*level)),
+            from => value.resolve(local.get(from).copied().unwrap_or(Logic::X)),
+        })
+    };
+
+    let mut children = component.sub_components_mut();
+    children.retain(|(name, _)| used(&wires, name));
+    let mut evaluated: Vec<Option<LBits>> = vec![None; children.len()];
+    for _ in 0..=2 * children.len() {
+        let mut changed = false;
+        for known_only in [true, false] {
+            for ((name, child), evaluated) in children.iter_mut().zip(evaluated.iter_mut()) {
+                let inputs = child
+                    .input_names()
+                    .iter()
+                    .map(|input| resolve(&local, &Pin::Child(name.clone(), input.clone())))
+                    .collect::<LBits>();
+                if evaluated.as_ref() == Some(&inputs) || known_only && !inputs.is_known() {
+                    continue;
+                }
+                let outputs = child.try_evaluate_logic_bits(&inputs)?;
+                for (output, &value) in child.output_names().iter().zip(outputs.iter()) {
+                    let pin = Pin::Child(name.clone(), output.clone());
+                    changed |= local.insert(pin, value) != Some(value);
+                }
+                *evaluated = Some(inputs);
+            }
+            if changed {
+                break;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    for ((name, child), inputs) in children.iter_mut().zip(evaluated) {
+        let inputs =
+            inputs.unwrap_or_else(|| LBits::from_vector(vec![Logic::X; child.input_count()]));
+        let outputs = child
+            .output_names()
+            .iter()
+            .map(|output| {
+                local
+                    .get(&Pin::Child(name.clone(), output.clone()))
+                    .copied()
+                    .unwrap_or(Logic::X)
+            })
+            .collect::<LBits>();
+        evaluate(
+            &mut **child,
+            &join_path(path, name),
+            &inputs,
+            &outputs,
+            level + 1,
+            depth,
+            values,
+        )?;
+    }
+    Ok(())
+}
+
+/// A node, or a port field of a record node.
+#[derive(Clone, Debug, PartialEq)]
+struct End {
+    node: usize,
+    port: Option<String>,
+}
+
+#[derive(Debug)]
+struct Edge {
+    from: End,
+    to: End,
+    value: Option<Logic>,
+}
+
+/// Drawn nodes, and the nets joining their ports once the opened sub-circuits are flattened.
+#[derive(Default)]
+struct Schematic {
+    text: String,
+    depth: Option<usize>,
+    show_values: bool,
+    nodes: Vec<String>,
+    index: HashMap<String, usize>,
+    /// Gate levels of each node.
+    weights: Vec<usize>,
+    /// Union-find over the ports of all components, by path and port name.
+    points: HashMap<(String, String), usize>,
+    parents: Vec<usize>,
+    drivers: Vec<(usize, End, Option<Logic>)>,
+    loads: Vec<(usize, End)>,
+    values: HashMap<(String, String), Logic>,
+}
+
+impl Schematic {
+    fn node(&mut self, id: String, weight: usize) -> usize {
+        self.index.insert(id.clone(), self.nodes.len());
+        self.nodes.push(id);
+        self.weights.push(weight);
+        self.nodes.len() - 1
+    }
+
+    fn point(&mut self, (path, port): (&str, &str)) -> usize {
+        let key = (path.to_string(), port.to_string());
+        let next = self.parents.len();
+        let point = *self.points.entry(key).or_insert(next);
+        if point == next {
+            self.parents.push(next);
+        }
+        point
+    }
+
+    fn find(&mut self, mut point: usize) -> usize {
+        while self.parents[point] != point {
+            self.parents[point] = self.parents[self.parents[point]];
+            point = self.parents[point];
+        }
+        point
+    }
+
+    fn join(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parents[a] = b;
+    }
+
+    fn driver(&mut self, key: (&str, &str), end: End) {
+        let value = self
+            .values
+            .get(&(key.0.to_string(), key.1.to_string()))
+            .copied();
+        let point = self.point(key);
+        self.drivers.push((point, end, value));
+    }
+
+    fn load(&mut self, key: (&str, &str), end: End) {
+        let point = self.point(key);
+        self.loads.push((point, end));
+    }
+
+    fn end(&self, end: &End) -> String {
+        match &end.port {
+            Some(port) => format!("\"{}\":{}", escape(&self.nodes[end.node]), port),
+            None => format!("\"{}\"", escape(&self.nodes[end.node])),
+        }
+    }
+
+    /// Writes the nodes and clusters of `component`, named `name` in its parent, and joins the
+    /// ends of its wires.
+    fn collect(
+        &mut self,
+        component: &dyn Component,
+        path: &str,
+        name: &str,
+        level: usize,
+        indent: usize,
+    ) {
+        let pad = "    ".repeat(indent);
+        if !expanded(component, level, self.depth) {
+            let id = match path {
+                "" => component.type_name().to_string(),
+                path => path.to_string(),
+            };
+            let weight = component.logic_depth();
+            let node = self.node(id.clone(), weight);
+            let fields = |prefix: &str, names: &[String]| {
+                let fields = names
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| format!("<{}{}> {}", prefix, i, escape_record(name)))
+                    .collect::<Vec<String>>();
+                fields.join("|")
+            };
+            let (inputs, outputs) = (component.input_names(), component.output_names());
+            let title = match name {
+                "" => escape_record(component.type_name()),
+                name => format!(
+                    "{}\\n{}",
+                    escape_record(name),
+                    escape_record(component.type_name())
+                ),
+            };
+            writeln!(
+                self.text,
+                "{}\"{}\" [label=\"{{{{{}}}|{}|{{{}}}}}\"];",
+                pad,
+                escape(&id),
+                fields("i", &inputs),
+                title,
+                fields("o", &outputs)
+            )
+            .unwrap();
+            for (i, input) in inputs.iter().enumerate() {
+                let port = Some(format!("i{}", i));
+                self.load((path, input), End { node, port });
+            }
+            for (i, output) in outputs.iter().enumerate() {
+                let port = Some(format!("o{}", i));
+                self.driver((path, output), End { node, port });
+            }
+            return;
+        }
+
+        let (pad, indent) = match path {
+            "" => (pad, indent),
+            path => {
+                writeln!(self.text, "{}subgraph \"cluster_{}\" {{", pad, escape(path)).unwrap();
+                let label = format!("{}: {}", name, component.type_name());
+                writeln!(self.text, "{}    label=\"{}\";", pad, escape(&label)).unwrap();
+                ("    ".repeat(indent + 1), indent + 1)
+            }
+        };
+        let wires = component.wires();
+        for (child_name, child) in component.sub_components() {
+            if used(&wires, &child_name) {
+                let child_path = join_path(path, &child_name);
+                self.collect(child, &child_path, &child_name, level + 1, indent);
+            }
+        }
+        let key = |pin: &Pin| match pin {
+            Pin::Port(port) => (path.to_string(), port.clone()),
+            Pin::Child(child, port) => (join_path(path, child), port.clone()),
+            Pin::Constant(_) => unreachable!("constants only drive"),
+        };
+        for (from, to) in &wires {
+            let (load_path, load_port) = key(to);
+            match from {
+                Pin::Constant(level) => {
+                    let id = format!("{} {}", load_path, load_port);
+                    let node = self.node(id.clone(), 0);
+                    let label = u8::from(*level);
+                    let line = format!(
+                        "\"{}\" [shape=plaintext, label=\"{}\"];",
+                        escape(&id),
+                        label
+                    );
+                    writeln!(self.text, "{}{}", pad, line).unwrap();
+                    let value = self.show_values.then_some(Logic::from(*level));
+                    let point = self.point((&load_path, &load_port));
+                    self.drivers.push((point, End { node, port: None }, value));
+                }
+                from => {
+                    let (driver_path, driver_port) = key(from);
+                    let a = self.point((&driver_path, &driver_port));
+                    let b = self.point((&load_path, &load_port));
+                    self.join(a, b);
+                }
+            }
+        }
+        if !path.is_empty() {
+            writeln!(self.text, "{}}}", "    ".repeat(indent - 1)).unwrap();
+        }
+    }
+
+    /// Every driver of a net to every one of its loads.
+    fn edges(&mut self) -> Vec<Edge> {
+        let drivers = std::mem::take(&mut self.drivers);
+        let loads = std::mem::take(&mut self.loads);
+        let mut edges = Vec::new();
+        for (driver, from, value) in &drivers {
+            let net = self.find(*driver);
+            for (load, to) in &loads {
+                if self.find(*load) == net {
+                    edges.push(Edge {
+                        from: from.clone(),
+                        to: to.clone(),
+                        value: *value,
+                    });
+                }
+            }
+        }
+        edges
+    }
+
+    /// Edges of the path with the most gate levels ending at an output, feedback edges being
+    /// ignored.
+    fn critical_path(&self, edges: &[Edge]) -> Vec<usize> {
+        let mut arrivals: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut visiting = vec![false; self.nodes.len()];
+        for node in 0..self.nodes.len() {
+            self.arrival(node, edges, &mut arrivals, &mut visiting);
+        }
+        let arrival = |node: usize| arrivals[node].unwrap_or(0);
+
+        let outputs = (0..self.nodes.len()).filter(|&node| self.nodes[node].starts_with("output "));
+        let Some(mut node) = outputs.max_by_key(|&node| (arrival(node), std::cmp::Reverse(node)))
+        else {
+            return Vec::new();
+        };
+        let mut path = Vec::new();
+        while let Some(edge) = (0..edges.len())
+            .filter(|&edge| edges[edge].to.node == node && !path.contains(&edge))
+            .filter(|&edge| arrival(edges[edge].from.node) + self.weights[node] == arrival(node))
+            .max_by_key(|&edge| std::cmp::Reverse(edge))
+        {
+            path.push(edge);
+            node = edges[edge].from.node;
+        }
+        path
+    }
+
+    fn arrival(
+        &self,
+        node: usize,
+        edges: &[Edge],
+        arrivals: &mut Vec<Option<usize>>,
+        visiting: &mut Vec<bool>,
+    ) -> usize {
+        if let Some(arrival) = arrivals[node] {
+            return arrival;
+        }
+        if visiting[node] {
+            return 0;
+        }
+        visiting[node] = true;
+        let mut latest = 0;
+        for edge in edges.iter().filter(|edge| edge.to.node == node) {
+            latest = latest.max(self.arrival(edge.from.node, edges, arrivals, visiting));
+        }
+        visiting[node] = false;
+        arrivals[node] = Some(latest + self.weights[node]);
+        latest + self.weights[node]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::decoder::Decoder;
+    use crate::electronic::circuits::register::PIPORegister;
+
+    #[test]
+    fn dot_clusters() {
+        let text = dot(&FullAdder::new(), &DotOptions::new());
+        assert!(text.starts_with("digraph \"FullAdder\" {\n    rankdir=LR;\n"));
+        assert!(text.contains("    \"xor0\" [label=\"{{<i0> a|<i1> b}|xor0\\nXor|{<o0> y}}\"];\n"));
+        assert!(text.contains(
+            "    subgraph \"cluster_and0\" {\n        label=\"and0: And\";\n        \"and0.nand\" "
+        ));
+        // Wires cross the cluster boundaries.
+        assert!(text.contains("    \"xor0\":o0 -> \"and0.nand\":i1;\n"));
+        assert!(text.contains("    \"or.not\":o0 -> \"output carry_out\";\n"));
+
+        let text = dot(&Decoder::new(2), &DotOptions::new().with_depth(0));
+        assert!(text.contains("    \"decoder2to4\" [label=\"{{<i0> a|<i1> b|<i2> enable}|"));
+        assert!(!text.contains("\"not\""));
+        assert!(!text.contains("subgraph"));
+    }
+
+    #[test]
+    fn dot_critical_path() {
+        let text = dot(&FullAdder::new(), &DotOptions::new().with_critical_path());
+        // xor0, then the nand and not of and0 and the nor and not of or, and the six wires
+        // from a to carry_out.
+        let highlighted = "[color=orange, penwidth=3, style=bold];\n";
+        assert_eq!(text.matches(highlighted).count(), 5 + 6);
+        assert!(text.contains(&format!("    \"and0.not\" {}", highlighted)));
+        assert!(text.contains(&format!(
+            "    \"or.not\":o0 -> \"output carry_out\" {}",
+            highlighted
+        )));
+        assert!(text.contains("    \"xor1\":o0 -> \"output sum\";\n"));
+
+        // With values, the path keeps its width and the wires their value colour.
+        let options = DotOptions::new().with_critical_path();
+        let inputs = Bits::from_int(0b110, Some(3));
+        let text = evaluate_dot(&mut FullAdder::new(), &inputs, &options).unwrap();
+        assert_eq!(text.matches("penwidth=3, style=bold];\n").count(), 5 + 6);
+        assert!(text.contains(
+            "    \"or.not\":o0 -> \"output carry_out\" [label=\"1\", color=red, fontcolor=red, \
+             penwidth=3, style=bold];\n"
+        ));
+        assert!(text
+            .lines()
+            .all(|line| line.matches("color=").count() - line.matches("fontcolor=").count() <= 1));
+    }
+
+    #[test]
+    fn dot_values() {
+        let inputs = Bits::from_int(0b11011, Some(5));
+        let options = DotOptions::new().with_depth(0);
+        let text = evaluate_dot(&mut BitwiseAdd::new(2), &inputs, &options).unwrap();
+        assert!(text.contains(
+            "    \"adders[0]\":o1 -> \"adders[1]\":i2 [label=\"1\", color=red, fontcolor=red];\n"
+        ));
+        assert!(text.contains(
+            "    \"adders[1]\":o0 -> \"output sum[1]\" [label=\"0\", color=blue, fontcolor=blue];\n"
+        ));
+
+        // A disabled latch shows the value it holds.
+        let mut register = PIPORegister::new(1);
+        register.reset_states();
+        register.evaluate_bits(&Bits::from_int(0b11, Some(2)));
+        let inputs = Bits::from_int(0b00, Some(2));
+        let text = evaluate_dot(&mut register, &inputs, &DotOptions::new()).unwrap();
+        assert!(text.contains(
+            "    \"d_flip_flops[0].nand2\":o0 -> \"output q[0]\" [label=\"1\", color=red, \
+             fontcolor=red];\n"
+        ));
+        assert!(text
+            .contains("\"d_flip_flops[0].nand3\":o0 -> \"d_flip_flops[0].nand2\":i1 [label=\"0\""));
+
+        // Enabled, the latch takes the data shown, as any evaluation would.
+        let inputs = Bits::from_int(0b01, Some(2));
+        let text = evaluate_dot(&mut register, &inputs, &DotOptions::new()).unwrap();
+        assert!(text.contains("-> \"output q[0]\" [label=\"0\""));
+        assert!(register.output() == [false]);
+
+        let inputs = Bits::from_int(0, Some(3));
+        assert_eq!(
+            evaluate_dot(&mut register, &inputs, &DotOptions::new()),
+            Err(Error::WidthMismatch {
+                input: "inputs",
+                expected: 2,
+                actual: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::data::bits::Bits;
+use crate::electronic::circuits::component::Component;
+use crate::electronic::truth_table::row_count;
+use crate::error::Error;
+
+/// First input value, counting up, on which the combinational circuits `a` and `b` give
+/// different outputs, `None` when they agree on all `2^n` of them. Like a truth table, at most
+/// [`MAX_TABLE_INPUTS`](crate::electronic::truth_table::MAX_TABLE_INPUTS) inputs are checked.
+pub fn counterexample(a: &mut dyn Component, b: &mut dyn Component) -> Result<Option<Bits>, Error> {
+    Error::check_width("inputs", a.input_count(), b.input_count())?;
+    Error::check_width("outputs", a.output_count(), b.output_count())?;
+    let size = a.input_count();
+    for value in 0..row_count(size)? {
+        let inputs = Bits::try_from_u128(value as u128, Some(size))?;
+        if a.try_evaluate_bits(&inputs)? != b.try_evaluate_bits(&inputs)? {
+            return Ok(Some(inputs));
+        }
+    }
+    Ok(None)
+}
+
+/// Whether `a` and `b` compute the same function, checked on every input value.
+pub fn equivalent(a: &mut dyn Component, b: &mut dyn Component) -> Result<bool, Error> {
+    Ok(counterexample(a, b)?.is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::logic_gates::and::And;
+    use crate::electronic::circuits::logic_gates::nand::Nand;
+    use crate::electronic::circuits::logic_gates::or::Or;
+
+    #[test]
+    fn equivalence_counterexample() {
+        assert_eq!(equivalent(&mut And::new(), &mut And::new()), Ok(true));
+        let inputs = counterexample(&mut And::new(), &mut Or::new()).unwrap();
+        assert!(inputs.unwrap() == Bits::from_int(0b01, Some(2)));
+        assert!(!equivalent(&mut And::new(), &mut Nand::new()).unwrap());
+        assert_eq!(
+            equivalent(&mut And::new(), &mut FullAdder::new()),
+            Err(Error::WidthMismatch {
+                input: "inputs",
+                expected: 2,
+                actual: 3
+            })
+        );
+        assert!(matches!(
+            equivalent(&mut BitwiseAdd::new(16), &mut BitwiseAdd::new(16)),
+            Err(Error::TooManyInputs { inputs: 33, .. })
+        ));
+    }
+}
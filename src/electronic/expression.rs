@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::data::bits::Bits;
+
+/// Two-level shape of an [`Expression`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Form {
+    /// An OR of ANDs of literals.
+    SumOfProducts,
+    /// An AND of ORs of literals.
+    ProductOfSums,
+}
+
+/// A product (in a sum of products) or a sum (in a product of sums) of literals, one slot per
+/// input: `Some(true)` for the input, `Some(false)` for its complement, `None` when it does not
+/// appear.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Term {
+    pub literals: Vec<Option<bool>>,
+}
+
+impl Term {
+    /// The product true only for the input `value`, MSB first over `size` inputs.
+    pub fn minterm(value: usize, size: usize) -> Self {
+        Term {
+            literals: (0..size)
+                .map(|i| Some(value >> (size - 1 - i) & 1 == 1))
+                .collect(),
+        }
+    }
+
+    /// The sum false only for the input `value`, MSB first over `size` inputs.
+    pub fn maxterm(value: usize, size: usize) -> Self {
+        Term {
+            literals: (0..size)
+                .map(|i| Some(value >> (size - 1 - i) & 1 == 0))
+                .collect(),
+        }
+    }
+
+    pub fn literal_count(&self) -> usize {
+        self.literals.iter().flatten().count()
+    }
+
+    /// Whether the product holds for the input `value`, MSB first.
+    pub fn covers(&self, value: usize) -> bool {
+        let size = self.literals.len();
+        self.literals.iter().enumerate().all(|(i, literal)| {
+            literal.is_none_or(|literal| literal == (value >> (size - 1 - i) & 1 == 1))
+        })
+    }
+
+    /// The term with every literal complemented, to go between a product and a sum by De
+    /// Morgan's laws.
+    pub fn complement(&self) -> Self {
+        Term {
+            literals: self
+                .literals
+                .iter()
+                .map(|literal| literal.map(|literal| !literal))
+                .collect(),
+        }
+    }
+
+    /// Value as a product: whether every literal holds.
+    pub fn product(&self, inputs: &Bits) -> bool {
+        self.literals
+            .iter()
+            .zip(inputs.iter())
+            .all(|(literal, input)| literal.is_none_or(|literal| literal == input))
+    }
+
+    /// Value as a sum: whether any literal holds.
+    pub fn sum(&self, inputs: &Bits) -> bool {
+        self.literals
+            .iter()
+            .zip(inputs.iter())
+            .any(|(literal, input)| *literal == Some(input))
+    }
+
+    fn fmt_with(&self, names: &[String], operator: &str, parenthesize: bool) -> String {
+        let literals = self
+            .literals
+            .iter()
+            .zip(names)
+            .filter_map(|(literal, name)| match literal {
+                Some(true) => Some(name.to_string()),
+                Some(false) => Some(format!("!{}", name)),
+                None => None,
+            })
+            .collect::<Vec<String>>();
+        if parenthesize && literals.len() > 1 {
+            format!("({})", literals.join(operator))
+        } else {
+            literals.join(operator)
+        }
+    }
+}
+
+/// Two-level Boolean expression over named inputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expression {
+    pub form: Form,
+    pub inputs: Vec<String>,
+    pub terms: Vec<Term>,
+}
+
+impl Expression {
+    /// Value for `inputs`, given in the order of the input names.
+    pub fn evaluate(&self, inputs: &Bits) -> bool {
+        match self.form {
+            Form::SumOfProducts => self.terms.iter().any(|term| term.product(inputs)),
+            Form::ProductOfSums => self.terms.iter().all(|term| term.sum(inputs)),
+        }
+    }
+
+    pub fn literal_count(&self) -> usize {
+        self.terms.iter().map(Term::literal_count).sum()
+    }
+}
+
+/// Written with `!`, `&` and `|`, e.g. `(!a & b) | (a & !b)`. An empty sum is `0`, an empty
+/// product `1`.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (inner, outer, empty_term, empty) = match self.form {
+            Form::SumOfProducts => (" & ", " | ", "1", "0"),
+            Form::ProductOfSums => (" | ", " & ", "0", "1"),
+        };
+        if self.terms.is_empty() {
+            return write!(f, "{}", empty);
+        }
+        let parenthesize = self.terms.len() > 1;
+        let terms = self
+            .terms
+            .iter()
+            .map(|term| match term.literal_count() {
+                0 => empty_term.to_string(),
+                _ => term.fmt_with(&self.inputs, inner, parenthesize),
+            })
+            .collect::<Vec<String>>();
+        write!(f, "{}", terms.join(outer))
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+
+use crate::data::logic::Logic;
+use crate::electronic::circuits::component::{walk, Component};
+use crate::error::Error;
+
+/// Manufacturing defect injected in a circuit to see how its outputs change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fault {
+    /// A gate output, or a transistor control gate, tied to 0.
+    StuckAt0,
+    /// A gate output, or a transistor control gate, tied to 1.
+    StuckAt1,
+    /// A transistor channel that never conducts.
+    StuckOpen,
+    /// A transistor channel that always conducts.
+    StuckShort,
+}
+
+impl Fault {
+    pub const STUCK_AT: &'static [Fault] = &[Fault::StuckAt0, Fault::StuckAt1];
+    pub const ALL: &'static [Fault] = &[
+        Fault::StuckAt0,
+        Fault::StuckAt1,
+        Fault::StuckOpen,
+        Fault::StuckShort,
+    ];
+
+    /// Value forced by a stuck-at fault.
+    pub fn stuck_value(self) -> Option<Logic> {
+        match self {
+            Fault::StuckAt0 => Some(Logic::Zero),
+            Fault::StuckAt1 => Some(Logic::One),
+            Fault::StuckOpen | Fault::StuckShort => None,
+        }
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Fault::StuckAt0 => "stuck-at-0",
+            Fault::StuckAt1 => "stuck-at-1",
+            Fault::StuckOpen => "stuck-open",
+            Fault::StuckShort => "stuck-short",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// `value` as seen past a possibly stuck node.
+pub(crate) fn stuck(value: Logic, fault: Option<Fault>) -> Logic {
+    fault.and_then(Fault::stuck_value).unwrap_or(value)
+}
+
+/// Boolean value of a gate output driven to `output` by a faulty transistor network. An output
+/// left floating or fought over keeps its `previous` value, as the node holds its charge.
+pub(crate) fn held(output: Logic, previous: Option<Logic>) -> bool {
+    output
+        .to_bool()
+        .or_else(|| previous.and_then(Logic::to_bool))
+        .unwrap_or(false)
+}
+
+/// Whether `component` or any of its descendants carries a fault.
+pub fn has_fault(component: &dyn Component) -> bool {
+    component.fault().is_some()
+        || component
+            .sub_components()
+            .iter()
+            .any(|(_, sub_component)| has_fault(*sub_component))
+}
+
+/// The descendant of `component` at the dot-separated `path`, e.g. `adders[3].xor0.nmos_a`.
+pub fn find_mut<'a>(component: &'a mut dyn Component, path: &str) -> Option<&'a mut dyn Component> {
+    if path.is_empty() {
+        return Some(component);
+    }
+    let (name, rest) = path.split_once('.').unwrap_or((path, ""));
+    component
+        .sub_components_mut()
+        .into_iter()
+        .find(|(sub_name, _)| sub_name == name)
+        .and_then(|(_, sub_component)| find_mut(sub_component, rest))
+}
+
+/// Injects `fault` on the descendant at `path`, replacing any fault already there.
+pub fn inject(component: &mut dyn Component, path: &str, fault: Fault) -> Result<(), Error> {
+    let target = find_mut(component, path).ok_or_else(|| Error::UnknownComponent {
+        path: path.to_string(),
+    })?;
+    if !target.supported_faults().contains(&fault) {
+        return Err(Error::UnsupportedFault {
+            path: path.to_string(),
+            fault,
+        });
+    }
+    target.set_fault(Some(fault));
+    Ok(())
+}
+
+/// Removes the fault of the descendant at `path`, if any.
+pub fn clear(component: &mut dyn Component, path: &str) -> Result<(), Error> {
+    let target = find_mut(component, path).ok_or_else(|| Error::UnknownComponent {
+        path: path.to_string(),
+    })?;
+    target.set_fault(None);
+    Ok(())
+}
+
+/// Removes every fault of `component` and its descendants.
+pub fn clear_all(component: &mut dyn Component) {
+    component.set_fault(None);
+    for (_, sub_component) in component.sub_components_mut() {
+        clear_all(sub_component);
+    }
+}
+
+/// Every fault that can be injected in `component`, with the path of its site.
+pub fn sites(component: &dyn Component, faults: &[Fault]) -> Vec<(String, Fault)> {
+    let mut sites = Vec::new();
+    walk(component, &mut |path, sub_component| {
+        for fault in sub_component.supported_faults() {
+            if faults.contains(fault) {
+                sites.push((path.to_string(), *fault));
+            }
+        }
+    });
+    sites
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::bits::Bits;
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::decoder::Decoder;
+    use crate::electronic::circuits::logic_gates::nand::Nand;
+    use crate::electronic::circuits::logic_gates::not::Not;
+    use crate::electronic::circuits::power_on::PowerOn;
+    use crate::electronic::circuits::register::PIPORegister;
+
+    #[test]
+    fn fault_gate_output() {
+        let mut not = Not::new();
+        inject(&mut not, "", Fault::StuckAt1).unwrap();
+        assert!(not.evaluate(true));
+        assert_eq!(not.evaluate_logic(Logic::One), Logic::One);
+        clear(&mut not, "").unwrap();
+        assert!(!not.evaluate(true));
+    }
+
+    #[test]
+    fn fault_transistor() {
+        // A shorted pull-down nmos fights the pmos when the output should be high.
+        let mut nand = Nand::new();
+        inject(&mut nand, "nmos_a", Fault::StuckShort).unwrap();
+        assert_eq!(nand.evaluate_logic(Logic::Zero, Logic::One), Logic::X);
+        assert_eq!(nand.evaluate_logic(Logic::Zero, Logic::Zero), Logic::One);
+
+        // A stuck-open pull-up leaves the output floating, it keeps its previous value.
+        let mut not = Not::new();
+        inject(&mut not, "pmos", Fault::StuckOpen).unwrap();
+        assert!(!not.evaluate(true));
+        assert!(!not.evaluate(false));
+        assert_eq!(not.evaluate_logic(Logic::Zero), Logic::X);
+
+        // A control gate stuck at 1 turns the nmos on and the pmos off.
+        let mut not = Not::new();
+        inject(&mut not, "pmos", Fault::StuckAt1).unwrap();
+        inject(&mut not, "nmos", Fault::StuckAt1).unwrap();
+        assert!(!not.evaluate(false));
+    }
+
+    #[test]
+    fn fault_paths() {
+        let mut bitwise_add = BitwiseAdd::new(4);
+        let inputs = Bits::zeros(9);
+        assert!(bitwise_add.evaluate_bits(&inputs) == Bits::zeros(5));
+
+        inject(&mut bitwise_add, "adders[3].xor1", Fault::StuckAt1).unwrap();
+        assert!(bitwise_add.evaluate_bits(&inputs) == Bits::from_int(0b10000, Some(5)));
+        clear_all(&mut bitwise_add);
+        assert!(bitwise_add.evaluate_bits(&inputs) == Bits::zeros(5));
+
+        assert_eq!(
+            inject(&mut bitwise_add, "adders[4]", Fault::StuckAt0),
+            Err(Error::UnknownComponent {
+                path: "adders[4]".to_string()
+            })
+        );
+        assert_eq!(
+            inject(&mut bitwise_add, "adders[3].xor0.nmos_a", Fault::StuckOpen),
+            Ok(())
+        );
+        assert_eq!(
+            inject(&mut bitwise_add, "adders[3]", Fault::StuckAt0),
+            Err(Error::UnsupportedFault {
+                path: "adders[3]".to_string(),
+                fault: Fault::StuckAt0
+            })
+        );
+    }
+
+    #[test]
+    fn fault_sites() {
+        let full_adder = FullAdder::new();
+        let stuck_at = sites(&full_adder, Fault::STUCK_AT);
+        // Xor, Xor, Nand, Not, Nand, Not, Nor, Not and their 34 transistors.
+        assert_eq!(stuck_at.len(), 2 * (8 + 34));
+        assert!(stuck_at.contains(&("and0.not".to_string(), Fault::StuckAt1)));
+        let open = sites(&full_adder, &[Fault::StuckOpen]);
+        assert_eq!(open.len(), 34);
+    }
+
+    #[test]
+    fn fault_sequential() {
+        // The decoder output 0 is always selected with a stuck enable gate.
+        let mut decoder = Decoder::new(3);
+        inject(&mut decoder, "ands[7].not", Fault::StuckAt1).unwrap();
+        let outputs = decoder.evaluate(Bits::from_int(0b000, Some(3)), false);
+        assert_eq!(outputs.iter().filter(|&&output| output).count(), 1);
+
+        // With the inverted data stuck at 0 the flip-flop never resets.
+        let mut register = PIPORegister::with_power_on(2, &mut PowerOn::Ones);
+        inject(&mut register, "d_flip_flops[0].not", Fault::StuckAt0).unwrap();
+        register.set_d(&[false, false]);
+        assert!(register.clock_tick(true) == Bits::from_int(0b01, Some(2)));
+        clear_all(&mut register);
+        assert!(register.clock_tick(true) == Bits::zeros(2));
+    }
+}
@@ -0,0 +1,380 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::data::bits::Bits;
+use crate::electronic::circuits::component::Component;
+use crate::electronic::circuits::gate_netlist::{CellId, GateNetlist, NetId};
+use crate::error::Error;
+
+/// How a combinational loop behaves, from the inversions along its cycles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoopKind {
+    /// Every cycle has an even number of inversions: the loop holds either of two stable
+    /// states, an intentional latch such as cross-coupled NANDs.
+    Latch,
+    /// A cycle has an odd number of inversions: the loop oscillates whenever its side inputs
+    /// let the signal around, like a ring oscillator.
+    Oscillator,
+    /// A cell of the loop, e.g. a Xor, inverts or not depending on its other inputs.
+    Undetermined,
+}
+
+impl fmt::Display for LoopKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LoopKind::Latch => "latch",
+            LoopKind::Oscillator => "oscillator",
+            LoopKind::Undetermined => "undetermined",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Nets of a netlist that depend on themselves through cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CombinationalLoop {
+    pub nets: Vec<NetId>,
+    /// Cells reading a net of the loop and driving another one.
+    pub cells: Vec<CellId>,
+    pub kind: LoopKind,
+}
+
+/// How an output of a cell follows one of its inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    Independent,
+    /// Rises, if at all, when the input rises.
+    Positive,
+    /// Falls, if at all, when the input rises.
+    Negative,
+    /// Rises or falls depending on the other inputs.
+    Binate,
+}
+
+impl Polarity {
+    /// Combined with an output change seen when the input rises.
+    fn with(self, rising: bool) -> Polarity {
+        match (self, rising) {
+            (Polarity::Independent | Polarity::Positive, true) => Polarity::Positive,
+            (Polarity::Independent | Polarity::Negative, false) => Polarity::Negative,
+            _ => Polarity::Binate,
+        }
+    }
+}
+
+/// Cells with more inputs are taken as binate rather than evaluated on every input value.
+pub const MAX_POLARITY_INPUTS: usize = 12;
+
+/// Polarity of every output of `component` in every input, indexed by input then output,
+/// from its truth table.
+pub fn polarities(component: &mut dyn Component) -> Result<Vec<Vec<Polarity>>, Error> {
+    let (inputs, outputs) = (component.input_count(), component.output_count());
+    if inputs > MAX_POLARITY_INPUTS {
+        return Ok(vec![vec![Polarity::Binate; outputs]; inputs]);
+    }
+    let table = (0..1_u32 << inputs)
+        .map(|value| component.try_evaluate_bits(&Bits::from_int(value, Some(inputs))))
+        .collect::<Result<Vec<Bits>, Error>>()?;
+
+    let mut polarities = vec![vec![Polarity::Independent; outputs]; inputs];
+    for (input, row) in polarities.iter_mut().enumerate() {
+        // The first input is the MSB.
+        let mask = 1 << (inputs - 1 - input);
+        for (value, low) in table.iter().enumerate() {
+            if value & mask != 0 {
+                continue;
+            }
+            let high = &table[value | mask];
+            for (output, polarity) in row.iter_mut().enumerate() {
+                if low[output] != high[output] {
+                    *polarity = polarity.with(high[output]);
+                }
+            }
+        }
+    }
+    Ok(polarities)
+}
+
+/// A cell port pair linking two nets.
+#[derive(Clone, Copy)]
+struct Edge {
+    from: usize,
+    to: usize,
+    cell: CellId,
+    input: usize,
+    output: usize,
+}
+
+/// Combinational loops of `netlist`: sets of nets reaching each other through cells, classified
+/// by the inversions along their cycles.
+///
+/// Loops are first found on the structure, taking every output of a cell to depend on all its
+/// inputs. The cells on those loops are then evaluated on all their input values to drop the
+/// dependencies they do not have and to find which inputs they invert. This counts toggles
+/// and changes the state of sequential cells.
+pub fn loops(netlist: &mut GateNetlist) -> Result<Vec<CombinationalLoop>, Error> {
+    let mut edges = Vec::new();
+    for cell in (0..netlist.cell_count()).map(CellId) {
+        for (input, from) in netlist.cell_inputs(cell).iter().enumerate() {
+            for (output, to) in netlist.cell_outputs(cell).iter().enumerate() {
+                edges.push(Edge {
+                    from: from.0,
+                    to: to.0,
+                    cell,
+                    input,
+                    output,
+                });
+            }
+        }
+    }
+
+    let mut cell_polarities: HashMap<CellId, Vec<Vec<Polarity>>> = HashMap::new();
+    for nets in cycles(netlist.net_count(), &edges) {
+        for edge in internal(&edges, &nets) {
+            if let Entry::Vacant(entry) = cell_polarities.entry(edge.cell) {
+                entry.insert(polarities(netlist.component_mut(edge.cell))?);
+            }
+        }
+    }
+    let polarity = |edge: &Edge| {
+        cell_polarities
+            .get(&edge.cell)
+            .map_or(Polarity::Binate, |polarities| {
+                polarities[edge.input][edge.output]
+            })
+    };
+    edges.retain(|edge| polarity(edge) != Polarity::Independent);
+
+    Ok(cycles(netlist.net_count(), &edges)
+        .into_iter()
+        .map(|nets| {
+            let loop_edges = internal(&edges, &nets);
+            let mut cells = loop_edges
+                .iter()
+                .map(|edge| edge.cell)
+                .collect::<Vec<CellId>>();
+            cells.sort_by_key(|cell| cell.0);
+            cells.dedup();
+            let polarities = loop_edges
+                .iter()
+                .map(|edge| (edge.from, edge.to, polarity(edge)))
+                .collect::<Vec<(usize, usize, Polarity)>>();
+            CombinationalLoop {
+                kind: classify(nets[0], &polarities),
+                nets: nets.into_iter().map(NetId).collect(),
+                cells,
+            }
+        })
+        .collect())
+}
+
+fn internal(edges: &[Edge], nets: &[usize]) -> Vec<Edge> {
+    edges
+        .iter()
+        .filter(|edge| nets.contains(&edge.from) && nets.contains(&edge.to))
+        .copied()
+        .collect()
+}
+
+/// Strongly connected sets of nets with at least one cycle, each sorted, in order of their
+/// first net.
+fn cycles(net_count: usize, edges: &[Edge]) -> Vec<Vec<usize>> {
+    let mut successors = vec![Vec::new(); net_count];
+    for edge in edges {
+        successors[edge.from].push(edge.to);
+    }
+    let mut tarjan = Tarjan {
+        successors: &successors,
+        index: vec![None; net_count],
+        low: vec![0; net_count],
+        on_stack: vec![false; net_count],
+        stack: Vec::new(),
+        next: 0,
+        components: Vec::new(),
+    };
+    for net in 0..net_count {
+        if tarjan.index[net].is_none() {
+            tarjan.visit(net);
+        }
+    }
+
+    let mut cycles = tarjan
+        .components
+        .into_iter()
+        .filter(|nets| nets.len() > 1 || successors[nets[0]].contains(&nets[0]))
+        .map(|mut nets| {
+            nets.sort();
+            nets
+        })
+        .collect::<Vec<Vec<usize>>>();
+    cycles.sort();
+    cycles
+}
+
+/// Tarjan's strongly connected components.
+struct Tarjan<'a> {
+    successors: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, net: usize) {
+        self.index[net] = Some(self.next);
+        self.low[net] = self.next;
+        self.next += 1;
+        self.stack.push(net);
+        self.on_stack[net] = true;
+
+        for &successor in &self.successors[net] {
+            match self.index[successor] {
+                None => {
+                    self.visit(successor);
+                    self.low[net] = self.low[net].min(self.low[successor]);
+                }
+                Some(index) if self.on_stack[successor] => {
+                    self.low[net] = self.low[net].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.low[net]) == self.index[net] {
+            let mut component = Vec::new();
+            loop {
+                let member = self.stack.pop().expect("net is on the stack");
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == net {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+/// Gives every net of a strongly connected loop a parity, flipped by each inverting edge. The
+/// parities only agree when every cycle has an even number of inversions.
+fn classify(start: usize, edges: &[(usize, usize, Polarity)]) -> LoopKind {
+    if edges
+        .iter()
+        .any(|&(_, _, polarity)| polarity == Polarity::Binate)
+    {
+        return LoopKind::Undetermined;
+    }
+    let mut parities = HashMap::from([(start, false)]);
+    let mut queue = vec![start];
+    while let Some(net) = queue.pop() {
+        for &(from, to, polarity) in edges {
+            if from == net && !parities.contains_key(&to) {
+                parities.insert(to, parities[&net] ^ (polarity == Polarity::Negative));
+                queue.push(to);
+            }
+        }
+    }
+    let even = edges.iter().all(|&(from, to, polarity)| {
+        parities[&to] == parities[&from] ^ (polarity == Polarity::Negative)
+    });
+    if even {
+        LoopKind::Latch
+    } else {
+        LoopKind::Oscillator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::logic::Logic;
+    use crate::electronic::circuits::logic_gates::and::And;
+    use crate::electronic::circuits::logic_gates::nand::Nand;
+    use crate::electronic::circuits::logic_gates::not::Not;
+    use crate::electronic::circuits::logic_gates::xor::Xor;
+    use crate::electronic::circuits::mux::Mux2To1;
+
+    fn ring_oscillator() -> GateNetlist {
+        let mut netlist = GateNetlist::new();
+        let enable = netlist.input("enable");
+        let (a, b, c) = (netlist.net("a"), netlist.net("b"), netlist.output("c"));
+        netlist
+            .add("nand", Nand::new(), &[enable, c], &[a])
+            .unwrap();
+        netlist.add("not0", Not::new(), &[a], &[b]).unwrap();
+        netlist.add("not1", Not::new(), &[b], &[c]).unwrap();
+        netlist
+    }
+
+    #[test]
+    fn loops_polarities() {
+        use Polarity::*;
+        assert_eq!(
+            polarities(&mut Nand::new()).unwrap(),
+            vec![vec![Negative], vec![Negative]]
+        );
+        assert_eq!(
+            polarities(&mut Mux2To1::new()).unwrap(),
+            vec![vec![Positive], vec![Positive], vec![Binate]]
+        );
+    }
+
+    #[test]
+    fn loops_latch() {
+        let mut netlist = GateNetlist::d_flip_flop();
+        let loops = loops(&mut netlist).unwrap();
+        assert_eq!(loops.len(), 1);
+        let nets = loops[0]
+            .nets
+            .iter()
+            .map(|&net| netlist.net_name(net))
+            .collect::<Vec<&str>>();
+        assert_eq!(nets, vec!["q", "q_bar"]);
+        let cells = loops[0]
+            .cells
+            .iter()
+            .map(|&cell| netlist.cell_name(cell))
+            .collect::<Vec<&str>>();
+        assert_eq!(cells, vec!["nand2", "nand3"]);
+        assert_eq!(loops[0].kind, LoopKind::Latch);
+    }
+
+    #[test]
+    fn loops_oscillator() {
+        let mut netlist = ring_oscillator();
+        let loops = loops(&mut netlist).unwrap();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].nets.len(), 3);
+        assert_eq!(loops[0].kind, LoopKind::Oscillator);
+
+        assert!(netlist.evaluate_bits(&Bits::zeros(1)) == Bits::ones(1));
+        let nodes = ["a", "b", "c"].map(String::from).to_vec();
+        assert_eq!(
+            netlist.simulate(&[Logic::One]),
+            Err(Error::NoConvergence {
+                iterations: 7,
+                nodes
+            })
+        );
+    }
+
+    #[test]
+    fn loops_undetermined_and_none() {
+        let mut netlist = GateNetlist::new();
+        let a = netlist.input("a");
+        let y = netlist.output("y");
+        netlist.add("xor", Xor::new(), &[a, y], &[y]).unwrap();
+        assert_eq!(loops(&mut netlist).unwrap()[0].kind, LoopKind::Undetermined);
+
+        let mut netlist = GateNetlist::new();
+        let (a, b) = (netlist.input("a"), netlist.input("b"));
+        let (y, z) = (netlist.net("y"), netlist.output("z"));
+        netlist.add("and", And::new(), &[a, b], &[y]).unwrap();
+        netlist.add("not", Not::new(), &[y], &[z]).unwrap();
+        assert!(loops(&mut netlist).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,744 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::electronic::circuits::component::{join_path, sealed::Gate, Component, Pin};
+use crate::electronic::circuits::gate_netlist::{CellId, GateNetlist};
+use crate::electronic::circuits::logic_gates::nand::Nand;
+use crate::electronic::circuits::logic_gates::nor::Nor;
+use crate::electronic::expression::Form;
+use crate::electronic::stats::stats;
+use crate::electronic::synthesis::minimize;
+use crate::electronic::truth_table::{truth_table, TruthTable};
+use crate::error::Error;
+
+/// Single gate type a circuit is mapped to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Technology {
+    Nand,
+    Nor,
+}
+
+impl fmt::Display for Technology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Technology::Nand => "NAND-only",
+            Technology::Nor => "NOR-only",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A circuit rewritten with a single gate type, with the transistor counts of both versions.
+pub struct Mapping {
+    pub netlist: GateNetlist,
+    pub technology: Technology,
+    pub transistors_before: usize,
+    pub transistors_after: usize,
+}
+
+impl Mapping {
+    /// Transistors added by the mapping, negative when it saves some.
+    pub fn difference(&self) -> i64 {
+        self.transistors_after as i64 - self.transistors_before as i64
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} transistors ({:+})",
+            self.technology,
+            self.transistors_before,
+            self.transistors_after,
+            self.difference()
+        )
+    }
+}
+
+/// Maps `component` structurally, following its [`wires`](Component::wires) down the
+/// hierarchy: gates are rewritten with the identities of [`map_netlist`], feedback such as the
+/// cross-coupled gates of a latch is kept, and only leaves without a gate identity, or
+/// circuits with a bus driven several times, are mapped from their truth table: a minimal sum
+/// of products as NAND-NAND, or a minimal product of sums as NOR-NOR.
+pub fn map(component: &mut dyn Component, technology: Technology) -> Result<Mapping, Error> {
+    let mut builder = Builder::new(technology);
+    let inputs = component
+        .input_names()
+        .iter()
+        .map(|name| builder.input(name))
+        .collect::<Vec<usize>>();
+    let values = builder.component(component, &inputs)?;
+    for (name, value) in component.output_names().iter().zip(values) {
+        let net = builder.output(name);
+        builder.drive(value, net)?;
+    }
+    Ok(Mapping {
+        transistors_before: stats(component, 0).total().transistors,
+        ..builder.finish()
+    })
+}
+
+/// Maps `netlist` cell by cell, its nets keeping their names. Gates are rewritten with the
+/// usual identities (an `Or` is a NAND of inverted inputs, a `Xor` four NANDs), other cells
+/// as [`map`] does. Identical gates are shared and double inversions cancel, so a full adder
+/// of `Xor`, `And` and `Or` becomes the classic nine NANDs.
+///
+/// A net with several drivers, such as a bus shared by tri-state buffers, is an
+/// [`Error::MultipleDrivers`]: the mapped gates always drive their outputs.
+pub fn map_netlist(netlist: &mut GateNetlist, technology: Technology) -> Result<Mapping, Error> {
+    let mut builder = Builder::new(technology);
+    let mut nets = HashMap::new();
+    for &net in netlist.inputs() {
+        nets.insert(net, builder.input(netlist.net_name(net)));
+    }
+    for &net in netlist.outputs() {
+        nets.insert(net, builder.output(netlist.net_name(net)));
+    }
+    for cell in (0..netlist.cell_count()).map(CellId) {
+        for &net in netlist
+            .cell_inputs(cell)
+            .iter()
+            .chain(netlist.cell_outputs(cell))
+        {
+            nets.entry(net)
+                .or_insert_with(|| builder.net(netlist.net_name(net)));
+        }
+    }
+
+    for cell in (0..netlist.cell_count()).map(CellId) {
+        builder.prefix = netlist.cell_name(cell).to_string();
+        let inputs = netlist
+            .cell_inputs(cell)
+            .iter()
+            .map(|net| nets[net])
+            .collect::<Vec<usize>>();
+        let outputs = builder.component(netlist.component_mut(cell), &inputs)?;
+        for (value, net) in outputs.into_iter().zip(netlist.cell_outputs(cell)) {
+            builder.drive(value, nets[net])?;
+        }
+    }
+    Ok(Mapping {
+        transistors_before: stats(netlist, 0).total().transistors,
+        ..builder.finish()
+    })
+}
+
+type Operator = fn(&mut Builder, usize, usize) -> usize;
+
+/// Gates of the target technology on numbered nets, only turned into a netlist at the end so
+/// that a gate output can still be merged with the named net it computes.
+struct Builder {
+    technology: Technology,
+    /// Name of the cell being mapped, prefixing its gate names.
+    prefix: String,
+    names: Vec<Option<String>>,
+    parents: Vec<usize>,
+    driven: Vec<bool>,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+    gates: Vec<(String, usize, usize, usize)>,
+    hashed: HashMap<(usize, usize), usize>,
+    inverses: HashMap<usize, usize>,
+}
+
+impl Builder {
+    fn new(technology: Technology) -> Self {
+        Builder {
+            technology,
+            prefix: String::new(),
+            names: Vec::new(),
+            parents: Vec::new(),
+            driven: Vec::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            gates: Vec::new(),
+            hashed: HashMap::new(),
+            inverses: HashMap::new(),
+        }
+    }
+
+    fn net(&mut self, name: &str) -> usize {
+        self.new_net(Some(name.to_string()))
+    }
+
+    fn new_net(&mut self, name: Option<String>) -> usize {
+        self.names.push(name);
+        self.parents.push(self.parents.len());
+        self.driven.push(false);
+        self.names.len() - 1
+    }
+
+    fn input(&mut self, name: &str) -> usize {
+        let net = self.net(name);
+        self.driven[net] = true;
+        self.inputs.push(net);
+        net
+    }
+
+    fn output(&mut self, name: &str) -> usize {
+        let net = self.net(name);
+        self.outputs.push(net);
+        net
+    }
+
+    fn find(&mut self, net: usize) -> usize {
+        let mut root = net;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        self.parents[net] = root;
+        root
+    }
+
+    /// A new gate of the target technology, unless the same one exists.
+    fn gate(&mut self, a: usize, b: usize) -> usize {
+        let (a, b) = (self.find(a), self.find(b));
+        let key = (a.min(b), a.max(b));
+        if let Some(&net) = self.hashed.get(&key) {
+            return net;
+        }
+        let net = self.raw_gate(a, b);
+        self.hashed.insert(key, net);
+        net
+    }
+
+    fn raw_gate(&mut self, a: usize, b: usize) -> usize {
+        let kind = match self.technology {
+            Technology::Nand => "nand",
+            Technology::Nor => "nor",
+        };
+        let name = if self.prefix.is_empty() {
+            format!("{}{}", kind, self.gates.len())
+        } else {
+            format!("{}_{}{}", self.prefix, kind, self.gates.len())
+        };
+        let net = self.new_net(None);
+        self.driven[net] = true;
+        self.gates.push((name, a, b, net));
+        net
+    }
+
+    fn not(&mut self, net: usize) -> usize {
+        let net = self.find(net);
+        if let Some(&inverse) = self.inverses.get(&net) {
+            return inverse;
+        }
+        let inverse = self.gate(net, net);
+        self.inverses.insert(net, inverse);
+        self.inverses.insert(inverse, net);
+        inverse
+    }
+
+    fn and(&mut self, a: usize, b: usize) -> usize {
+        match self.technology {
+            Technology::Nand => {
+                let nand = self.gate(a, b);
+                self.not(nand)
+            }
+            Technology::Nor => {
+                let (a, b) = (self.not(a), self.not(b));
+                self.gate(a, b)
+            }
+        }
+    }
+
+    fn or(&mut self, a: usize, b: usize) -> usize {
+        match self.technology {
+            Technology::Nand => {
+                let (a, b) = (self.not(a), self.not(b));
+                self.gate(a, b)
+            }
+            Technology::Nor => {
+                let nor = self.gate(a, b);
+                self.not(nor)
+            }
+        }
+    }
+
+    /// Four gates give a Xor from NANDs, or a Xnor from NORs.
+    fn xor(&mut self, a: usize, b: usize) -> usize {
+        let both = self.gate(a, b);
+        let (a, b) = (self.gate(a, both), self.gate(b, both));
+        let four = self.gate(a, b);
+        match self.technology {
+            Technology::Nand => four,
+            Technology::Nor => self.not(four),
+        }
+    }
+
+    /// Pairs the nets level by level like [`GateN`](crate::electronic::circuits::logic_gates::gate_n::GateN).
+    fn reduce(&mut self, nets: &[usize], f: Operator) -> usize {
+        let mut nets = nets.to_vec();
+        while nets.len() > 1 {
+            nets = nets
+                .chunks(2)
+                .map(|pair| match *pair {
+                    [a, b] => f(self, a, b),
+                    [a] => a,
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        nets[0]
+    }
+
+    fn not_of(&mut self, nets: &[usize], f: Operator) -> usize {
+        let net = self.reduce(nets, f);
+        self.not(net)
+    }
+
+    /// Output nets of `component` over the `inputs` nets.
+    fn component(
+        &mut self,
+        component: &mut dyn Component,
+        inputs: &[usize],
+    ) -> Result<Vec<usize>, Error> {
+        let outputs = match component.gate() {
+            Some(Gate::Not) => vec![self.not(inputs[0])],
+            Some(Gate::And) => vec![self.reduce(inputs, Builder::and)],
+            Some(Gate::Nand) => vec![self.not_of(inputs, Builder::and)],
+            Some(Gate::Or) => vec![self.reduce(inputs, Builder::or)],
+            Some(Gate::Nor) => vec![self.not_of(inputs, Builder::or)],
+            Some(Gate::Xor) => vec![self.reduce(inputs, Builder::xor)],
+            Some(Gate::Xnor) => vec![self.not_of(inputs, Builder::xor)],
+            None => {
+                let wires = component.wires();
+                let loads = wires.iter().map(|(_, to)| to).collect::<HashSet<&Pin>>();
+                if wires.is_empty() || loads.len() < wires.len() {
+                    let table = truth_table(component)?;
+                    (0..table.output_names().len())
+                        .map(|output| self.two_level(&table, output, inputs))
+                        .collect::<Result<Vec<usize>, Error>>()?
+                } else {
+                    self.structure(component, &wires, inputs)?
+                }
+            }
+        };
+        Ok(outputs)
+    }
+
+    /// Children of `component` mapped along its `wires`, each once its inputs are known. A
+    /// child in a loop reads the outputs of the later ones from placeholder nets, driven when
+    /// they are mapped.
+    fn structure(
+        &mut self,
+        component: &mut dyn Component,
+        wires: &[(Pin, Pin)],
+        inputs: &[usize],
+    ) -> Result<Vec<usize>, Error> {
+        let drivers = wires
+            .iter()
+            .map(|(from, to)| (to, from))
+            .collect::<HashMap<&Pin, &Pin>>();
+        let mut nets = component
+            .input_names()
+            .into_iter()
+            .map(Pin::Port)
+            .zip(inputs.iter().copied())
+            .collect::<HashMap<Pin, usize>>();
+        let mut placeholders = HashMap::new();
+        let prefix = self.prefix.clone();
+
+        let mut children = component.sub_components_mut();
+        let mut pending = (0..children.len()).collect::<Vec<usize>>();
+        while !pending.is_empty() {
+            let known = |pin: &Pin| match drivers.get(pin) {
+                Some(Pin::Child(..)) => nets.contains_key(drivers[pin]),
+                _ => true,
+            };
+            let ready = pending.iter().position(|&i| {
+                let (name, child) = &children[i];
+                child
+                    .input_names()
+                    .into_iter()
+                    .all(|input| known(&Pin::Child(name.clone(), input)))
+            });
+            let (name, child) = &mut children[pending.remove(ready.unwrap_or(0))];
+
+            let mut child_inputs = Vec::new();
+            for input in child.input_names() {
+                let load = Pin::Child(name.clone(), input);
+                let net = match drivers.get(&load) {
+                    None => self.constant(false)?,
+                    Some(Pin::Constant(level)) => self.constant(*level)?,
+                    Some(&driver) => match nets.get(driver) {
+                        Some(&net) => net,
+                        None => *placeholders
+                            .entry(driver.clone())
+                            .or_insert_with(|| self.new_net(None)),
+                    },
+                };
+                child_inputs.push(net);
+            }
+            self.prefix = join_path(&prefix, name);
+            let outputs = self.component(&mut **child, &child_inputs)?;
+            for (output, value) in child.output_names().into_iter().zip(outputs) {
+                let pin = Pin::Child(name.clone(), output);
+                if let Some(&placeholder) = placeholders.get(&pin) {
+                    self.drive(value, placeholder)?;
+                }
+                nets.insert(pin, value);
+            }
+        }
+        self.prefix = prefix;
+        drop(children);
+
+        let outputs = component.output_names().into_iter().map(|output| {
+            match drivers.get(&Pin::Port(output)) {
+                None => self.constant(false),
+                Some(Pin::Constant(level)) => self.constant(*level),
+                Some(driver) => Ok(nets[*driver]),
+            }
+        });
+        outputs.collect()
+    }
+
+    /// A net tied to `level`, computed from the first input of the circuit as `a NAND !a` or
+    /// `a NOR !a`, [`Error::NoInputs`] without one.
+    fn constant(&mut self, level: bool) -> Result<usize, Error> {
+        let a = *self.inputs.first().ok_or(Error::NoInputs)?;
+        let inverse = self.not(a);
+        let constant = self.gate(a, inverse);
+        Ok(match (self.technology, level) {
+            (Technology::Nand, true) | (Technology::Nor, false) => constant,
+            _ => self.not(constant),
+        })
+    }
+
+    /// `output` of `table` over the `inputs` nets, as NAND-NAND or NOR-NOR.
+    fn two_level(
+        &mut self,
+        table: &TruthTable,
+        output: usize,
+        inputs: &[usize],
+    ) -> Result<usize, Error> {
+        let (form, inner, outer): (_, Operator, Operator) = match self.technology {
+            Technology::Nand => (Form::SumOfProducts, Builder::and, Builder::or),
+            Technology::Nor => (Form::ProductOfSums, Builder::or, Builder::and),
+        };
+        let expression = minimize(table, output, form);
+        let mut terms = Vec::new();
+        for term in &expression.terms {
+            let mut literals = Vec::new();
+            for (input, literal) in term.literals.iter().enumerate() {
+                match literal {
+                    Some(true) => literals.push(inputs[input]),
+                    Some(false) => literals.push(self.not(inputs[input])),
+                    None => {}
+                }
+            }
+            if literals.is_empty() {
+                // A term without literals is a constant, the complement of the empty outer one:
+                // a product is 1, a sum is 0.
+                literals.push(self.constant(self.technology == Technology::Nand)?);
+            }
+            terms.push(self.reduce(&literals, inner));
+        }
+        if terms.is_empty() {
+            terms.push(self.constant(self.technology == Technology::Nor)?);
+        }
+        Ok(self.reduce(&terms, outer))
+    }
+
+    /// Makes the named net `target` carry `value`: a gate output with no name yet takes over
+    /// the name, anything else goes through two inverting gates. A net already driven is an
+    /// [`Error::MultipleDrivers`].
+    fn drive(&mut self, value: usize, target: usize) -> Result<(), Error> {
+        let (value, target) = (self.find(value), self.find(target));
+        if value == target {
+            return Ok(());
+        }
+        if self.driven[target] {
+            return Err(Error::MultipleDrivers {
+                net: self.names[target].clone().unwrap_or_default(),
+            });
+        }
+        let value = if self.names[value].is_none() {
+            value
+        } else {
+            let inverse = self.raw_gate(value, value);
+            self.raw_gate(inverse, inverse)
+        };
+        // The gate output stays the root, keeping the gates and inverses already found for it.
+        self.parents[target] = value;
+        self.names[value] = self.names[target].take();
+        if let Some(inverse) = self.inverses.remove(&target) {
+            if !self.inverses.contains_key(&value) {
+                self.inverses.insert(value, inverse);
+                self.inverses.insert(inverse, value);
+            }
+        }
+        Ok(())
+    }
+
+    /// The netlist of the gates an output depends on.
+    fn finish(mut self) -> Mapping {
+        let mut drivers = HashMap::new();
+        for gate in 0..self.gates.len() {
+            let out = self.find(self.gates[gate].3);
+            drivers.insert(out, gate);
+        }
+        let mut used = vec![false; self.gates.len()];
+        let mut stack = self.outputs.clone();
+        while let Some(net) = stack.pop() {
+            let net = self.find(net);
+            if let Some(&gate) = drivers.get(&net) {
+                if !used[gate] {
+                    used[gate] = true;
+                    stack.extend([self.gates[gate].1, self.gates[gate].2]);
+                }
+            }
+        }
+
+        let mut netlist = GateNetlist::new();
+        let mut nets = HashMap::new();
+        for &net in &self.inputs {
+            let name = self.names[net].clone().expect("inputs are named");
+            nets.insert(net, netlist.input(&name));
+        }
+        for i in 0..self.outputs.len() {
+            let net = self.find(self.outputs[i]);
+            let name = self.names[net].clone().expect("outputs are named");
+            nets.insert(net, netlist.output(&name));
+        }
+        let gates = std::mem::take(&mut self.gates);
+        for (gate, (name, a, b, out)) in gates.into_iter().enumerate() {
+            if !used[gate] {
+                continue;
+            }
+            let ports = [a, b, out].map(|net| {
+                let net = self.find(net);
+                *nets
+                    .entry(net)
+                    .or_insert_with(|| netlist.net(self.names[net].as_deref().unwrap_or(&name)))
+            });
+            let result = match self.technology {
+                Technology::Nand => netlist.add(&name, Nand::new(), &ports[..2], &ports[2..]),
+                Technology::Nor => netlist.add(&name, Nor::new(), &ports[..2], &ports[2..]),
+            };
+            result.expect("gates have two inputs and one output");
+        }
+
+        Mapping {
+            transistors_after: stats(&netlist, 0).total().transistors,
+            transistors_before: 0,
+            netlist,
+            technology: self.technology,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::bits::Bits;
+    use crate::data::logic::Logic;
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::logic_gates::and::And;
+    use crate::electronic::circuits::logic_gates::not::Not;
+    use crate::electronic::circuits::logic_gates::or::Or;
+    use crate::electronic::circuits::logic_gates::xor::Xor;
+    use crate::electronic::circuits::mux::Mux2To1;
+    use crate::electronic::circuits::register::PIPORegister;
+    use crate::electronic::components::tri_state::TriStateBuffer;
+    use crate::electronic::equivalence::equivalent;
+
+    fn only(netlist: &GateNetlist, type_name: &str) -> bool {
+        netlist
+            .sub_components()
+            .iter()
+            .all(|(_, cell)| cell.type_name() == type_name)
+    }
+
+    fn full_adder() -> GateNetlist {
+        let mut netlist = GateNetlist::new();
+        let (a, b) = (netlist.input("a"), netlist.input("b"));
+        let carry_in = netlist.input("carry_in");
+        let sum = netlist.output("sum");
+        let carry_out = netlist.output("carry_out");
+        let (xor0, and0, and1) = (
+            netlist.net("xor0"),
+            netlist.net("and0"),
+            netlist.net("and1"),
+        );
+        netlist.add("xor0", Xor::new(), &[a, b], &[xor0]).unwrap();
+        netlist
+            .add("xor1", Xor::new(), &[xor0, carry_in], &[sum])
+            .unwrap();
+        netlist
+            .add("and0", And::new(), &[carry_in, xor0], &[and0])
+            .unwrap();
+        netlist.add("and1", And::new(), &[a, b], &[and1]).unwrap();
+        netlist
+            .add("or", Or::new(), &[and0, and1], &[carry_out])
+            .unwrap();
+        netlist
+    }
+
+    #[test]
+    fn mapping_netlist() {
+        let mut netlist = full_adder();
+        let mut mapping = map_netlist(&mut netlist, Technology::Nand).unwrap();
+        assert!(only(&mapping.netlist, "Nand"));
+        assert_eq!(mapping.netlist.cell_count(), 9);
+        assert_eq!(mapping.transistors_before, 34);
+        assert_eq!(mapping.difference(), 2);
+        assert_eq!(mapping.to_string(), "NAND-only: 34 -> 36 transistors (+2)");
+        assert!(equivalent(&mut netlist, &mut mapping.netlist).unwrap());
+        assert!(equivalent(&mut FullAdder::new(), &mut mapping.netlist).unwrap());
+
+        let mut mapping = map_netlist(&mut netlist, Technology::Nor).unwrap();
+        assert!(only(&mapping.netlist, "Nor"));
+        assert!(equivalent(&mut netlist, &mut mapping.netlist).unwrap());
+    }
+
+    #[test]
+    fn mapping_component() {
+        // An and-or structure suits NANDs, NORs need its inputs inverted.
+        for (technology, difference) in [(Technology::Nand, -4), (Technology::Nor, 8)] {
+            let mut mux = Mux2To1::new();
+            let mut mapping = map(&mut mux, technology).unwrap();
+            assert!(equivalent(&mut mux, &mut mapping.netlist).unwrap());
+            assert_eq!(mapping.difference(), difference);
+
+            // A constant output and an output equal to an input.
+            let mut netlist = GateNetlist::new();
+            let a = netlist.input("a");
+            let (y, z) = (netlist.output("y"), netlist.output("z"));
+            let not_a = netlist.net("not_a");
+            netlist.add("not", Not::new(), &[a], &[not_a]).unwrap();
+            netlist.add("and", And::new(), &[a, not_a], &[y]).unwrap();
+            netlist.add("or", Or::new(), &[a, a], &[z]).unwrap();
+            let mut mapping = map(&mut netlist, technology).unwrap();
+            assert!(equivalent(&mut netlist, &mut mapping.netlist).unwrap());
+            let mut mapping = map_netlist(&mut netlist, technology).unwrap();
+            assert!(equivalent(&mut netlist, &mut mapping.netlist).unwrap());
+        }
+    }
+
+    #[test]
+    fn mapping_structural() {
+        // Nine NANDs per full adder, without going through the truth table of 17 inputs.
+        let mut adder = BitwiseAdd::new(8);
+        let mapping = map(&mut adder, Technology::Nand).unwrap();
+        assert!(only(&mapping.netlist, "Nand"));
+        assert_eq!(mapping.netlist.cell_count(), 8 * 9);
+        assert_eq!(
+            mapping.to_string(),
+            "NAND-only: 272 -> 288 transistors (+16)"
+        );
+
+        let mut adder = BitwiseAdd::new(3);
+        for technology in [Technology::Nand, Technology::Nor] {
+            let mut mapping = map(&mut adder, technology).unwrap();
+            assert!(equivalent(&mut adder, &mut mapping.netlist).unwrap());
+        }
+
+        // The flip-flop keeps its cross-coupled gates.
+        let mut register = PIPORegister::new(2);
+        register.reset_states();
+        let mut mapping = map(&mut register, Technology::Nand).unwrap();
+        assert!(only(&mapping.netlist, "Nand"));
+        for value in [0b101, 0b011, 0b010, 0b000, 0b111] {
+            let inputs = Bits::from_int(value, Some(3));
+            assert!(register.evaluate_bits(&inputs) == mapping.netlist.evaluate_bits(&inputs));
+        }
+    }
+
+    /// An `Or` of `0` or more inputs that calls itself `And`.
+    struct Impostor(usize);
+
+    impl Component for Impostor {
+        fn type_name(&self) -> &'static str {
+            "And"
+        }
+
+        fn input_names(&self) -> Vec<String> {
+            (0..self.0).map(|i| format!("i{}", i)).collect()
+        }
+
+        fn output_names(&self) -> Vec<String> {
+            vec!["y".to_string()]
+        }
+
+        fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+            self.check_inputs(inputs)?;
+            Ok(Bits::from_slice_b(&[inputs.iter().any(|bit| bit)], None))
+        }
+
+        fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+            Vec::new()
+        }
+
+        fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn mapping_errors() {
+        // Only the crate's own gates are rewritten as gates, whatever the type name.
+        let mut impostor = Impostor(2);
+        let mut mapping = map(&mut impostor, Technology::Nand).unwrap();
+        assert!(equivalent(&mut impostor, &mut mapping.netlist).unwrap());
+
+        // A constant needs an input to be derived from.
+        assert_eq!(
+            map(&mut Impostor(0), Technology::Nor).err(),
+            Some(Error::NoInputs)
+        );
+
+        // A bus shared by two tri-state buffers, which have no gate equivalent either, and
+        // a net driven by two gates.
+        let mut netlist = GateNetlist::new();
+        let [a, b, enable_a, enable_b] =
+            ["a", "b", "enable_a", "enable_b"].map(|name| netlist.input(name));
+        let bus = netlist.output("bus");
+        netlist
+            .add("buffer_a", TriStateBuffer::new(), &[a, enable_a], &[bus])
+            .unwrap();
+        netlist
+            .add("buffer_b", TriStateBuffer::new(), &[b, enable_b], &[bus])
+            .unwrap();
+        assert!(map_netlist(&mut netlist, Technology::Nand).is_err());
+
+        let mut netlist = GateNetlist::new();
+        let [a, b] = ["a", "b"].map(|name| netlist.input(name));
+        let y = netlist.output("y");
+        netlist.add("and", And::new(), &[a, b], &[y]).unwrap();
+        netlist.add("or", Or::new(), &[a, b], &[y]).unwrap();
+        assert_eq!(
+            map_netlist(&mut netlist, Technology::Nand).err(),
+            Some(Error::MultipleDrivers {
+                net: "y".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn mapping_latch() {
+        let mut netlist = GateNetlist::d_flip_flop();
+        let mut mapping = map_netlist(&mut netlist, Technology::Nor).unwrap();
+        assert!(only(&mapping.netlist, "Nor"));
+        // One input changes at a time, the latch being as sensitive to races as the original.
+        let vectors = [
+            (true, true),
+            (true, false),
+            (false, false),
+            (false, true),
+            (false, false),
+        ];
+        for (d, enable) in vectors {
+            let inputs = [Logic::from(d), Logic::from(enable)];
+            assert_eq!(mapping.netlist.simulate(&inputs), netlist.simulate(&inputs));
+        }
+        assert!(
+            mapping
+                .netlist
+                .evaluate_bits(&Bits::from_int(0b11, Some(2)))
+                == Bits::from_int(0b10, Some(2))
+        );
+    }
+}
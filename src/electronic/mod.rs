@@ -1,2 +1,15 @@
+pub mod activity;
+pub mod atpg;
 pub mod circuits;
 pub mod components;
+pub mod dot;
+pub mod equivalence;
+pub mod expression;
+pub mod fault;
+pub mod loops;
+pub mod mapping;
+pub mod stats;
+pub mod synthesis;
+pub mod timing;
+pub mod truth_table;
+pub mod verilog;
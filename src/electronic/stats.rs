@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::electronic::circuits::component::{join_path, Component};
+
+/// Size of one component, including all its descendants.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatsRow {
+    pub path: String,
+    pub type_name: &'static str,
+    pub depth: usize,
+    pub transistors: usize,
+    /// Cells built directly from transistors: `Not`, `Nand`, `Xor`, transmission gates, ...
+    pub gates: usize,
+    pub logic_depth: usize,
+}
+
+/// Hierarchical size of a circuit, one row per component, parents before their children.
+#[derive(Clone, Debug)]
+pub struct StatsReport {
+    pub rows: Vec<StatsRow>,
+    /// Instances of every component type below the top, at all levels.
+    pub counts: BTreeMap<&'static str, usize>,
+}
+
+impl StatsReport {
+    /// The whole component.
+    pub fn total(&self) -> &StatsRow {
+        &self.rows[0]
+    }
+
+    pub fn find(&self, path: &str) -> Option<&StatsRow> {
+        self.rows.iter().find(|row| row.path == path)
+    }
+
+    pub fn count(&self, type_name: &str) -> usize {
+        self.counts.get(type_name).copied().unwrap_or(0)
+    }
+}
+
+/// Counts the transistors, gates and logic depth of `component`, rows down to `max_depth`
+/// levels below it.
+pub fn stats(component: &dyn Component, max_depth: usize) -> StatsReport {
+    let mut counts = BTreeMap::new();
+    let rows = rows(
+        component,
+        max_depth,
+        &mut |path, depth, component| {
+            if depth > 0 {
+                *counts.entry(component.type_name()).or_insert(0) += 1;
+            }
+            StatsRow {
+                path: path.to_string(),
+                type_name: component.type_name(),
+                depth,
+                transistors: component.own_transistors(),
+                gates: usize::from(is_gate(component)),
+                logic_depth: component.logic_depth(),
+            }
+        },
+        &|component, row, child| {
+            row.transistors += child.transistors;
+            if !is_gate(component) {
+                row.gates += child.gates;
+            }
+        },
+    );
+    StatsReport { rows, counts }
+}
+
+fn is_gate(component: &dyn Component) -> bool {
+    component
+        .sub_components()
+        .iter()
+        .any(|(_, sub_component)| is_transistor(*sub_component))
+}
+
+fn is_transistor(component: &dyn Component) -> bool {
+    component.own_transistors() > 0 && component.sub_components().is_empty()
+}
+
+/// One row per component down to `max_depth` levels below `component`, parents before their
+/// children. `own` makes the row of a component alone from its path and depth, and `add`
+/// totals the row of a child into the row of its parent `component`.
+pub(crate) fn rows<R: Clone>(
+    component: &dyn Component,
+    max_depth: usize,
+    own: &mut dyn FnMut(&str, usize, &dyn Component) -> R,
+    add: &dyn Fn(&dyn Component, &mut R, &R),
+) -> Vec<R> {
+    let mut rows = Vec::new();
+    collect(component, "", 0, max_depth, own, add, &mut rows);
+    rows
+}
+
+fn collect<R: Clone>(
+    component: &dyn Component,
+    path: &str,
+    depth: usize,
+    max_depth: usize,
+    own: &mut dyn FnMut(&str, usize, &dyn Component) -> R,
+    add: &dyn Fn(&dyn Component, &mut R, &R),
+    rows: &mut Vec<R>,
+) -> R {
+    let mut row = own(path, depth, component);
+    let index = rows.len();
+    if depth <= max_depth {
+        rows.push(row.clone());
+    }
+    for (name, sub_component) in component.sub_components() {
+        let path = join_path(path, &name);
+        let child = collect(sub_component, &path, depth + 1, max_depth, own, add, rows);
+        add(component, &mut row, &child);
+    }
+    if depth <= max_depth {
+        rows[index] = row.clone();
+    }
+    row
+}
+
+/// The last segment of `path` indented by `depth`, `.` for the top, as the first column of
+/// a report table.
+pub(crate) fn indented_name(path: &str, depth: usize) -> String {
+    let name = path.rsplit('.').next().unwrap_or("");
+    let name = if depth == 0 { "." } else { name };
+    format!("{}{}", "  ".repeat(depth), name)
+}
+
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<40} {:<24} {:>12} {:>8} {:>8}",
+            "path", "type", "transistors", "gates", "depth"
+        )?;
+        for row in &self.rows {
+            writeln!(
+                f,
+                "{:<40} {:<24} {:>12} {:>8} {:>8}",
+                indented_name(&row.path, row.depth),
+                row.type_name,
+                row.transistors,
+                row.gates,
+                row.logic_depth
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:<24} {:>8}", "type", "count")?;
+        for (type_name, count) in &self.counts {
+            writeln!(f, "{:<24} {:>8}", type_name, count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::bitwise::div::BitwiseDiv;
+    use crate::electronic::circuits::decoder::Decoder;
+    use crate::electronic::circuits::logic_gates::not::Not;
+    use crate::electronic::components::netlist::Netlist;
+
+    #[test]
+    fn stats_gates() {
+        let report = stats(&Not::new(), usize::MAX);
+        assert_eq!(report.total().transistors, 2);
+        assert_eq!(report.total().gates, 1);
+        assert_eq!(report.total().logic_depth, 1);
+        assert_eq!(report.count("NMOSTransistor"), 1);
+
+        let report = stats(&Netlist::nand2(), 0);
+        assert_eq!(report.total().transistors, 4);
+        assert_eq!(report.total().gates, 0);
+    }
+
+    #[test]
+    fn stats_full_adder() {
+        let report = stats(&FullAdder::new(), 1);
+        assert_eq!(report.total().transistors, 2 * 8 + 2 * 6 + 6);
+        // 2 Xor, 2 And and 1 Or of two gates each.
+        assert_eq!(report.total().gates, 8);
+        assert_eq!(report.count("Xor"), 2);
+        assert_eq!(report.count("Nand"), 2);
+        // a -> xor0 -> and0 (nand, not) -> or (nor, not).
+        assert_eq!(report.total().logic_depth, 5);
+        assert_eq!(report.find("xor0").unwrap().transistors, 8);
+        assert!(report.find("xor0.nmos_a").is_none());
+
+        let table = report.to_string();
+        assert!(table.contains("FullAdder"));
+        assert!(table.contains("  and1"));
+    }
+
+    #[test]
+    fn stats_scaling() {
+        let adder = |size: usize| stats(&BitwiseAdd::new(size), 0);
+        assert_eq!(
+            adder(8).total().transistors,
+            2 * adder(4).total().transistors
+        );
+        // The first stage's a -> carry_out, then carry_in -> and0 -> or through the other 7.
+        assert_eq!(adder(8).total().logic_depth, 5 + 7 * 4);
+        assert_eq!(adder(4).count("FullAdder"), 4);
+
+        let decoder = stats(&Decoder::new(5), 0);
+        assert_eq!(decoder.count("Decoder2To4"), 8);
+        assert!(decoder.total().logic_depth > stats(&Decoder::new(3), 0).total().logic_depth);
+
+        let divider = stats(&BitwiseDiv::new(8), 0);
+        assert_eq!(divider.count("FullSubtractorRestore"), 64);
+        assert!(divider.total().transistors > adder(8).total().transistors);
+    }
+}
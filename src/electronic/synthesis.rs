@@ -0,0 +1,444 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::zip;
+
+use crate::electronic::circuits::gate_netlist::{GateNetlist, NetId};
+use crate::electronic::circuits::logic_gates::and::And;
+use crate::electronic::circuits::logic_gates::gate_n::{AndN, OrN};
+use crate::electronic::circuits::logic_gates::not::Not;
+use crate::electronic::circuits::logic_gates::or::Or;
+use crate::electronic::expression::{Expression, Form, Term};
+use crate::electronic::truth_table::TruthTable;
+use crate::error::Error;
+
+/// Prime implicants of `output` by Quine–McCluskey: minterms and don't-cares are merged
+/// pairwise while they differ in a single literal, the terms that merge no further are prime.
+pub fn prime_implicants(table: &TruthTable, output: usize) -> Vec<Term> {
+    let size = table.input_names().len();
+    let mut terms = table
+        .minterms(output)
+        .into_iter()
+        .chain(table.dont_cares(output))
+        .map(|value| Term::minterm(value, size))
+        .collect::<Vec<Term>>();
+    let mut primes = Vec::new();
+
+    while !terms.is_empty() {
+        let mut merged = vec![false; terms.len()];
+        let mut next = HashSet::new();
+        for i in 0..terms.len() {
+            for j in i + 1..terms.len() {
+                if let Some(term) = merge(&terms[i], &terms[j]) {
+                    merged[i] = true;
+                    merged[j] = true;
+                    next.insert(term);
+                }
+            }
+        }
+        primes.extend(
+            terms
+                .into_iter()
+                .zip(merged)
+                .filter(|(_, merged)| !merged)
+                .map(|(term, _)| term),
+        );
+        terms = next.into_iter().collect();
+    }
+    primes.sort_by(|a, b| b.literals.cmp(&a.literals));
+    primes
+}
+
+/// The term covering both `a` and `b`, if they only differ in the value of one literal.
+fn merge(a: &Term, b: &Term) -> Option<Term> {
+    let mut differing = None;
+    for (i, (a, b)) in a.literals.iter().zip(&b.literals).enumerate() {
+        match (a, b) {
+            _ if a == b => {}
+            (Some(_), Some(_)) if differing.is_none() => differing = Some(i),
+            _ => return None,
+        }
+    }
+    differing.map(|i| {
+        let mut term = a.clone();
+        term.literals[i] = None;
+        term
+    })
+}
+
+/// Minimal two-level expression of `output` in the given `form`, the don't-cares taking
+/// whichever value makes it smaller.
+///
+/// The minterms are covered by as few prime implicants as possible, then as few literals, with
+/// an exact branch and bound search meant for the small tables of teaching material. A product
+/// of sums is the complement of the minimal sum of products of the maxterms.
+pub fn minimize(table: &TruthTable, output: usize, form: Form) -> Expression {
+    let terms = match form {
+        Form::SumOfProducts => cover(&prime_implicants(table, output), &table.minterms(output)),
+        Form::ProductOfSums => {
+            let complement = complement(table, output);
+            cover(&prime_implicants(&complement, 0), &complement.minterms(0))
+                .iter()
+                .map(Term::complement)
+                .collect()
+        }
+    };
+    Expression {
+        form,
+        inputs: table.input_names().to_vec(),
+        terms,
+    }
+}
+
+/// Single-output table of the complement of `output`, with the same don't-cares.
+fn complement(table: &TruthTable, output: usize) -> TruthTable {
+    TruthTable::from_minterms(
+        table.input_names().to_vec(),
+        &table.output_names()[output],
+        &table.maxterms(output),
+        &table.dont_cares(output),
+    )
+    .expect("the table already has as many inputs")
+}
+
+/// Smallest set of `primes` covering every minterm.
+fn cover(primes: &[Term], minterms: &[usize]) -> Vec<Term> {
+    let covering = minterms
+        .iter()
+        .map(|&minterm| {
+            (0..primes.len())
+                .filter(|&prime| primes[prime].covers(minterm))
+                .collect()
+        })
+        .collect::<Vec<Vec<usize>>>();
+    let mut best: Option<((usize, usize), Vec<usize>)> = None;
+    search(primes, &covering, &mut Vec::new(), &mut best);
+    best.map_or_else(Vec::new, |(_, mut chosen)| {
+        chosen.sort();
+        chosen
+            .into_iter()
+            .map(|prime| primes[prime].clone())
+            .collect()
+    })
+}
+
+/// Branches on the primes covering the uncovered minterm with the fewest of them, keeping the
+/// cheapest cover in terms then literals.
+fn search(
+    primes: &[Term],
+    covering: &[Vec<usize>],
+    chosen: &mut Vec<usize>,
+    best: &mut Option<((usize, usize), Vec<usize>)>,
+) {
+    let cost = (
+        chosen.len(),
+        chosen
+            .iter()
+            .map(|&prime| primes[prime].literal_count())
+            .sum::<usize>(),
+    );
+    if best.as_ref().is_some_and(|(best, _)| cost >= *best) {
+        return;
+    }
+    let uncovered = covering
+        .iter()
+        .filter(|primes| !primes.iter().any(|prime| chosen.contains(prime)))
+        .min_by_key(|primes| primes.len());
+    match uncovered {
+        None => *best = Some((cost, chosen.clone())),
+        Some(candidates) => {
+            for &prime in candidates {
+                chosen.push(prime);
+                search(primes, covering, chosen, best);
+                chosen.pop();
+            }
+        }
+    }
+}
+
+/// Builds a netlist of `Not`, `And` and `Or` gates computing every output of `table` from its
+/// minimal sum of products, with the same port names. Inverted inputs and products are shared
+/// between outputs, wide products and sums use `AndN` and `OrN` trees.
+///
+/// An output equal to an input goes through a buffer of two inverters, a constant output is
+/// an input ANDed or ORed with its complement. Internal nets and cells get a numeric suffix
+/// where their name is already taken, so they never merge with a port of the same name. A
+/// table without inputs has nothing to build constants from, an [`Error::NoInputs`].
+pub fn synthesize(table: &TruthTable) -> Result<GateNetlist, Error> {
+    let mut synthesis = Synthesis {
+        netlist: GateNetlist::new(),
+        inputs: Vec::new(),
+        inverted: HashMap::new(),
+        products: HashMap::new(),
+    };
+    for name in table.input_names() {
+        let net = synthesis.netlist.input(name);
+        synthesis.inputs.push(net);
+    }
+    let outputs = table
+        .output_names()
+        .iter()
+        .map(|name| synthesis.netlist.output(name))
+        .collect::<Vec<NetId>>();
+    for (output, (name, net)) in zip(table.output_names(), outputs).enumerate() {
+        let expression = minimize(table, output, Form::SumOfProducts);
+        synthesis.output(&expression.terms, name, net)?;
+    }
+    Ok(synthesis.netlist)
+}
+
+struct Synthesis {
+    netlist: GateNetlist,
+    inputs: Vec<NetId>,
+    inverted: HashMap<usize, NetId>,
+    products: HashMap<Term, NetId>,
+}
+
+impl Synthesis {
+    const PORTS: &'static str = "synthesized cells have matching ports";
+
+    fn output(&mut self, terms: &[Term], name: &str, net: NetId) -> Result<(), Error> {
+        let first = *self.inputs.first().ok_or(Error::NoInputs)?;
+        match terms {
+            [] => {
+                let inverted = self.inverted(0);
+                self.add_and(&format!("{}_zero", name), &[first, inverted], net);
+            }
+            [term] if term.literal_count() == 0 => {
+                let inverted = self.inverted(0);
+                self.add_or(&format!("{}_one", name), &[first, inverted], net);
+            }
+            [term] if term.literal_count() == 1 => {
+                let (input, value) = term
+                    .literals
+                    .iter()
+                    .enumerate()
+                    .find_map(|(input, literal)| literal.map(|value| (input, value)))
+                    .expect("one literal");
+                let source = if value {
+                    let buffered = self.net(&format!("{}_buffer", name));
+                    self.add_not(&format!("{}_buffer0", name), self.inputs[input], buffered);
+                    buffered
+                } else {
+                    self.inputs[input]
+                };
+                self.add_not(&format!("{}_buffer1", name), source, net);
+            }
+            [term] => {
+                let literals = self.literals(term);
+                self.add_and(&format!("and_{}", name), &literals, net);
+            }
+            terms => {
+                let products = terms
+                    .iter()
+                    .map(|term| self.product(term))
+                    .collect::<Vec<NetId>>();
+                self.add_or(&format!("or_{}", name), &products, net);
+            }
+        }
+        Ok(())
+    }
+
+    /// Net of `term` as a product, built once and shared.
+    fn product(&mut self, term: &Term) -> NetId {
+        if let Some(&net) = self.products.get(term) {
+            return net;
+        }
+        let literals = self.literals(term);
+        let net = if let [literal] = literals[..] {
+            literal
+        } else {
+            let name = format!("and{}", self.products.len());
+            let net = self.net(&name);
+            self.add_and(&name, &literals, net);
+            net
+        };
+        self.products.insert(term.clone(), net);
+        net
+    }
+
+    fn literals(&mut self, term: &Term) -> Vec<NetId> {
+        term.literals
+            .iter()
+            .enumerate()
+            .filter_map(|(input, literal)| match literal {
+                Some(true) => Some(self.inputs[input]),
+                Some(false) => Some(self.inverted(input)),
+                None => None,
+            })
+            .collect()
+    }
+
+    fn inverted(&mut self, input: usize) -> NetId {
+        if let Some(&net) = self.inverted.get(&input) {
+            return net;
+        }
+        let name = format!("not_{}", self.netlist.net_name(self.inputs[input]));
+        let net = self.net(&name);
+        self.add_not(&name, self.inputs[input], net);
+        self.inverted.insert(input, net);
+        net
+    }
+
+    /// A new net called `name`, or `name_1`, `name_2`, ... if a net already has that name.
+    fn net(&mut self, name: &str) -> NetId {
+        let name = unique(name, |name| self.netlist.find(name).is_some());
+        self.netlist.net(&name)
+    }
+
+    fn add_not(&mut self, name: &str, input: NetId, output: NetId) {
+        let name = unique(name, |name| self.netlist.cell(name).is_some());
+        self.netlist
+            .add(&name, Not::new(), &[input], &[output])
+            .expect(Synthesis::PORTS);
+    }
+
+    fn add_and(&mut self, name: &str, inputs: &[NetId], output: NetId) {
+        let name = unique(name, |name| self.netlist.cell(name).is_some());
+        let result = match inputs.len() {
+            2 => self.netlist.add(&name, And::new(), inputs, &[output]),
+            size => self.netlist.add(&name, AndN::new(size), inputs, &[output]),
+        };
+        result.expect(Synthesis::PORTS);
+    }
+
+    fn add_or(&mut self, name: &str, inputs: &[NetId], output: NetId) {
+        let name = unique(name, |name| self.netlist.cell(name).is_some());
+        let result = match inputs.len() {
+            2 => self.netlist.add(&name, Or::new(), inputs, &[output]),
+            size => self.netlist.add(&name, OrN::new(size), inputs, &[output]),
+        };
+        result.expect(Synthesis::PORTS);
+    }
+}
+
+/// `name`, or the first of `name_1`, `name_2`, ... that is not `taken`.
+fn unique(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut unique = name.to_string();
+    let mut suffix = 0;
+    while taken(&unique) {
+        suffix += 1;
+        unique = format!("{}_{}", name, suffix);
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::bits::Bits;
+    use crate::data::logic::{LBits, Logic};
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::component::{names, Component};
+    use crate::electronic::stats::stats;
+    use crate::electronic::truth_table::truth_table;
+
+    /// BCD to 7-segment decoder, the codes above 9 never occur.
+    fn seven_segment() -> TruthTable {
+        let digits = [
+            "1111110", "0110000", "1101101", "1111001", "0110011", "1011011", "1011111", "1110000",
+            "1111111", "1111011",
+        ];
+        let rows = (0..16)
+            .map(|value| match digits.get(value) {
+                Some(segments) => segments.parse::<LBits>().unwrap(),
+                None => LBits::filled(Logic::X, 7),
+            })
+            .collect();
+        TruthTable::new(
+            names(&["b3", "b2", "b1", "b0"]),
+            names(&["a", "b", "c", "d", "e", "f", "g"]),
+            rows,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn synthesis_minimize() {
+        let table = seven_segment();
+        let a = minimize(&table, 0, Form::SumOfProducts);
+        assert_eq!(a.to_string(), "b3 | (b2 & b0) | (!b2 & !b0) | b1");
+        for value in 0..10 {
+            let inputs = Bits::from_int(value, Some(4));
+            assert_eq!(
+                Logic::from(a.evaluate(&inputs)),
+                table.value(value as usize, 0)
+            );
+        }
+
+        let table = truth_table(&mut FullAdder::new()).unwrap();
+        let carry_out = minimize(&table, 1, Form::SumOfProducts);
+        assert_eq!(carry_out.terms.len(), 3);
+        assert_eq!(carry_out.literal_count(), 6);
+        let carry_out = minimize(&table, 1, Form::ProductOfSums);
+        assert_eq!(
+            carry_out.to_string(),
+            "(a | b) & (a | carry_in) & (b | carry_in)"
+        );
+        assert_eq!(
+            TruthTable::from_expression(&carry_out, "carry_out")
+                .unwrap()
+                .rows(),
+            TruthTable::from_expression(&table.sum_of_products(1), "carry_out")
+                .unwrap()
+                .rows()
+        );
+
+        // Cyclic cover: six primes of two literals, three are enough.
+        let table =
+            TruthTable::from_minterms(names(&["a", "b", "c"]), "y", &[0, 1, 2, 5, 6, 7], &[])
+                .unwrap();
+        assert_eq!(prime_implicants(&table, 0).len(), 6);
+        assert_eq!(minimize(&table, 0, Form::SumOfProducts).terms.len(), 3);
+
+        let table = TruthTable::from_minterms(names(&["a", "b"]), "y", &[], &[3]).unwrap();
+        assert_eq!(minimize(&table, 0, Form::SumOfProducts).to_string(), "0");
+        assert_eq!(minimize(&table, 0, Form::ProductOfSums).to_string(), "0");
+    }
+
+    #[test]
+    fn synthesis_netlist() {
+        let mut full_adder = FullAdder::new();
+        let table = truth_table(&mut full_adder).unwrap();
+        let mut netlist = synthesize(&table).unwrap();
+        assert_eq!(truth_table(&mut netlist).unwrap(), table);
+
+        let table = seven_segment();
+        let mut netlist = synthesize(&table).unwrap();
+        for value in 0..10 {
+            let outputs = netlist.evaluate_bits(&Bits::from_int(value, Some(4)));
+            assert!(LBits::from(outputs) == table.rows()[value as usize]);
+        }
+        // The 7 outputs share their inverters, b3 is never complemented.
+        let report = stats(&netlist, 1);
+        assert_eq!(
+            report
+                .rows
+                .iter()
+                .filter(|row| row.depth == 1 && row.type_name == "Not")
+                .count(),
+            3
+        );
+
+        let table = TruthTable::from_minterms(names(&["a", "b"]), "y", &[2, 3], &[]).unwrap();
+        let mut netlist = synthesize(&table).unwrap();
+        assert_eq!(truth_table(&mut netlist).unwrap(), table);
+        let table = TruthTable::from_minterms(names(&["a"]), "y", &[0, 1], &[]).unwrap();
+        let mut netlist = synthesize(&table).unwrap();
+        assert_eq!(truth_table(&mut netlist).unwrap(), table);
+
+        // Inputs named like the product and inverter nets stay separate nets.
+        let table =
+            TruthTable::from_minterms(names(&["and0", "b", "not_b"]), "y", &[2, 3, 4, 5], &[])
+                .unwrap();
+        let mut netlist = synthesize(&table).unwrap();
+        assert_eq!(truth_table(&mut netlist).unwrap(), table);
+        assert!(netlist.find("and0_1").is_some());
+        assert!(netlist.find("not_b_1").is_some());
+
+        let table = TruthTable::new(
+            Vec::new(),
+            names(&["y"]),
+            vec![LBits::from_vector(vec![Logic::One])],
+        );
+        assert_eq!(synthesize(&table.unwrap()).err(), Some(Error::NoInputs));
+    }
+}
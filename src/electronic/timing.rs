@@ -0,0 +1,278 @@
+use crate::data::bits::Bits;
+use crate::electronic::circuits::component::Component;
+use crate::error::Error;
+
+/// Simulation time, in picoseconds.
+pub type Time = u64;
+
+/// Switching time of a single transistor. PMOS are slower because holes are less mobile than
+/// electrons.
+pub const NMOS_DELAY: Time = 10;
+pub const PMOS_DELAY: Time = 20;
+
+/// Propagation delay of a gate output going high (`rise`) or low (`fall`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Delay {
+    pub rise: Time,
+    pub fall: Time,
+}
+
+impl Delay {
+    pub fn new(rise: Time, fall: Time) -> Self {
+        Delay { rise, fall }
+    }
+
+    /// Delay of an output changing to `value`.
+    pub fn to(&self, value: bool) -> Time {
+        if value {
+            self.rise
+        } else {
+            self.fall
+        }
+    }
+}
+
+/// Value of a signal over time: its value before time 0 and the time-stamped changes after.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Waveform {
+    initial: bool,
+    events: Vec<(Time, bool)>,
+}
+
+impl Waveform {
+    pub fn constant(value: bool) -> Self {
+        Waveform {
+            initial: value,
+            events: Vec::new(),
+        }
+    }
+
+    /// Changes from `from` to `to` at time `at`, or stays constant if they are equal.
+    pub fn step(from: bool, to: bool, at: Time) -> Self {
+        let mut waveform = Waveform::constant(from);
+        waveform.schedule(at, to);
+        waveform
+    }
+
+    /// One step waveform per bit, MSB first, all switching at time `at`.
+    pub fn bus_step(from: &Bits, to: &Bits, at: Time) -> Vec<Waveform> {
+        from.iter()
+            .zip(to.iter())
+            .map(|(from, to)| Waveform::step(from, to, at))
+            .collect()
+    }
+
+    pub fn initial(&self) -> bool {
+        self.initial
+    }
+
+    /// The changes, in time order. Consecutive events always have different values.
+    pub fn events(&self) -> &[(Time, bool)] {
+        &self.events
+    }
+
+    pub fn value_at(&self, time: Time) -> bool {
+        self.events
+            .iter()
+            .take_while(|&&(at, _)| at <= time)
+            .last()
+            .map_or(self.initial, |&(_, value)| value)
+    }
+
+    pub fn final_value(&self) -> bool {
+        self.events.last().map_or(self.initial, |&(_, value)| value)
+    }
+
+    /// Time of the last change, 0 for a constant.
+    pub fn settle_time(&self) -> Time {
+        self.events.last().map_or(0, |&(at, _)| at)
+    }
+
+    /// Number of changes. More than one for a single input transition is a glitch.
+    pub fn transitions(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Transport-delay scheduling: the new event cancels any change scheduled at or after it.
+    fn schedule(&mut self, at: Time, value: bool) {
+        while self.events.last().is_some_and(|&(time, _)| time >= at) {
+            self.events.pop();
+        }
+        if self.final_value() != value {
+            self.events.push((at, value));
+        }
+    }
+}
+
+/// Output of a gate computing `f` over `inputs`, each change of `f` appearing after `delay`.
+///
+/// The gate is assumed settled before time 0.
+pub fn propagate(
+    inputs: &[&Waveform],
+    delay: Delay,
+    mut f: impl FnMut(&[bool]) -> bool,
+) -> Waveform {
+    let initials = inputs
+        .iter()
+        .map(|waveform| waveform.initial())
+        .collect::<Vec<bool>>();
+    let mut output = Waveform::constant(f(&initials));
+
+    for time in event_times(inputs) {
+        let values = inputs
+            .iter()
+            .map(|waveform| waveform.value_at(time))
+            .collect::<Vec<bool>>();
+        let value = f(&values);
+        output.schedule(time + delay.to(value), value);
+    }
+    output
+}
+
+/// Zero-delay evaluation of any component on waveforms, used by components without a timing
+/// model.
+pub fn instant<C: Component + ?Sized>(
+    component: &mut C,
+    inputs: &[Waveform],
+) -> Result<Vec<Waveform>, Error> {
+    component.check_waveforms(inputs)?;
+    let inputs = inputs.iter().collect::<Vec<&Waveform>>();
+    let sample = |component: &mut C, values: &[bool]| {
+        component.try_evaluate_bits(&Bits::from_slice_b(values, None))
+    };
+
+    let initials = inputs
+        .iter()
+        .map(|waveform| waveform.initial())
+        .collect::<Vec<bool>>();
+    let mut outputs = sample(component, &initials)?
+        .iter()
+        .map(Waveform::constant)
+        .collect::<Vec<Waveform>>();
+    for time in event_times(&inputs) {
+        let values = inputs
+            .iter()
+            .map(|waveform| waveform.value_at(time))
+            .collect::<Vec<bool>>();
+        for (output, value) in outputs.iter_mut().zip(sample(component, &values)?.iter()) {
+            output.schedule(time, value);
+        }
+    }
+    Ok(outputs)
+}
+
+/// Time for every output of `component` to settle after its inputs switch from `from` to `to`
+/// at time 0.
+pub fn settle_time(component: &mut dyn Component, from: &Bits, to: &Bits) -> Result<Time, Error> {
+    Error::check_width("to", from.len(), to.len())?;
+    let outputs = component.evaluate_waveforms(&Waveform::bus_step(from, to, 0))?;
+    Ok(outputs.iter().map(Waveform::settle_time).max().unwrap_or(0))
+}
+
+fn event_times(inputs: &[&Waveform]) -> Vec<Time> {
+    let mut times = inputs
+        .iter()
+        .flat_map(|waveform| waveform.events().iter().map(|&(time, _)| time))
+        .collect::<Vec<Time>>();
+    times.sort_unstable();
+    times.dedup();
+    times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::decoder::Decoder;
+    use crate::electronic::circuits::logic_gates::nand::Nand;
+    use crate::electronic::circuits::logic_gates::not::Not;
+
+    #[test]
+    fn waveform_schedule() {
+        let mut waveform = Waveform::step(false, true, 10);
+        assert!(!waveform.value_at(9));
+        assert!(waveform.value_at(10));
+        waveform.schedule(30, false);
+        waveform.schedule(20, false);
+        assert_eq!(waveform.events(), &[(10, true), (20, false)]);
+        waveform.schedule(5, false);
+        assert_eq!(waveform.transitions(), 0);
+    }
+
+    #[test]
+    fn propagate_rise_fall() {
+        let input = Waveform::step(false, true, 0);
+        let output = propagate(&[&input], Delay::new(20, 10), |values| !values[0]);
+        assert!(output.initial());
+        assert_eq!(output.events(), &[(10, false)]);
+
+        // A pulse shorter than the difference between rise and fall delays is swallowed.
+        let mut pulse = Waveform::step(true, false, 0);
+        pulse.schedule(5, true);
+        let output = propagate(&[&pulse], Delay::new(20, 10), |values| !values[0]);
+        assert_eq!(output.events(), &[]);
+    }
+
+    #[test]
+    fn gate_delays_from_transistors() {
+        assert_eq!(Not::new().delay(), Delay::new(PMOS_DELAY, NMOS_DELAY));
+        assert_eq!(Nand::new().delay(), Delay::new(PMOS_DELAY, 2 * NMOS_DELAY));
+
+        let mut not = Not::new();
+        not.set_delay(Delay::new(1, 2));
+        let output = not.evaluate_timed(&Waveform::step(false, true, 0));
+        assert_eq!(output.events(), &[(2, false)]);
+    }
+
+    #[test]
+    fn ripple_carry_settle_time() {
+        let mut full_adder = FullAdder::new();
+        let from = Bits::from_int(0b000, Some(3));
+        let to = Bits::from_int(0b111, Some(3));
+        let full_adder_time = settle_time(&mut full_adder, &from, &to).unwrap();
+        assert!(full_adder_time > 0);
+
+        // 0b1111 + 0b0001: the carry ripples through every stage.
+        let settle = |size: usize| {
+            let mut bitwise_add = BitwiseAdd::new(size);
+            let from = Bits::zeros(2 * size + 1);
+            let to = Bits::ones(size)
+                .concat(&Bits::from_int(1, Some(size)))
+                .concat(&Bits::zeros(1));
+            settle_time(&mut bitwise_add, &from, &to).unwrap()
+        };
+        assert!(settle(8) > settle(4));
+        assert!(settle(4) > settle(2));
+
+        let mut bitwise_add = BitwiseAdd::new(4);
+        let from = Bits::zeros(9);
+        let outputs = bitwise_add
+            .evaluate_waveforms(&Waveform::bus_step(&from, &from, 0))
+            .unwrap();
+        assert!(outputs.iter().all(|output| output.transitions() == 0));
+    }
+
+    #[test]
+    fn decoder_glitch() {
+        // 01 -> 10 with the low address bit arriving late: y[3] pulses while both read 1.
+        let mut decoder = Decoder::new(2);
+        let inputs = [
+            Waveform::step(false, true, 0),
+            Waveform::step(true, false, 100),
+            Waveform::constant(true),
+        ];
+        let outputs = decoder.evaluate_waveforms(&inputs).unwrap();
+        let finals = outputs
+            .iter()
+            .map(Waveform::final_value)
+            .collect::<Vec<bool>>();
+        assert!(Bits::from_int(0b0100, Some(4)) == finals);
+        assert_eq!(outputs[0].transitions(), 2);
+        assert!(!outputs[0].initial() && !outputs[0].final_value());
+
+        let from = Bits::from_int(0b011, Some(3));
+        let to = Bits::from_int(0b101, Some(3));
+        assert!(settle_time(&mut decoder, &from, &to).unwrap() > 0);
+    }
+}
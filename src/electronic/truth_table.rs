@@ -0,0 +1,301 @@
+use std::fmt;
+
+use crate::data::bits::Bits;
+use crate::data::logic::{LBits, Logic};
+use crate::electronic::circuits::component::Component;
+use crate::electronic::expression::{Expression, Form, Term};
+use crate::error::Error;
+
+/// Most inputs a table, or an exhaustive check, enumerates the `2^n` values of.
+pub const MAX_TABLE_INPUTS: usize = 20;
+
+/// Number of values of `inputs` inputs, [`Error::TooManyInputs`] above [`MAX_TABLE_INPUTS`].
+pub(crate) fn row_count(inputs: usize) -> Result<usize, Error> {
+    if inputs > MAX_TABLE_INPUTS {
+        return Err(Error::TooManyInputs {
+            inputs,
+            limit: MAX_TABLE_INPUTS,
+        });
+    }
+    Ok(1_usize << inputs)
+}
+
+/// Outputs of a combinational circuit for every input value.
+///
+/// Row `i` holds the outputs for the inputs whose value is `i`, the first input being the MSB.
+/// An `X` output is a don't-care, as in tables written by hand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TruthTable {
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    rows: Vec<LBits>,
+}
+
+impl TruthTable {
+    /// Table of `rows` outputs, one row per input value.
+    pub fn new(inputs: Vec<String>, outputs: Vec<String>, rows: Vec<LBits>) -> Result<Self, Error> {
+        Error::check_width("rows", row_count(inputs.len())?, rows.len())?;
+        for row in &rows {
+            Error::check_width("outputs", outputs.len(), row.len())?;
+        }
+        Ok(TruthTable {
+            inputs,
+            outputs,
+            rows,
+        })
+    }
+
+    /// Single-output table from the input values giving 1 and those that do not matter.
+    pub fn from_minterms(
+        inputs: Vec<String>,
+        output: &str,
+        minterms: &[usize],
+        dont_cares: &[usize],
+    ) -> Result<Self, Error> {
+        let rows = (0..row_count(inputs.len())?)
+            .map(|value| {
+                let output = if minterms.contains(&value) {
+                    Logic::One
+                } else if dont_cares.contains(&value) {
+                    Logic::X
+                } else {
+                    Logic::Zero
+                };
+                LBits::from_vector(vec![output])
+            })
+            .collect();
+        Ok(TruthTable {
+            inputs,
+            outputs: vec![output.to_string()],
+            rows,
+        })
+    }
+
+    /// Single-output table of `expression`, over its inputs.
+    pub fn from_expression(expression: &Expression, output: &str) -> Result<Self, Error> {
+        let size = expression.inputs.len();
+        let rows = (0..row_count(size)?)
+            .map(|value| {
+                let inputs = Bits::try_from_u128(value as u128, Some(size))?;
+                let value = expression.evaluate(&inputs);
+                Ok(LBits::from_vector(vec![Logic::from(value)]))
+            })
+            .collect::<Result<Vec<LBits>, Error>>()?;
+        Ok(TruthTable {
+            inputs: expression.inputs.clone(),
+            outputs: vec![output.to_string()],
+            rows,
+        })
+    }
+
+    pub fn input_names(&self) -> &[String] {
+        &self.inputs
+    }
+
+    pub fn output_names(&self) -> &[String] {
+        &self.outputs
+    }
+
+    pub fn output_index(&self, name: &str) -> Option<usize> {
+        self.outputs.iter().position(|output| output == name)
+    }
+
+    pub fn rows(&self) -> &[LBits] {
+        &self.rows
+    }
+
+    pub fn value(&self, inputs: usize, output: usize) -> Logic {
+        self.rows[inputs][output]
+    }
+
+    /// Input values for which `output` is 1.
+    pub fn minterms(&self, output: usize) -> Vec<usize> {
+        self.values_where(output, Logic::One)
+    }
+
+    /// Input values for which `output` is 0.
+    pub fn maxterms(&self, output: usize) -> Vec<usize> {
+        self.values_where(output, Logic::Zero)
+    }
+
+    pub fn dont_cares(&self, output: usize) -> Vec<usize> {
+        self.values_where(output, Logic::X)
+    }
+
+    /// Canonical sum of products of `output`: one full product per minterm.
+    pub fn sum_of_products(&self, output: usize) -> Expression {
+        Expression {
+            form: Form::SumOfProducts,
+            inputs: self.inputs.clone(),
+            terms: self
+                .minterms(output)
+                .into_iter()
+                .map(|value| Term::minterm(value, self.inputs.len()))
+                .collect(),
+        }
+    }
+
+    /// Canonical product of sums of `output`: one full sum per maxterm.
+    pub fn product_of_sums(&self, output: usize) -> Expression {
+        Expression {
+            form: Form::ProductOfSums,
+            inputs: self.inputs.clone(),
+            terms: self
+                .maxterms(output)
+                .into_iter()
+                .map(|value| Term::maxterm(value, self.inputs.len()))
+                .collect(),
+        }
+    }
+
+    fn values_where(&self, output: usize, value: Logic) -> Vec<usize> {
+        (0..self.rows.len())
+            .filter(|&inputs| self.rows[inputs][output] == value)
+            .collect()
+    }
+}
+
+/// Evaluates the combinational `component` on each of its `2^n` input values, at most
+/// [`MAX_TABLE_INPUTS`] inputs.
+pub fn truth_table(component: &mut dyn Component) -> Result<TruthTable, Error> {
+    let size = component.input_count();
+    let rows = (0..row_count(size)?)
+        .map(|value| {
+            let inputs = Bits::try_from_u128(value as u128, Some(size))?;
+            Ok(LBits::from(component.try_evaluate_bits(&inputs)?))
+        })
+        .collect::<Result<Vec<LBits>, Error>>()?;
+    Ok(TruthTable {
+        inputs: component.input_names(),
+        outputs: component.output_names(),
+        rows,
+    })
+}
+
+/// One column per port, named in the header, with the inputs and outputs split by a `|`.
+impl fmt::Display for TruthTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = |names: &[String]| names.join(" ");
+        writeln!(f, "{} | {}", header(&self.inputs), header(&self.outputs))?;
+        let cells = |names: &[String], values: &mut dyn Iterator<Item = char>| {
+            names
+                .iter()
+                .zip(values)
+                .map(|(name, value)| format!("{:<width$}", value, width = name.len()))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        for (value, row) in self.rows.iter().enumerate() {
+            let size = self.inputs.len();
+            let mut inputs = (0..size).map(|i| {
+                if value >> (size - 1 - i) & 1 == 1 {
+                    '1'
+                } else {
+                    '0'
+                }
+            });
+            let mut outputs = row.iter().map(|value| value.to_char());
+            writeln!(
+                f,
+                "{} | {}",
+                cells(&self.inputs, &mut inputs),
+                cells(&self.outputs, &mut outputs)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::adder::HalfAdder;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::decoder::Decoder2To4;
+    use crate::electronic::circuits::gate_netlist::GateNetlist;
+    use crate::electronic::circuits::logic_gates::and::And;
+    use crate::electronic::circuits::mux::Mux2To1;
+    use crate::electronic::circuits::subtractor::FullSubtractor;
+
+    /// Both canonical forms of every output give back the table.
+    fn check_expressions(table: &TruthTable) {
+        for output in 0..table.output_names().len() {
+            let sum_of_products = table.sum_of_products(output);
+            let product_of_sums = table.product_of_sums(output);
+            for (value, row) in table.rows().iter().enumerate() {
+                let inputs = Bits::from_int(value as u32, Some(table.input_names().len()));
+                assert_eq!(Logic::from(sum_of_products.evaluate(&inputs)), row[output]);
+                assert_eq!(Logic::from(product_of_sums.evaluate(&inputs)), row[output]);
+            }
+        }
+    }
+
+    #[test]
+    fn truth_table_mux() {
+        let table = truth_table(&mut Mux2To1::new()).unwrap();
+        assert_eq!(table.input_names(), &["a", "b", "s"]);
+        assert_eq!(table.minterms(0), vec![0b011, 0b100, 0b110, 0b111]);
+        assert_eq!(table.maxterms(0).len(), 4);
+        assert!(table.dont_cares(0).is_empty());
+        check_expressions(&table);
+    }
+
+    #[test]
+    fn truth_table_expressions() {
+        let table = truth_table(&mut HalfAdder::new()).unwrap();
+        let sum = table.output_index("sum").unwrap();
+        let carry_out = table.output_index("carry_out").unwrap();
+        assert_eq!(
+            table.sum_of_products(sum).to_string(),
+            "(!a & b) | (a & !b)"
+        );
+        assert_eq!(table.sum_of_products(carry_out).to_string(), "a & b");
+        assert_eq!(
+            table.product_of_sums(carry_out).to_string(),
+            "(a | b) & (a | !b) & (!a | b)"
+        );
+        assert_eq!(table.product_of_sums(sum).literal_count(), 4);
+
+        check_expressions(&truth_table(&mut FullSubtractor::new()).unwrap());
+        check_expressions(&truth_table(&mut Decoder2To4::new()).unwrap());
+
+        let table = TruthTable::from_minterms(vec!["a".to_string()], "y", &[], &[1]).unwrap();
+        assert_eq!(table.sum_of_products(0).to_string(), "0");
+        assert_eq!(table.product_of_sums(0).to_string(), "a");
+    }
+
+    #[test]
+    fn truth_table_netlist_and_display() {
+        let mut netlist = GateNetlist::new();
+        let (a, b) = (netlist.input("a"), netlist.input("b"));
+        let y = netlist.output("y");
+        netlist.add("and", And::new(), &[a, b], &[y]).unwrap();
+        let table = truth_table(&mut netlist).unwrap();
+        assert_eq!(table, truth_table(&mut And::new()).unwrap());
+        assert_eq!(
+            table.to_string(),
+            "a b | y\n0 0 | 0\n0 1 | 0\n1 0 | 0\n1 1 | 1\n"
+        );
+
+        assert_eq!(
+            TruthTable::new(vec!["a".to_string()], Vec::new(), Vec::new()),
+            Err(Error::WidthMismatch {
+                input: "rows",
+                expected: 2,
+                actual: 0
+            })
+        );
+
+        // 21 and 128 inputs are refused rather than enumerated or overflowing the shift.
+        let too_many = Err(Error::TooManyInputs {
+            inputs: 21,
+            limit: MAX_TABLE_INPUTS,
+        });
+        assert_eq!(truth_table(&mut BitwiseAdd::new(10)), too_many);
+        let inputs = (0..128).map(|i| format!("i{}", i)).collect();
+        assert!(matches!(
+            TruthTable::from_minterms(inputs, "y", &[], &[]),
+            Err(Error::TooManyInputs { inputs: 128, .. })
+        ));
+    }
+}
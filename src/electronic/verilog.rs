@@ -0,0 +1,691 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::data::bits::Bits;
+use crate::data::logic::LBits;
+use crate::electronic::circuits::component::{Component, Pin};
+use crate::electronic::truth_table::truth_table;
+use crate::error::Error;
+
+/// Reserved words of IEEE 1364-2005, a name equal to one gets a trailing `_`.
+const KEYWORDS: &[&str] = &[
+    "always",
+    "and",
+    "assign",
+    "automatic",
+    "begin",
+    "buf",
+    "bufif0",
+    "bufif1",
+    "case",
+    "casex",
+    "casez",
+    "cell",
+    "cmos",
+    "config",
+    "deassign",
+    "default",
+    "defparam",
+    "design",
+    "disable",
+    "edge",
+    "else",
+    "end",
+    "endcase",
+    "endconfig",
+    "endfunction",
+    "endgenerate",
+    "endmodule",
+    "endprimitive",
+    "endspecify",
+    "endtable",
+    "endtask",
+    "event",
+    "for",
+    "force",
+    "forever",
+    "fork",
+    "function",
+    "generate",
+    "genvar",
+    "highz0",
+    "highz1",
+    "if",
+    "ifnone",
+    "incdir",
+    "include",
+    "initial",
+    "inout",
+    "input",
+    "instance",
+    "integer",
+    "join",
+    "large",
+    "liblist",
+    "library",
+    "localparam",
+    "macromodule",
+    "medium",
+    "module",
+    "nand",
+    "negedge",
+    "nmos",
+    "nor",
+    "noshowcancelled",
+    "not",
+    "notif0",
+    "notif1",
+    "or",
+    "output",
+    "parameter",
+    "pmos",
+    "posedge",
+    "primitive",
+    "pull0",
+    "pull1",
+    "pulldown",
+    "pullup",
+    "pulsestyle_ondetect",
+    "pulsestyle_onevent",
+    "rcmos",
+    "real",
+    "realtime",
+    "reg",
+    "release",
+    "repeat",
+    "rnmos",
+    "rpmos",
+    "rtran",
+    "rtranif0",
+    "rtranif1",
+    "scalared",
+    "showcancelled",
+    "signed",
+    "small",
+    "specify",
+    "specparam",
+    "strong0",
+    "strong1",
+    "supply0",
+    "supply1",
+    "table",
+    "task",
+    "time",
+    "tran",
+    "tranif0",
+    "tranif1",
+    "tri",
+    "tri0",
+    "tri1",
+    "triand",
+    "trior",
+    "trireg",
+    "unsigned",
+    "use",
+    "uwire",
+    "vectored",
+    "wait",
+    "wand",
+    "weak0",
+    "weak1",
+    "while",
+    "wire",
+    "wor",
+    "xnor",
+    "xor",
+];
+
+/// Structural Verilog of `component`, one module per distinct circuit and the top module last.
+///
+/// Modules follow the struct hierarchy through [`Component::wires`], e.g. `FullAdder` instances
+/// `xor0`, `and0`, …. Gates are written as Verilog primitives, the bus ports of a circuit as
+/// vectors. A leaf without a primitive becomes a `case` on its truth table, which evaluates
+/// it and so only suits combinational circuits. Circuits of one type built differently, like
+/// the decoders inside a `Decoder`, get numbered modules: `Decoder`, `Decoder_1`.
+pub fn verilog(component: &mut dyn Component) -> Result<String, Error> {
+    let mut writer = Writer::default();
+    writer.module(component)?;
+    Ok(writer.text)
+}
+
+/// Self-checking testbench for the top module of [`verilog`]: applies each of `vectors`, all
+/// inputs packed as for `evaluate_bits`, for 10 time units and compares the outputs with those
+/// computed here. Vectors are applied in order, so a sequential circuit should start from a
+/// known state and load it before its outputs are read. A circuit without outputs has nothing
+/// to check, an [`Error::NoOutputs`].
+pub fn testbench(component: &mut dyn Component, vectors: &[Bits]) -> Result<String, Error> {
+    if component.output_count() == 0 {
+        return Err(Error::NoOutputs);
+    }
+    let top = Writer::default().module(component)?;
+    let inputs = buses(&component.input_names());
+    let outputs = buses(&component.output_names());
+
+    let mut text = String::new();
+    writeln!(text, "`timescale 1ns / 1ps").unwrap();
+    writeln!(text, "module {}_tb;", top).unwrap();
+    for bus in &inputs {
+        writeln!(
+            text,
+            "    reg {}{};",
+            range(bus.width),
+            identifier(&bus.name)
+        )
+        .unwrap();
+    }
+    for bus in &outputs {
+        writeln!(
+            text,
+            "    wire {}{};",
+            range(bus.width),
+            identifier(&bus.name)
+        )
+        .unwrap();
+    }
+    writeln!(text, "    integer errors = 0;").unwrap();
+    writeln!(text).unwrap();
+    let connections = inputs
+        .iter()
+        .chain(&outputs)
+        .map(|bus| format!(".{0}({0})", identifier(&bus.name)))
+        .collect::<Vec<String>>();
+    writeln!(text, "    {} dut ({});", top, connections.join(", ")).unwrap();
+    writeln!(text).unwrap();
+
+    let concat = |buses: &[Bus]| {
+        let names = buses
+            .iter()
+            .map(|bus| identifier(&bus.name))
+            .collect::<Vec<String>>();
+        format!("{{{}}}", names.join(", "))
+    };
+    let (input_concat, output_concat) = (concat(&inputs), concat(&outputs));
+    writeln!(text, "    initial begin").unwrap();
+    for vector in vectors {
+        component.check_inputs(vector)?;
+        let expected = component.try_evaluate_bits(vector)?;
+        let (vector, expected) = (literal(vector), literal(&expected));
+        if !inputs.is_empty() {
+            writeln!(text, "        {} = {};", input_concat, vector).unwrap();
+        }
+        writeln!(text, "        #10;").unwrap();
+        writeln!(
+            text,
+            "        if ({} !== {}) begin",
+            output_concat, expected
+        )
+        .unwrap();
+        writeln!(
+            text,
+            "            $display(\"FAIL {}: got %b, expected {}\", {});",
+            vector, expected, output_concat
+        )
+        .unwrap();
+        writeln!(text, "            errors = errors + 1;").unwrap();
+        writeln!(text, "        end").unwrap();
+    }
+    writeln!(text, "        $display(\"%0d errors\", errors);").unwrap();
+    writeln!(text, "        $finish;").unwrap();
+    writeln!(text, "    end").unwrap();
+    writeln!(text, "endmodule").unwrap();
+    Ok(text)
+}
+
+/// Verilog gate primitive a component is written as, output first then inputs in order.
+fn primitive(type_name: &str) -> Option<&'static str> {
+    match type_name {
+        "And" | "And3" | "AndN" => Some("and"),
+        "Nand" | "NandN" => Some("nand"),
+        "Or" | "OrN" => Some("or"),
+        "Nor" | "NorN" => Some("nor"),
+        "Xor" | "XorN" => Some("xor"),
+        "Xnor" | "XnorN" => Some("xnor"),
+        "Not" => Some("not"),
+        "TriStateBuffer" | "TransmissionGate" => Some("bufif1"),
+        _ => None,
+    }
+}
+
+/// Port bits `name[i]` grouped into one vector, scalar ports as they are.
+struct Bus {
+    name: String,
+    width: Option<usize>,
+    /// Lowest index grouped so far, the bus being complete once it reaches 0.
+    low: usize,
+    /// Port names, most significant first.
+    bits: Vec<String>,
+}
+
+fn split_index(name: &str) -> Option<(&str, usize)> {
+    let (base, index) = name.strip_suffix(']')?.rsplit_once('[')?;
+    Some((base, index.parse().ok()?))
+}
+
+/// Bits of one bus listed from the most significant down grouped, e.g. `d1[1]`, `d1[0]` into
+/// `d1` of width 2, as
+/// [`bus_names`](crate::electronic::circuits::component::bus_names) lists them.
+fn buses(names: &[String]) -> Vec<Bus> {
+    let mut buses: Vec<Bus> = Vec::new();
+    for name in names {
+        match split_index(name) {
+            Some((base, index)) => match buses.last_mut() {
+                Some(bus) if bus.name == base && bus.width.is_some() && bus.low == index + 1 => {
+                    bus.low = index;
+                    bus.bits.push(name.clone());
+                }
+                _ => buses.push(Bus {
+                    name: base.to_string(),
+                    width: Some(index + 1),
+                    low: index,
+                    bits: vec![name.clone()],
+                }),
+            },
+            None => buses.push(Bus {
+                name: name.clone(),
+                width: None,
+                low: 0,
+                bits: vec![name.clone()],
+            }),
+        }
+    }
+    buses
+}
+
+fn range(width: Option<usize>) -> String {
+    match width {
+        Some(width) => format!("[{}:0] ", width - 1),
+        None => String::new(),
+    }
+}
+
+/// Valid Verilog identifier for a port, net or instance name, e.g. `adders_3` for `adders[3]`.
+fn identifier(name: &str) -> String {
+    let mut identifier = name
+        .replace(']', "")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Reference to a port bit: `d1[3]`, or `a` for a scalar port.
+fn port_bit(name: &str) -> String {
+    match split_index(name) {
+        Some((base, index)) => format!("{}[{}]", identifier(base), index),
+        None => identifier(name),
+    }
+}
+
+fn net(child: &str, port: &str) -> String {
+    identifier(&format!("{}_{}", child, port))
+}
+
+fn literal(bits: &Bits) -> String {
+    let digits = bits
+        .iter()
+        .map(|bit| if bit { '1' } else { '0' })
+        .collect::<String>();
+    format!("{}'b{}", bits.len(), digits)
+}
+
+#[derive(Default)]
+struct Writer {
+    text: String,
+    /// Name, type and body of each module written.
+    modules: Vec<(String, &'static str, String)>,
+}
+
+impl Writer {
+    /// Writes the module of `component` after those it instantiates, unless the same one is
+    /// already written, and returns its name.
+    fn module(&mut self, component: &mut dyn Component) -> Result<String, Error> {
+        let type_name = component.type_name();
+        let wires = component.wires();
+        let body = match primitive(type_name) {
+            Some(primitive) if wires.is_empty() || component.output_count() == 1 => {
+                let mut ports = component.output_names();
+                ports.extend(component.input_names());
+                let ports = ports.iter().map(|port| port_bit(port)).collect::<Vec<_>>();
+                format!("    {} gate ({});\n", primitive, ports.join(", "))
+            }
+            _ if wires.is_empty() => self.behavioural(component)?,
+            _ => self.structural(component, &wires)?,
+        };
+
+        if let Some((name, _, _)) = self
+            .modules
+            .iter()
+            .find(|(_, other, other_body)| *other == type_name && *other_body == body)
+        {
+            return Ok(name.clone());
+        }
+        let variants = self
+            .modules
+            .iter()
+            .filter(|(_, other, _)| *other == type_name)
+            .count();
+        let name = match variants {
+            0 => type_name.to_string(),
+            _ => format!("{}_{}", type_name, variants),
+        };
+
+        let mut ports = Vec::new();
+        for bus in buses(&component.input_names()) {
+            ports.push(format!(
+                "input {}{}",
+                range(bus.width),
+                identifier(&bus.name)
+            ));
+        }
+        for bus in buses(&component.output_names()) {
+            ports.push(format!(
+                "output {}{}",
+                range(bus.width),
+                identifier(&bus.name)
+            ));
+        }
+        writeln!(self.text, "module {} (", name).unwrap();
+        writeln!(self.text, "    {}", ports.join(",\n    ")).unwrap();
+        writeln!(self.text, ");").unwrap();
+        write!(self.text, "{}", body).unwrap();
+        writeln!(self.text, "endmodule").unwrap();
+        writeln!(self.text).unwrap();
+        self.modules.push((name.clone(), type_name, body));
+        Ok(name)
+    }
+
+    /// Children as primitives or module instances, a net per child output bit and the loads
+    /// with several drivers resolved on a net of their own.
+    fn structural(
+        &mut self,
+        component: &mut dyn Component,
+        wires: &[(Pin, Pin)],
+    ) -> Result<String, Error> {
+        let mut drivers: HashMap<&Pin, Vec<String>> = HashMap::new();
+        for (from, to) in wires {
+            let driver = match from {
+                Pin::Port(name) => port_bit(name),
+                Pin::Child(child, port) => net(child, port),
+                Pin::Constant(level) => format!("1'b{}", *level as u8),
+            };
+            drivers.entry(to).or_default().push(driver);
+        }
+
+        let mut nets = Vec::new();
+        let mut instances = Vec::new();
+        let mut assigns = Vec::new();
+        for (child_name, child) in component.sub_components_mut() {
+            // Parts only some variants use, like the `not` of a two-input `Decoder`.
+            let used = |pin: &Pin| matches!(pin, Pin::Child(name, _) if *name == child_name);
+            if !wires.iter().any(|(from, to)| used(from) || used(to)) {
+                continue;
+            }
+            for output in child.output_names() {
+                nets.push(net(&child_name, &output));
+            }
+            let mut input = |port: &str| {
+                let load = Pin::Child(child_name.clone(), port.to_string());
+                match drivers.get(&load).map(Vec::as_slice) {
+                    None | Some([]) => "1'bz".to_string(),
+                    Some([driver]) => driver.clone(),
+                    Some(several) => {
+                        let resolved = net(&child_name, port);
+                        for driver in several {
+                            assigns.push(format!("assign {} = {};", resolved, driver));
+                        }
+                        nets.push(resolved.clone());
+                        resolved
+                    }
+                }
+            };
+
+            let instance = identifier(&child_name);
+            match primitive(child.type_name()) {
+                Some(primitive) if child.output_count() == 1 => {
+                    let mut ports = vec![net(&child_name, &child.output_names()[0])];
+                    ports.extend(child.input_names().iter().map(|port| input(port)));
+                    instances.push(format!(
+                        "{} {} ({});",
+                        primitive,
+                        instance,
+                        ports.join(", ")
+                    ));
+                }
+                _ => {
+                    let mut connections = Vec::new();
+                    for bus in buses(&child.input_names()) {
+                        let bits = bus.bits.iter().map(|port| input(port)).collect();
+                        connections.push(connection(&bus, bits));
+                    }
+                    for bus in buses(&child.output_names()) {
+                        let bits = bus.bits.iter().map(|port| net(&child_name, port)).collect();
+                        connections.push(connection(&bus, bits));
+                    }
+                    let module = self.module(child)?;
+                    instances.push(format!(
+                        "{} {} ({});",
+                        module,
+                        instance,
+                        connections.join(", ")
+                    ));
+                }
+            }
+        }
+        for output in component.output_names() {
+            for driver in drivers
+                .get(&Pin::Port(output.clone()))
+                .into_iter()
+                .flatten()
+            {
+                assigns.push(format!("assign {} = {};", port_bit(&output), driver));
+            }
+        }
+
+        let mut body = String::new();
+        for net in nets {
+            writeln!(body, "    wire {};", net).unwrap();
+        }
+        for line in instances.iter().chain(&assigns) {
+            writeln!(body, "    {}", line).unwrap();
+        }
+        Ok(body)
+    }
+
+    /// A `case` on the truth table of `component`, or a constant `assign` when it has no
+    /// inputs. A component without outputs is an [`Error::NoOutputs`].
+    fn behavioural(&mut self, component: &mut dyn Component) -> Result<String, Error> {
+        let table = truth_table(component)?;
+        let concat = |names: &[String]| {
+            let bits = names.iter().map(|name| port_bit(name)).collect::<Vec<_>>();
+            format!("{{{}}}", bits.join(", "))
+        };
+        let (inputs, outputs) = (table.input_names().len(), table.output_names().len());
+        if outputs == 0 {
+            return Err(Error::NoOutputs);
+        }
+        let row = |row: &LBits| row.iter().map(|value| value.to_char()).collect::<String>();
+
+        let mut body = String::new();
+        if inputs == 0 {
+            writeln!(
+                body,
+                "    assign {} = {}'b{};",
+                concat(table.output_names()),
+                outputs,
+                row(&table.rows()[0])
+            )
+            .unwrap();
+            return Ok(body);
+        }
+        writeln!(body, "    reg [{}:0] value;", outputs - 1).unwrap();
+        writeln!(body, "    assign {} = value;", concat(table.output_names())).unwrap();
+        writeln!(body, "    always @* begin").unwrap();
+        writeln!(body, "        case ({})", concat(table.input_names())).unwrap();
+        for (value, values) in table.rows().iter().enumerate() {
+            let value = Bits::try_from_u128(value as u128, Some(inputs))?;
+            writeln!(
+                body,
+                "            {}: value = {}'b{};",
+                literal(&value),
+                outputs,
+                row(values)
+            )
+            .unwrap();
+        }
+        writeln!(
+            body,
+            "            default: value = {{{}{{1'bx}}}};",
+            outputs
+        )
+        .unwrap();
+        writeln!(body, "        endcase").unwrap();
+        writeln!(body, "    end").unwrap();
+        Ok(body)
+    }
+}
+
+/// Named port connection of a child, a concatenation for a vector port.
+fn connection(bus: &Bus, bits: Vec<String>) -> String {
+    match bus.width {
+        Some(_) => format!(".{}({{{}}})", identifier(&bus.name), bits.join(", ")),
+        None => format!(".{}({})", identifier(&bus.name), bits[0]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electronic::circuits::adder::FullAdder;
+    use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+    use crate::electronic::circuits::decoder::Decoder;
+    use crate::electronic::circuits::register::PIPORegister;
+    use crate::electronic::components::netlist::Netlist;
+
+    #[test]
+    fn verilog_full_adder() {
+        let text = verilog(&mut FullAdder::new()).unwrap();
+        assert!(text.starts_with("module FullAdder (\n    input a,\n    input b,\n"));
+        assert!(text.contains("    wire xor0_y;\n"));
+        assert!(text.contains("    xor xor0 (xor0_y, a, b);\n"));
+        assert!(text.contains("    or or_ (or_y, and0_y, and1_y);\n"));
+        assert!(text.contains("    assign carry_out = or_y;\n"));
+        assert_eq!(text.matches("module ").count(), 1);
+    }
+
+    #[test]
+    fn verilog_hierarchy() {
+        let text = verilog(&mut BitwiseAdd::new(4)).unwrap();
+        assert!(text.contains("module BitwiseAdd (\n    input [3:0] d1,\n"));
+        assert!(text.contains(
+            "    FullAdder adders_1 (.a(d1[1]), .b(d2[1]), .carry_in(adders_0_carry_out), \
+             .sum(adders_1_sum), .carry_out(adders_1_carry_out));\n"
+        ));
+        assert!(text.contains("    assign sum[3] = adders_3_sum;\n"));
+        // One FullAdder module for the four instances, before the top module.
+        assert_eq!(text.matches("module FullAdder (").count(), 1);
+        assert!(text.find("module FullAdder").unwrap() < text.find("module BitwiseAdd").unwrap());
+
+        let text = verilog(&mut Decoder::new(3)).unwrap();
+        assert!(text.contains("module Decoder2To4 ("));
+        assert!(text.contains("    and ands3_0 (ands3_0_y, not1_y, not0_y, enable);\n"));
+        assert!(text.contains("    Decoder2To4 decoder2to4 (.a(a[0]), .b(a[1]), .enable(enable)"));
+        assert!(text.contains("    Decoder upper_half_decoder (.a({a[1], a[0]}), .enable(a[2])"));
+        assert!(text.ends_with("endmodule\n\n"));
+        assert!(text.contains("module Decoder_1 (\n    input [2:0] a,\n    input enable,\n"));
+        assert_eq!(text.matches("    not not_ (").count(), 1);
+    }
+
+    #[test]
+    fn verilog_behavioural_leaf() {
+        let text = verilog(&mut Netlist::nand2()).unwrap();
+        assert!(text.contains("        case ({a, b})\n"));
+        assert!(text.contains("            2'b11: value = 1'b0;\n"));
+    }
+
+    /// Leaf driving its outputs high whatever its inputs.
+    struct Tie {
+        inputs: usize,
+        outputs: usize,
+    }
+
+    impl Component for Tie {
+        fn type_name(&self) -> &'static str {
+            "Tie"
+        }
+
+        fn input_names(&self) -> Vec<String> {
+            (0..self.inputs).map(|i| format!("a{}", i)).collect()
+        }
+
+        fn output_names(&self) -> Vec<String> {
+            (0..self.outputs).map(|i| format!("y{}", i)).collect()
+        }
+
+        fn try_evaluate_bits(&mut self, inputs: &Bits) -> Result<Bits, Error> {
+            self.check_inputs(inputs)?;
+            Ok(Bits::ones(self.outputs))
+        }
+
+        fn sub_components(&self) -> Vec<(String, &dyn Component)> {
+            Vec::new()
+        }
+
+        fn sub_components_mut(&mut self) -> Vec<(String, &mut dyn Component)> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn verilog_edge_leaves() {
+        let text = verilog(&mut Tie {
+            inputs: 0,
+            outputs: 2,
+        })
+        .unwrap();
+        assert!(text.contains("    assign {y0, y1} = 2'b11;\n"));
+        assert!(!text.contains("case"));
+        let mut tie = Tie {
+            inputs: 0,
+            outputs: 1,
+        };
+        let text = testbench(&mut tie, &[Bits::zeros(0)]).unwrap();
+        assert!(!text.contains(" = 0'b"));
+        assert!(text.contains("        if ({y0} !== 1'b1) begin\n"));
+        assert_eq!(
+            verilog(&mut Tie {
+                inputs: 1,
+                outputs: 0
+            }),
+            Err(Error::NoOutputs)
+        );
+        assert_eq!(identifier("table"), "table_");
+        assert_eq!(identifier("wand"), "wand_");
+    }
+
+    #[test]
+    fn verilog_testbench() {
+        let mut register = PIPORegister::new(2);
+        register.reset_states();
+        let vectors = [
+            Bits::from_int(0b101, Some(3)),
+            Bits::from_int(0b011, Some(3)),
+            Bits::from_int(0b010, Some(3)),
+        ];
+        let text = testbench(&mut register, &vectors).unwrap();
+        assert!(text.contains("module PIPORegister_tb;\n    reg [1:0] d;\n    reg enable;\n"));
+        assert!(text.contains("    PIPORegister dut (.d(d), .enable(enable), .q(q));\n"));
+        assert!(text.contains("        {d, enable} = 3'b011;\n        #10;\n"));
+        assert!(text.contains("        if ({q} !== 2'b01) begin\n"));
+        assert!(text.ends_with("        $finish;\n    end\nendmodule\n"));
+
+        let text = verilog(&mut register).unwrap();
+        assert!(text.contains("    nand nand2 (nand2_y, nand0_y, nand3_y);\n"));
+        assert!(text.contains("    DFlipFlop d_flip_flops_1 (.d(d[1]), .enable(enable)"));
+    }
+}
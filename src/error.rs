@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::data::bits::ConversionError;
+use crate::data::literal::ParseBitsError;
+use crate::electronic::fault::Fault;
+
+/// Errors reported by the fallible (`try_`) circuit entry points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The `input` signal does not have the width the circuit was built for.
+    WidthMismatch {
+        input: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// A divider received a zero divisor.
+    DivisionByZero,
+    /// A latch was clocked with set and reset both high, its outputs would be undefined.
+    SetResetConflict,
+    /// A node of a transistor netlist is connected to both VDD and GND.
+    ShortCircuit {
+        node: String,
+    },
+    /// A node of a transistor netlist is connected to no driver.
+    FloatingNode {
+        node: String,
+    },
+    /// A node of a transistor netlist has no known value, e.g. an uninitialized latch.
+    UnknownValue {
+        node: String,
+    },
+    /// The simulation was still changing after `iterations` passes, at these `nodes`.
+    NoConvergence {
+        iterations: usize,
+        nodes: Vec<String>,
+    },
+    /// No sub-component at this hierarchical path.
+    UnknownComponent {
+        path: String,
+    },
+    /// A net id that belongs to no net of the gate netlist it is used with.
+    UnknownNet {
+        net: usize,
+    },
+    /// A net of a gate netlist is driven by more than one cell, which a mapping cannot keep.
+    MultipleDrivers {
+        net: String,
+    },
+    /// A constant is derived from an input, and the circuit has none.
+    NoInputs,
+    /// A circuit has no outputs to describe.
+    NoOutputs,
+    /// A sub-component already has this name.
+    DuplicateComponent {
+        name: String,
+    },
+    /// The component at `path` cannot carry this kind of fault.
+    UnsupportedFault {
+        path: String,
+        fault: Fault,
+    },
+    /// Enumerating every value of `inputs` inputs would take more than `2^limit` rows.
+    TooManyInputs {
+        inputs: usize,
+        limit: usize,
+    },
+    Conversion(ConversionError),
+    Parse(ParseBitsError),
+}
+
+impl Error {
+    /// Checks that `input` has exactly `expected` bits.
+    pub fn check_width(input: &'static str, expected: usize, actual: usize) -> Result<(), Error> {
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(Error::WidthMismatch {
+                input,
+                expected,
+                actual,
+            })
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::WidthMismatch {
+                input,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Length of {} should be {} but is {}",
+                input, expected, actual
+            ),
+            Error::DivisionByZero => write!(f, "Divider cannot be 0"),
+            Error::SetResetConflict => write!(f, "Invalid state: set and reset are both high"),
+            Error::ShortCircuit { node } => write!(f, "Node {} is shorted to VDD and GND", node),
+            Error::FloatingNode { node } => write!(f, "Node {} is floating", node),
+            Error::UnknownValue { node } => write!(f, "Node {} has an unknown value", node),
+            Error::NoConvergence { iterations, nodes } => {
+                write!(
+                    f,
+                    "Simulation did not settle after {} iterations, {} still changing",
+                    iterations,
+                    nodes.join(", ")
+                )
+            }
+            Error::UnknownComponent { path } => write!(f, "No component at {}", path),
+            Error::UnknownNet { net } => write!(f, "No net {} in the netlist", net),
+            Error::MultipleDrivers { net } => write!(f, "Net {} has several drivers", net),
+            Error::NoInputs => write!(f, "Circuit has no inputs to derive constants from"),
+            Error::NoOutputs => write!(f, "Circuit has no outputs"),
+            Error::DuplicateComponent { name } => {
+                write!(f, "A component is already called {}", name)
+            }
+            Error::UnsupportedFault { path, fault } => {
+                write!(f, "Component {} cannot be {}", path, fault)
+            }
+            Error::TooManyInputs { inputs, limit } => write!(
+                f,
+                "Cannot enumerate {} inputs, at most {} are supported",
+                inputs, limit
+            ),
+            Error::Conversion(error) => error.fmt(f),
+            Error::Parse(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Conversion(error) => Some(error),
+            Error::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ConversionError> for Error {
+    fn from(error: ConversionError) -> Self {
+        Error::Conversion(error)
+    }
+}
+
+impl From<ParseBitsError> for Error {
+    fn from(error: ParseBitsError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_display() {
+        let error = Error::WidthMismatch {
+            input: "d1",
+            expected: 4,
+            actual: 3,
+        };
+        assert_eq!(error.to_string(), "Length of d1 should be 4 but is 3");
+
+        let error = Error::from(ConversionError::ValueTooLarge {
+            required: 5,
+            size: 4,
+        });
+        assert!(matches!(error, Error::Conversion(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
@@ -0,0 +1,9 @@
+//! A computer built up from CMOS transistors: logic gates, arithmetic units and sequential
+//! elements, with the [`Bits`](data::bits::Bits) vector type to drive them.
+//!
+//! Most users only need the [`prelude`].
+
+pub mod data;
+pub mod electronic;
+pub mod error;
+pub mod prelude;
@@ -1,6 +1,26 @@
-mod data;
-mod electronic;
+use std::env;
+use std::process;
 
+use computer_rust::prelude::*;
+
+/// Adds two integer literals (`0xA5`, `8'b1010_0101`, `42`...) on a transistor-level adder.
 fn main() {
-    println!("Hello, world!");
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    if args.len() != 2 {
+        eprintln!("usage: computer-rust <a> <b>");
+        process::exit(2);
+    }
+
+    let (a, b) = match (args[0].parse::<Bits>(), args[1].parse::<Bits>()) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(error), _) | (_, Err(error)) => {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    };
+
+    let size = a.len().max(b.len());
+    let mut adder = BitwiseAdd::new(size);
+    let (sum, carry_out) = adder.evaluate(&a.zero_extend(size), &b.zero_extend(size), false);
+    println!("{} + {} = {} (carry {})", a, b, sum, carry_out as u8);
 }
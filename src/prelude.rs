@@ -0,0 +1,56 @@
+//! Commonly used types and traits, for a single `use computer_rust::prelude::*;`. Analyses and
+//! exports are free functions, used through their module: `computer_rust::electronic::stats`.
+
+pub use crate::data::bits::{Bits, BitsView, ConversionError};
+pub use crate::data::literal::{ParseBitsError, Radix};
+pub use crate::data::logic::{LBits, Logic};
+pub use crate::data::signed::Signedness;
+pub use crate::error::Error;
+
+pub use crate::electronic::activity::{ActivityReport, PowerModel};
+pub use crate::electronic::atpg::TestSet;
+pub use crate::electronic::circuits::component::Component;
+pub use crate::electronic::circuits::logic_gates::and::And;
+pub use crate::electronic::circuits::logic_gates::and3::And3;
+pub use crate::electronic::circuits::logic_gates::gate_n::{
+    AndN, GateN, NandN, NorN, OrN, XnorN, XorN,
+};
+pub use crate::electronic::circuits::logic_gates::nand::Nand;
+pub use crate::electronic::circuits::logic_gates::nor::Nor;
+pub use crate::electronic::circuits::logic_gates::not::Not;
+pub use crate::electronic::circuits::logic_gates::or::Or;
+pub use crate::electronic::circuits::logic_gates::xnor::Xnor;
+pub use crate::electronic::circuits::logic_gates::xor::Xor;
+pub use crate::electronic::expression::{Expression, Form, Term};
+
+pub use crate::electronic::circuits::adder::{AdderResult, FullAdder, HalfAdder};
+pub use crate::electronic::circuits::bitwise::add::BitwiseAdd;
+pub use crate::electronic::circuits::bitwise::div::BitwiseDiv;
+pub use crate::electronic::circuits::bitwise::mult::BitwiseMult;
+pub use crate::electronic::circuits::bitwise::sub::BitwiseSub;
+pub use crate::electronic::circuits::bitwise::BitwiseCheck;
+pub use crate::electronic::circuits::decoder::{Decoder, Decoder2To4};
+pub use crate::electronic::circuits::demux::Demux1To2;
+pub use crate::electronic::circuits::mux::{Mux2To1, PassMux2To1};
+pub use crate::electronic::circuits::shifter::BarrelShifter;
+pub use crate::electronic::circuits::subtractor::{
+    FullSubtractor, FullSubtractorRestore, HalfSubtractor, SubtractorResult,
+};
+
+pub use crate::electronic::circuits::flip_flop::DFlipFlop;
+pub use crate::electronic::circuits::power_on::PowerOn;
+pub use crate::electronic::circuits::register::PIPORegister;
+
+pub use crate::electronic::circuits::gate_netlist::{CellId, GateNetlist, NetId};
+
+pub use crate::electronic::components::netlist::{Netlist, NodeId, TransistorKind};
+pub use crate::electronic::components::transistor::{NMOSTransistor, PMOSTransistor, Transistor};
+pub use crate::electronic::components::transmission_gate::TransmissionGate;
+pub use crate::electronic::components::tri_state::TriStateBuffer;
+pub use crate::electronic::dot::{DotOptions, Highlight};
+pub use crate::electronic::fault::Fault;
+pub use crate::electronic::loops::{CombinationalLoop, LoopKind};
+pub use crate::electronic::mapping::{Mapping, Technology};
+pub use crate::electronic::stats::StatsReport;
+pub use crate::electronic::timing::{Delay, Time, Waveform};
+pub use crate::electronic::truth_table::TruthTable;